@@ -0,0 +1,80 @@
+#![feature(test)]
+
+extern crate bellman;
+extern crate ff;
+extern crate futures;
+extern crate pairing;
+extern crate rand;
+extern crate test;
+
+use std::sync::Arc;
+
+use futures::Future;
+use rand::{Rand, SeedableRng, XorShiftRng};
+
+use ff::PrimeField;
+use pairing::{CurveAffine, CurveProjective, Engine};
+use pairing::bls12_381::Bls12;
+
+use bellman::multicore::Worker;
+use bellman::multiexp::{multiexp_with, FixedBaseTable, FullDensity, MultiexpAlgorithm};
+
+const SAMPLES: usize = 1 << 14;
+
+fn samples<G: CurveAffine>() -> (Arc<Vec<G>>, Arc<Vec<<G::Scalar as PrimeField>::Repr>>) {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let g = (0..SAMPLES).map(|_| G::Projective::rand(&mut rng).into_affine()).collect();
+    let v = (0..SAMPLES).map(|_| G::Scalar::rand(&mut rng).into_repr()).collect();
+
+    (Arc::new(g), Arc::new(v))
+}
+
+fn bench_multiexp<G: CurveAffine>(b: &mut ::test::Bencher, algorithm: MultiexpAlgorithm) {
+    let (g, v) = samples::<G>();
+    let pool = Worker::new();
+
+    b.iter(|| {
+        multiexp_with(&pool, (g.clone(), 0), FullDensity, v.clone(), algorithm).wait().unwrap()
+    });
+}
+
+fn bench_fixed_base<G: CurveAffine>(b: &mut ::test::Bencher) {
+    let (g, v) = samples::<G>();
+    let pool = Worker::new();
+    let table = FixedBaseTable::new(&pool, &g, 12).unwrap();
+
+    b.iter(|| {
+        table.multiexp(&pool, FullDensity, v.clone()).wait().unwrap()
+    });
+}
+
+#[bench]
+fn bench_g1_multiexp_unsigned(b: &mut ::test::Bencher) {
+    bench_multiexp::<<Bls12 as Engine>::G1Affine>(b, MultiexpAlgorithm::Unsigned);
+}
+
+#[bench]
+fn bench_g1_multiexp_signed_batch_affine(b: &mut ::test::Bencher) {
+    bench_multiexp::<<Bls12 as Engine>::G1Affine>(b, MultiexpAlgorithm::SignedBatchAffine);
+}
+
+#[bench]
+fn bench_g1_multiexp_fixed_base(b: &mut ::test::Bencher) {
+    bench_fixed_base::<<Bls12 as Engine>::G1Affine>(b);
+}
+
+#[bench]
+fn bench_g2_multiexp_unsigned(b: &mut ::test::Bencher) {
+    bench_multiexp::<<Bls12 as Engine>::G2Affine>(b, MultiexpAlgorithm::Unsigned);
+}
+
+#[bench]
+fn bench_g2_multiexp_signed_batch_affine(b: &mut ::test::Bencher) {
+    bench_multiexp::<<Bls12 as Engine>::G2Affine>(b, MultiexpAlgorithm::SignedBatchAffine);
+}
+
+#[bench]
+fn bench_g2_multiexp_fixed_base(b: &mut ::test::Bencher) {
+    bench_fixed_base::<<Bls12 as Engine>::G2Affine>(b);
+}
@@ -9,6 +9,14 @@
 //! roots to be the powers of a 2^n root of unity in the field.
 //! This allows us to perform polynomial operations in O(n)
 //! by performing an O(n log n) FFT over such a domain.
+//!
+//! The roots of unity of a `Domain` and the other powers used by
+//! the FFTs are computed once, so a domain can be shared by all
+//! of the polynomials of a proof. If the field has roots of unity
+//! of order 3^k, mixed-radix domains of size 2^a * 3^b can be used
+//! to get closer to the number of coefficients.
+
+use std::sync::Arc;
 
 use pairing::{
     Engine,
@@ -17,6 +25,7 @@ use pairing::{
 
 use ff::{
     PrimeField,
+    PrimeFieldRepr,
     Field
 };
 
@@ -26,34 +35,30 @@ use super::{
 
 use super::multicore::Worker;
 
-pub struct EvaluationDomain<E: Engine, G: Group<E>> {
-    coeffs: Vec<G>,
-    exp: u32,
+/// The multiplicative subgroup of the roots of unity of some
+/// order, with the tables of powers used by the FFTs
+pub struct Domain<E: Engine> {
+    size: usize,
+    // The radices of the FFT, from the outermost one
+    radices: Vec<usize>,
     omega: E::Fr,
-    omegainv: E::Fr,
-    geninv: E::Fr,
+    // omega^i and omega^-i, as far as the butterflies need them
+    twiddles: Vec<E::Fr>,
+    inv_twiddles: Vec<E::Fr>,
+    // g^i and g^-i / size, with g the multiplicative generator
+    coset_powers: Vec<E::Fr>,
+    inv_coset_powers: Vec<E::Fr>,
     minv: E::Fr
 }
 
-impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
-    pub fn as_ref(&self) -> &[G] {
-        &self.coeffs
-    }
-
-    pub fn as_mut(&mut self) -> &mut [G] {
-        &mut self.coeffs
-    }
-
-    pub fn into_coeffs(self) -> Vec<G> {
-        self.coeffs
-    }
-
-    pub fn from_coeffs(mut coeffs: Vec<G>) -> Result<EvaluationDomain<E, G>, SynthesisError>
-    {
+impl<E: Engine> Domain<E> {
+    /// Creates the radix-2 domain of the smallest power of two
+    /// which is at least `size`
+    pub fn new(size: usize) -> Result<Domain<E>, SynthesisError> {
         // Compute the size of our evaluation domain
         let mut m = 1;
         let mut exp = 0;
-        while m < coeffs.len() {
+        while m < size {
             m *= 2;
             exp += 1;
 
@@ -64,36 +69,222 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
             }
         }
 
-        // Compute omega, the 2^exp primitive root of unity
+        Ok(Self::with_radices(exp, 0, &E::Fr::one()))
+    }
+
+    /// Creates the smallest domain of size 2^a * 3^b which is at
+    /// least `size`, where b is at most the 3-adicity of the field
+    pub fn new_mixed_radix(size: usize) -> Result<Domain<E>, SynthesisError> {
+        let (three_adicity, three_adic_root) = three_adic_root_of_unity::<E::Fr>();
+
+        let mut best = None;
+        let mut power_of_three = 1usize;
+        for b in 0..(three_adicity + 1) {
+            let mut m = power_of_three;
+            let mut exp = 0;
+            while m < size && exp < E::Fr::S {
+                m *= 2;
+                exp += 1;
+            }
+
+            if m >= size && best.map_or(true, |(best_m, _, _)| m < best_m) {
+                best = Some((m, exp, b));
+            }
+
+            power_of_three = match power_of_three.checked_mul(3) {
+                Some(power_of_three) => power_of_three,
+                None => break
+            };
+        }
+
+        match best {
+            Some((_, exp, b)) => {
+                // Compute the 3^b primitive root of unity
+                let mut root = three_adic_root;
+                for _ in b..three_adicity {
+                    root = root.pow(&[3]);
+                }
+
+                Ok(Self::with_radices(exp, b, &root))
+            },
+            None => Err(SynthesisError::PolynomialDegreeTooLarge)
+        }
+    }
+
+    /// The domain of size 2^exp * 3^b, with the primitive 3^b root of unity
+    fn with_radices(exp: u32, b: u32, three_adic_root: &E::Fr) -> Domain<E> {
+        let worker = Worker::new();
+
+        // The radix-3 butterflies are the innermost ones
+        let mut radices = vec![2; exp as usize];
+        radices.extend(vec![3; b as usize]);
+        let m = radices.iter().product();
+
+        // Compute omega, the 2^exp primitive root of unity,
+        // times the 3^b one
         let mut omega = E::Fr::root_of_unity();
         for _ in exp..E::Fr::S {
             omega.square();
         }
+        omega.mul_assign(three_adic_root);
+
+        let omegainv = omega.inverse().unwrap();
+        let minv = E::Fr::from_str(&format!("{}", m)).unwrap().inverse().unwrap();
+        let gen = E::Fr::multiplicative_generator();
+        let geninv = gen.inverse().unwrap();
+
+        // The butterflies of a radix-3 stage use the
+        // powers up to 2/3 of the domain, otherwise 1/2
+        let num_twiddles = if b > 0 { 2 * m / 3 } else { m / 2 };
+
+        let twiddles = powers(&worker, &E::Fr::one(), &omega, num_twiddles);
+        let inv_twiddles = powers(&worker, &E::Fr::one(), &omegainv, num_twiddles);
+        let coset_powers = powers(&worker, &E::Fr::one(), &gen, m);
+        let inv_coset_powers = powers(&worker, &minv, &geninv, m);
+
+        Domain {
+            size: m,
+            radices: radices,
+            omega: omega,
+            twiddles: twiddles,
+            inv_twiddles: inv_twiddles,
+            coset_powers: coset_powers,
+            inv_coset_powers: inv_coset_powers,
+            minv: minv
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The primitive root of unity generating the domain
+    pub fn omega(&self) -> E::Fr {
+        self.omega
+    }
+
+    /// This evaluates t(tau) for this domain, which is
+    /// tau^m - 1 for the subgroup of the m-th roots of unity.
+    pub fn z(&self, tau: &E::Fr) -> E::Fr {
+        let mut tmp = tau.pow(&[self.size as u64]);
+        tmp.sub_assign(&E::Fr::one());
+
+        tmp
+    }
+}
+
+/// Returns the vector of c * g^i for i < n
+fn powers<F: Field>(worker: &Worker, c: &F, g: &F, n: usize) -> Vec<F> {
+    let mut result = vec![F::zero(); n];
+
+    worker.scope(n, |scope, chunk| {
+        for (i, result) in result.chunks_mut(chunk).enumerate() {
+            scope.spawn(move || {
+                let mut u = g.pow(&[(i * chunk) as u64]);
+                u.mul_assign(c);
+
+                for v in result.iter_mut() {
+                    *v = u;
+                    u.mul_assign(g);
+                }
+            });
+        }
+    });
+
+    result
+}
+
+/// Returns the largest k such that 3^k divides the order of the
+/// multiplicative group, and a primitive 3^k root of unity
+fn three_adic_root_of_unity<F: PrimeField>() -> (u32, F) {
+    // Divides the representation by a small number,
+    // and returns the remainder
+    fn div_rem<R: PrimeFieldRepr>(repr: &mut R, d: u64) -> u64 {
+        let mut rem = 0u128;
+        for limb in repr.as_mut().iter_mut().rev() {
+            let tmp = (rem << 64) | u128::from(*limb);
+            *limb = (tmp / u128::from(d)) as u64;
+            rem = tmp % u128::from(d);
+        }
+
+        rem as u64
+    }
+
+    let mut order = F::char();
+    order.sub_noborrow(&F::Repr::from(1));
+
+    // The exponent (p - 1) / 3^k of the generator
+    let mut exp = order;
+    let mut k = 0;
+    loop {
+        let mut tmp = exp;
+        if div_rem(&mut tmp, 3) != 0 {
+            break;
+        }
+        exp = tmp;
+        k += 1;
+    }
+
+    (k, F::multiplicative_generator().pow(exp.as_ref()))
+}
+
+pub struct EvaluationDomain<E: Engine, G: Group<E>> {
+    coeffs: Vec<G>,
+    domain: Arc<Domain<E>>
+}
+
+impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
+    pub fn as_ref(&self) -> &[G] {
+        &self.coeffs
+    }
+
+    pub fn as_mut(&mut self) -> &mut [G] {
+        &mut self.coeffs
+    }
+
+    pub fn into_coeffs(self) -> Vec<G> {
+        self.coeffs
+    }
+
+    /// The domain, which can be reused for other polynomials of the same size
+    pub fn domain(&self) -> &Arc<Domain<E>> {
+        &self.domain
+    }
+
+    pub fn from_coeffs(coeffs: Vec<G>) -> Result<EvaluationDomain<E, G>, SynthesisError>
+    {
+        let domain = Arc::new(Domain::new(coeffs.len())?);
+
+        Self::from_coeffs_in(coeffs, &domain)
+    }
+
+    /// Uses the domain, which must be at least as large as the coeffs vector
+    pub fn from_coeffs_in(mut coeffs: Vec<G>, domain: &Arc<Domain<E>>) -> Result<EvaluationDomain<E, G>, SynthesisError>
+    {
+        if coeffs.len() > domain.size {
+            return Err(SynthesisError::PolynomialDegreeTooLarge);
+        }
 
         // Extend the coeffs vector with zeroes if necessary
-        coeffs.resize(m, G::group_zero());
+        coeffs.resize(domain.size, G::group_zero());
 
         Ok(EvaluationDomain {
             coeffs: coeffs,
-            exp: exp,
-            omega: omega,
-            omegainv: omega.inverse().unwrap(),
-            geninv: E::Fr::multiplicative_generator().inverse().unwrap(),
-            minv: E::Fr::from_str(&format!("{}", m)).unwrap().inverse().unwrap()
+            domain: domain.clone()
         })
     }
 
     pub fn fft(&mut self, worker: &Worker)
     {
-        best_fft(&mut self.coeffs, worker, &self.omega, self.exp);
+        best_fft(&mut self.coeffs, worker, &self.domain.twiddles, &self.domain.radices);
     }
 
     pub fn ifft(&mut self, worker: &Worker)
     {
-        best_fft(&mut self.coeffs, worker, &self.omegainv, self.exp);
+        best_fft(&mut self.coeffs, worker, &self.domain.inv_twiddles, &self.domain.radices);
 
         worker.scope(self.coeffs.len(), |scope, chunk| {
-            let minv = self.minv;
+            let minv = self.domain.minv;
 
             for v in self.coeffs.chunks_mut(chunk) {
                 scope.spawn(move || {
@@ -120,27 +311,42 @@ impl<E: Engine, G: Group<E>> EvaluationDomain<E, G> {
         });
     }
 
+    /// Multiplies the coefficients by the precomputed powers
+    fn mul_by_powers(&mut self, worker: &Worker, powers: &[E::Fr])
+    {
+        worker.scope(self.coeffs.len(), |scope, chunk| {
+            for (v, powers) in self.coeffs.chunks_mut(chunk).zip(powers.chunks(chunk)) {
+                scope.spawn(move || {
+                    for (v, u) in v.iter_mut().zip(powers.iter()) {
+                        v.group_mul_assign(u);
+                    }
+                });
+            }
+        });
+    }
+
     pub fn coset_fft(&mut self, worker: &Worker)
     {
-        self.distribute_powers(worker, E::Fr::multiplicative_generator());
+        let domain = self.domain.clone();
+
+        self.mul_by_powers(worker, &domain.coset_powers);
         self.fft(worker);
     }
 
     pub fn icoset_fft(&mut self, worker: &Worker)
     {
-        let geninv = self.geninv;
+        let domain = self.domain.clone();
 
-        self.ifft(worker);
-        self.distribute_powers(worker, geninv);
+        // The inverse powers of the generator include
+        // the division by the size of the domain
+        best_fft(&mut self.coeffs, worker, &domain.inv_twiddles, &domain.radices);
+        self.mul_by_powers(worker, &domain.inv_coset_powers);
     }
 
     /// This evaluates t(tau) for this domain, which is
-    /// tau^m - 1 for these radix-2 domains.
+    /// tau^m - 1 for these domains of m-th roots of unity.
     pub fn z(&self, tau: &E::Fr) -> E::Fr {
-        let mut tmp = tau.pow(&[self.coeffs.len() as u64]);
-        tmp.sub_assign(&E::Fr::one());
-
-        tmp
+        self.domain.z(tau)
     }
 
     /// The target polynomial is the zero polynomial in our
@@ -261,18 +467,21 @@ impl<E: Engine> Group<E> for Scalar<E> {
     }
 }
 
-fn best_fft<E: Engine, T: Group<E>>(a: &mut [T], worker: &Worker, omega: &E::Fr, log_n: u32)
+
+fn best_fft<E: Engine, T: Group<E>>(a: &mut [T], worker: &Worker, twiddles: &[E::Fr], radices: &[usize])
 {
     let log_cpus = worker.log_num_cpus();
 
-    if log_n <= log_cpus {
-        serial_fft(a, omega, log_n);
+    if log_cpus == 0 || a.len() <= (1 << log_cpus) {
+        serial_fft(a, twiddles, radices);
     } else {
-        parallel_fft(a, worker, omega, log_n, log_cpus);
+        parallel_fft(a, worker, twiddles, radices, log_cpus);
     }
 }
 
-fn serial_fft<E: Engine, T: Group<E>>(a: &mut [T], omega: &E::Fr, log_n: u32)
+/// Permutes the elements so that the butterflies of the
+/// innermost radix are applied to adjacent elements
+fn digit_reverse<T: Copy>(a: &mut [T], radices: &[usize])
 {
     fn bitreverse(mut n: u32, l: u32) -> u32 {
         let mut r = 0;
@@ -283,99 +492,219 @@ fn serial_fft<E: Engine, T: Group<E>>(a: &mut [T], omega: &E::Fr, log_n: u32)
         r
     }
 
-    let n = a.len() as u32;
-    assert_eq!(n, 1 << log_n);
+    let n = a.len();
+    assert_eq!(n, radices.iter().product());
+
+    if radices.iter().all(|&r| r == 2) {
+        let log_n = radices.len() as u32;
+
+        for k in 0..(n as u32) {
+            let rk = bitreverse(k, log_n);
+            if k < rk {
+                a.swap(rk as usize, k as usize);
+            }
+        }
+    } else {
+        let v = a.to_vec();
+
+        for (k, x) in v.into_iter().enumerate() {
+            let mut k = k;
+            let mut rk = 0;
+            let mut size = n;
+            for &r in radices {
+                size /= r;
+                rk += (k % r) * size;
+                k /= r;
+            }
+
+            a[rk] = x;
+        }
+    }
+}
+
+/// The butterflies of a radix-2 stage for the elements of index k..
+/// of both halves, where the twiddles of the stage are at multiples
+/// of the stride
+fn radix_2<E: Engine, T: Group<E>>(
+    y0: &mut [T],
+    y1: &mut [T],
+    twiddles: &[E::Fr],
+    k: usize,
+    stride: usize
+)
+{
+    for (j, (y0, y1)) in y0.iter_mut().zip(y1.iter_mut()).enumerate() {
+        let mut t = *y1;
+        if k + j > 0 {
+            t.group_mul_assign(&twiddles[(k + j) * stride]);
+        }
+        *y1 = *y0;
+        y1.group_sub_assign(&t);
+        y0.group_add_assign(&t);
+    }
+}
 
-    for k in 0..n {
-        let rk = bitreverse(k, log_n);
-        if k < rk {
-            a.swap(rk as usize, k as usize);
+/// The butterflies of a radix-3 stage, as for `radix_2`, where u
+/// is the primitive cube root of unity
+fn radix_3<E: Engine, T: Group<E>>(
+    y0: &mut [T],
+    y1: &mut [T],
+    y2: &mut [T],
+    twiddles: &[E::Fr],
+    u: &E::Fr,
+    k: usize,
+    stride: usize
+)
+{
+    for (j, ((y0, y1), y2)) in y0.iter_mut().zip(y1.iter_mut()).zip(y2.iter_mut()).enumerate() {
+        let mut a1 = *y1;
+        let mut a2 = *y2;
+        if k + j > 0 {
+            a1.group_mul_assign(&twiddles[(k + j) * stride]);
+            a2.group_mul_assign(&twiddles[2 * (k + j) * stride]);
         }
+
+        // With u^2 = -1 - u, the outputs are
+        // a0 + a1 + a2, a0 - a2 + u * (a1 - a2) and a0 - a1 + u * (a2 - a1)
+        let mut d = a1;
+        d.group_sub_assign(&a2);
+        d.group_mul_assign(u);
+
+        let mut x1 = *y0;
+        x1.group_sub_assign(&a2);
+        x1.group_add_assign(&d);
+
+        let mut x2 = *y0;
+        x2.group_sub_assign(&a1);
+        x2.group_sub_assign(&d);
+
+        y0.group_add_assign(&a1);
+        y0.group_add_assign(&a2);
+        *y1 = x1;
+        *y2 = x2;
+    }
+}
+
+/// Applies the butterflies of a stage to a block of r
+/// consecutive sub-FFTs, for the elements of index k..
+fn butterflies<E: Engine, T: Group<E>>(
+    block: &mut [T],
+    r: usize,
+    twiddles: &[E::Fr],
+    u: &E::Fr,
+    k: usize,
+    stride: usize
+)
+{
+    let m = block.len() / r;
+
+    match r {
+        2 => {
+            let (y0, y1) = block.split_at_mut(m);
+            radix_2::<E, T>(y0, y1, twiddles, k, stride);
+        },
+        3 => {
+            let (y0, y1) = block.split_at_mut(m);
+            let (y1, y2) = y1.split_at_mut(m);
+            radix_3::<E, T>(y0, y1, y2, twiddles, u, k, stride);
+        },
+        _ => unreachable!()
+    }
+}
+
+/// The primitive cube root of unity of the twiddles, if the domain has one
+fn cube_root<F: Field>(twiddles: &[F], n: usize) -> F {
+    if n % 3 == 0 {
+        twiddles[n / 3]
+    } else {
+        F::one()
     }
+}
+
+fn serial_fft<E: Engine, T: Group<E>>(a: &mut [T], twiddles: &[E::Fr], radices: &[usize])
+{
+    let n = a.len();
+    let u = cube_root(twiddles, n);
+
+    digit_reverse(a, radices);
 
     let mut m = 1;
-    for _ in 0..log_n {
-        let w_m = omega.pow(&[(n / (2*m)) as u64]);
-
-        let mut k = 0;
-        while k < n {
-            let mut w = E::Fr::one();
-            for j in 0..m {
-                let mut t = a[(k+j+m) as usize];
-                t.group_mul_assign(&w);
-                let mut tmp = a[(k+j) as usize];
-                tmp.group_sub_assign(&t);
-                a[(k+j+m) as usize] = tmp;
-                a[(k+j) as usize].group_add_assign(&t);
-                w.mul_assign(&w_m);
-            }
+    for &r in radices.iter().rev() {
+        let l = m * r;
 
-            k += 2*m;
+        for block in a.chunks_mut(l) {
+            butterflies::<E, T>(block, r, twiddles, &u, 0, n / l);
         }
 
-        m *= 2;
+        m = l;
     }
 }
 
 fn parallel_fft<E: Engine, T: Group<E>>(
     a: &mut [T],
     worker: &Worker,
-    omega: &E::Fr,
-    log_n: u32,
+    twiddles: &[E::Fr],
+    radices: &[usize],
     log_cpus: u32
 )
 {
-    assert!(log_n >= log_cpus);
-
+    let n = a.len();
+    let u = cube_root(twiddles, n);
+    let u = &u;
     let num_cpus = 1 << log_cpus;
-    let log_new_n = log_n - log_cpus;
-    let mut tmp = vec![vec![T::group_zero(); 1 << log_new_n]; num_cpus];
-    let new_omega = omega.pow(&[num_cpus as u64]);
 
-    worker.scope(0, |scope, _| {
-        let a = &*a;
+    digit_reverse(a, radices);
 
-        for (j, tmp) in tmp.iter_mut().enumerate() {
-            scope.spawn(move || {
-                // Shuffle into a sub-FFT
-                let omega_j = omega.pow(&[j as u64]);
-                let omega_step = omega.pow(&[(j as u64) << log_new_n]);
-
-                let mut elt = E::Fr::one();
-                for i in 0..(1 << log_new_n) {
-                    for s in 0..num_cpus {
-                        let idx = (i + (s << log_new_n)) % (1 << log_n);
-                        let mut t = a[idx];
-                        t.group_mul_assign(&elt);
-                        tmp[i].group_add_assign(&t);
-                        elt.mul_assign(&omega_step);
-                    }
-                    elt.mul_assign(&omega_j);
+    let mut m = 1;
+    for &r in radices.iter().rev() {
+        let l = m * r;
+        let stride = n / l;
+
+        if n / l >= num_cpus {
+            // There are enough blocks for each thread
+            worker.scope(n / l, |scope, chunk| {
+                for blocks in a.chunks_mut(chunk * l) {
+                    scope.spawn(move || {
+                        for block in blocks.chunks_mut(l) {
+                            butterflies::<E, T>(block, r, twiddles, u, 0, stride);
+                        }
+                    });
                 }
-
-                // Perform sub-FFT
-                serial_fft(tmp, &new_omega, log_new_n);
             });
+        } else {
+            // Otherwise the butterflies of each block are split between the threads
+            for block in a.chunks_mut(l) {
+                let (y0, y1) = block.split_at_mut(m);
+
+                worker.scope(m, |scope, chunk| {
+                    if r == 2 {
+                        for (i, (y0, y1)) in y0.chunks_mut(chunk).zip(y1.chunks_mut(chunk)).enumerate() {
+                            scope.spawn(move || {
+                                radix_2::<E, T>(y0, y1, twiddles, i * chunk, stride);
+                            });
+                        }
+                    } else {
+                        let (y1, y2) = y1.split_at_mut(m);
+
+                        for (i, ((y0, y1), y2)) in y0.chunks_mut(chunk)
+                            .zip(y1.chunks_mut(chunk))
+                            .zip(y2.chunks_mut(chunk))
+                            .enumerate()
+                        {
+                            scope.spawn(move || {
+                                radix_3::<E, T>(y0, y1, y2, twiddles, u, i * chunk, stride);
+                            });
+                        }
+                    }
+                });
+            }
         }
-    });
-
-    // TODO: does this hurt or help?
-    worker.scope(a.len(), |scope, chunk| {
-        let tmp = &tmp;
 
-        for (idx, a) in a.chunks_mut(chunk).enumerate() {
-            scope.spawn(move || {
-                let mut idx = idx * chunk;
-                let mask = (1 << log_cpus) - 1;
-                for a in a {
-                    *a = tmp[idx & mask][idx >> log_cpus];
-                    idx += 1;
-                }
-            });
-        }
-    });
+        m = l;
+    }
 }
 
+
 // Test multiplying various (low degree) polynomials together and
 // comparing with naive evaluations.
 #[test]
@@ -480,10 +809,11 @@ fn parallel_fft_consistency() {
                 let v1 = (0..d).map(|_| Scalar::<E>(E::Fr::rand(rng))).collect::<Vec<_>>();
                 let mut v1 = EvaluationDomain::from_coeffs(v1).unwrap();
                 let mut v2 = EvaluationDomain::from_coeffs(v1.coeffs.clone()).unwrap();
+                let domain = v1.domain.clone();
 
                 for log_cpus in log_d..min(log_d+1, 3) {
-                    parallel_fft(&mut v1.coeffs, &worker, &v1.omega, log_d, log_cpus);
-                    serial_fft(&mut v2.coeffs, &v2.omega, log_d);
+                    parallel_fft(&mut v1.coeffs, &worker, &domain.twiddles, &domain.radices, log_cpus);
+                    serial_fft(&mut v2.coeffs, &domain.twiddles, &domain.radices);
 
                     assert!(v1.coeffs == v2.coeffs);
                 }
@@ -495,3 +825,79 @@ fn parallel_fft_consistency() {
 
     test_consistency::<Bls12, _>(rng);
 }
+
+#[test]
+fn mixed_radix_fft() {
+    use pairing::bls12_381::Bls12;
+    use pairing::bn256::Bn256;
+    use rand::{self, Rand};
+
+    fn test_mixed_radix<E: Engine, R: rand::Rng>(rng: &mut R, three_adicity: u32)
+    {
+        let worker = Worker::new();
+
+        for size in 1..40 {
+            let domain = Arc::new(Domain::<E>::new_mixed_radix(size).unwrap());
+            let m = domain.size();
+            assert!(m >= size);
+            assert_eq!(m, domain.radices.iter().product());
+            assert!(domain.radices.iter().filter(|&&r| r == 3).count() as u32 <= three_adicity);
+
+            // omega is a primitive m-th root of unity
+            assert_eq!(domain.omega().pow(&[m as u64]), E::Fr::one());
+            for &p in [2, 3].iter() {
+                if m % p == 0 {
+                    assert!(domain.omega().pow(&[(m / p) as u64]) != E::Fr::one());
+                }
+            }
+
+            let v: Vec<_> = (0..size).map(|_| Scalar::<E>(E::Fr::rand(rng))).collect();
+
+            // Compare with the naive evaluation at the powers of omega
+            let mut fft = EvaluationDomain::from_coeffs_in(v.clone(), &domain).unwrap();
+            fft.fft(&worker);
+            let mut point = E::Fr::one();
+            for value in fft.as_ref() {
+                let mut naive = E::Fr::zero();
+                for c in v.iter().rev() {
+                    naive.mul_assign(&point);
+                    naive.add_assign(&c.0);
+                }
+                assert!(naive == value.0);
+
+                point.mul_assign(&domain.omega());
+            }
+
+            let mut v = v;
+            v.resize(m, Scalar(E::Fr::zero()));
+
+            fft.ifft(&worker);
+            assert!(v == fft.coeffs);
+            fft.coset_fft(&worker);
+            fft.icoset_fft(&worker);
+            assert!(v == fft.coeffs);
+
+            for log_cpus in 0..3 {
+                let mut v1 = v.clone();
+                let mut v2 = v.clone();
+                parallel_fft(&mut v1, &worker, &domain.twiddles, &domain.radices, log_cpus);
+                serial_fft(&mut v2, &domain.twiddles, &domain.radices);
+                assert!(v1 == v2);
+            }
+        }
+
+        let domain = Arc::new(Domain::<E>::new_mixed_radix(4).unwrap());
+        assert!(EvaluationDomain::from_coeffs_in(vec![Scalar::<E>(E::Fr::zero()); 5], &domain).is_err());
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    // The scalar field of BLS12-381 has the roots of unity of
+    // order 2^32 * 3, and the one of BN256 of order 2^28 * 3^2
+    test_mixed_radix::<Bls12, _>(rng, 1);
+    test_mixed_radix::<Bn256, _>(rng, 2);
+
+    assert_eq!(Domain::<Bls12>::new_mixed_radix(33000).unwrap().size(), 3 << 14);
+    assert_eq!(Domain::<Bn256>::new_mixed_radix(33000).unwrap().size(), 9 << 12);
+    assert_eq!(Domain::<Bls12>::new(33000).unwrap().size(), 1 << 16);
+}
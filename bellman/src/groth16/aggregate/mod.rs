@@ -0,0 +1,563 @@
+//! Aggregation of Groth16 proofs for the same verifying key, following
+//! SnarkPack (Gailly, Maller and Nitulescu).
+//!
+//! The `A`, `B` and `C` elements of the proofs are committed to with
+//! pairing-based commitments under keys from two powers-of-tau reference
+//! strings, with different secrets. A random linear combination of the
+//! verification equations of the proofs then only needs the inner
+//! pairing product of the `A` and `B` elements, and the multi-scalar
+//! product of the `C` elements, which are proven consistent with the
+//! commitments by an inner product argument of logarithmic size. The
+//! final commitment keys of the argument are checked with KZG openings,
+//! so the verifier does a logarithmic amount of work.
+//!
+//! The number of proofs is padded to a power of two by repeating the
+//! last one, and the verifier repeats its public inputs likewise.
+
+use pairing::{
+    Engine,
+    CurveProjective,
+    CurveAffine
+};
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use futures::Future;
+
+use std::sync::{Arc, Mutex};
+
+use ::{
+    SynthesisError
+};
+
+use ::kzg::Srs;
+
+use ::multiexp::{
+    FullDensity,
+    multiexp
+};
+
+use ::multicore::Worker;
+
+use ::transcript::Transcript;
+
+use super::VerifyingKey;
+
+mod prover;
+mod verifier;
+
+pub use self::prover::*;
+pub use self::verifier::*;
+
+/// Reference string of the prover for aggregating up to `n` proofs. The
+/// keys of `m` proofs are the first `m` powers in G2 and the `m` powers
+/// in G1 after them, so that they commit to separate powers.
+#[derive(Clone)]
+pub struct ProverSrs<E: Engine> {
+    pub n: usize,
+
+    // a^i and b^i in G1 for i < 2n, for the keys
+    // of the elements in G2 and their openings
+    g_alpha_powers: Arc<Vec<E::G1Affine>>,
+    g_beta_powers: Arc<Vec<E::G1Affine>>,
+
+    // a^i and b^i in G2 for i < n, for the keys
+    // of the elements in G1 and their openings
+    h_alpha_powers: Arc<Vec<E::G2Affine>>,
+    h_beta_powers: Arc<Vec<E::G2Affine>>
+}
+
+/// Reference string of the verifier for aggregates of up to `n` proofs
+#[derive(Clone)]
+pub struct VerifierSrs<E: Engine> {
+    pub n: usize,
+    pub g_alpha: E::G1Affine,
+    pub g_beta: E::G1Affine,
+    pub h_alpha: E::G2Affine,
+    pub h_beta: E::G2Affine
+}
+
+/// Derives the reference strings for aggregating up to `max_num_proofs`
+/// proofs from two KZG reference strings, whose secrets must be distinct
+/// and independent. Ceremonies of the powers of tau can be reused.
+pub fn specialize_srs<E: Engine>(
+    alpha: &Srs<E>,
+    beta: &Srs<E>,
+    max_num_proofs: usize
+) -> Result<(ProverSrs<E>, VerifierSrs<E>), SynthesisError>
+{
+    let n = padded_num_proofs(max_num_proofs);
+
+    let alpha = alpha.trim(2 * n, n)?;
+    let beta = beta.trim(2 * n, n)?;
+
+    let verifier_srs = VerifierSrs {
+        n: n,
+        g_alpha: alpha.powers_g1[1],
+        g_beta: beta.powers_g1[1],
+        h_alpha: alpha.powers_g2[1],
+        h_beta: beta.powers_g2[1]
+    };
+
+    let prover_srs = ProverSrs {
+        n: n,
+        g_alpha_powers: alpha.powers_g1,
+        g_beta_powers: beta.powers_g1,
+        h_alpha_powers: alpha.powers_g2,
+        h_beta_powers: beta.powers_g2
+    };
+
+    Ok((prover_srs, verifier_srs))
+}
+
+fn padded_num_proofs(num_proofs: usize) -> usize {
+    if num_proofs < 2 {
+        2
+    } else {
+        num_proofs.next_power_of_two()
+    }
+}
+
+/// Commitment to a vector under a pair of keys, one for
+/// each of the secrets, which is a pair of pairing products
+#[derive(Clone)]
+pub struct Commitment<E: Engine> {
+    pub t: E::Fqk,
+    pub u: E::Fqk
+}
+
+impl<E: Engine> PartialEq for Commitment<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.t == other.t &&
+        self.u == other.u
+    }
+}
+
+impl<E: Engine> Commitment<E> {
+    fn mul_assign(&mut self, other: &Self) {
+        self.t.mul_assign(&other.t);
+        self.u.mul_assign(&other.u);
+    }
+
+    fn pow(&self, exp: &E::Fr) -> Self {
+        Commitment {
+            t: self.t.pow(exp.into_repr()),
+            u: self.u.pow(exp.into_repr())
+        }
+    }
+
+    fn absorb_into(&self, transcript: &mut Transcript) {
+        transcript.commit_extension(&self.t);
+        transcript.commit_extension(&self.u);
+    }
+}
+
+/// Messages of a round of the inner product argument, which halves the
+/// vectors. The values multiplied by the challenge of the round come
+/// from the right half of the vectors in G1, paired with the left half
+/// of the vector in G2 and of the keys of the vectors in G1, and the
+/// others the other way around.
+#[derive(Clone)]
+pub struct Round<E: Engine> {
+    pub com_ab_x: Commitment<E>,
+    pub com_ab_x_inv: Commitment<E>,
+    pub com_c_x: Commitment<E>,
+    pub com_c_x_inv: Commitment<E>,
+    pub ip_ab_x: E::Fqk,
+    pub ip_ab_x_inv: E::Fqk,
+    pub agg_c_x: E::G1Affine,
+    pub agg_c_x_inv: E::G1Affine
+}
+
+impl<E: Engine> Round<E> {
+    fn absorb_into(&self, transcript: &mut Transcript) {
+        self.com_ab_x.absorb_into(transcript);
+        self.com_ab_x_inv.absorb_into(transcript);
+        self.com_c_x.absorb_into(transcript);
+        self.com_c_x_inv.absorb_into(transcript);
+        transcript.commit_extension(&self.ip_ab_x);
+        transcript.commit_extension(&self.ip_ab_x_inv);
+        transcript.commit_point(&self.agg_c_x);
+        transcript.commit_point(&self.agg_c_x_inv);
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateProof<E: Engine> {
+    // Commitments to the A and B elements together,
+    // and to the C elements
+    pub com_ab: Commitment<E>,
+    pub com_c: Commitment<E>,
+
+    // prod e(A_i, B_i)^(r^i) and sum r^i * C_i
+    pub ip_ab: E::Fqk,
+    pub agg_c: E::G1Affine,
+
+    pub rounds: Vec<Round<E>>,
+
+    // The vectors and their keys folded to a single element
+    pub final_a: E::G1Affine,
+    pub final_b: E::G2Affine,
+    pub final_c: E::G1Affine,
+    pub final_vkey: (E::G2Affine, E::G2Affine),
+    pub final_wkey: (E::G1Affine, E::G1Affine),
+
+    // KZG openings of the final keys, for the
+    // secrets a and b of the reference strings
+    pub vkey_opening: (E::G2Affine, E::G2Affine),
+    pub wkey_opening: (E::G1Affine, E::G1Affine)
+}
+
+/// Starts the transcript of the aggregation, which is bound to the
+/// verifying key and the public inputs of the proofs
+fn start_transcript<E: Engine>(
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::Fr>],
+    n: usize,
+    com_ab: &Commitment<E>,
+    com_c: &Commitment<E>
+) -> Transcript
+{
+    let mut transcript = Transcript::new(b"bellman groth16 aggregation");
+
+    transcript.commit_point(&vk.alpha_g1);
+    transcript.commit_point(&vk.beta_g1);
+    transcript.commit_point(&vk.beta_g2);
+    transcript.commit_point(&vk.gamma_g2);
+    transcript.commit_point(&vk.delta_g1);
+    transcript.commit_point(&vk.delta_g2);
+    transcript.absorb(&(vk.ic.len() as u64).to_be_bytes());
+    for ic in vk.ic.iter() {
+        transcript.commit_point(ic);
+    }
+
+    transcript.absorb(&(public_inputs.len() as u64).to_be_bytes());
+    for inputs in public_inputs.iter() {
+        for input in inputs.iter() {
+            transcript.commit_scalar(input);
+        }
+    }
+
+    transcript.absorb(&(n as u64).to_be_bytes());
+    com_ab.absorb_into(&mut transcript);
+    com_c.absorb_into(&mut transcript);
+
+    transcript
+}
+
+/// Returns a non-zero challenge and its inverse
+fn invertible_challenge<F: PrimeField>(transcript: &mut Transcript) -> (F, F) {
+    loop {
+        let c: F = transcript.challenge();
+
+        if let Some(c_inv) = c.inverse() {
+            return (c, c_inv);
+        }
+    }
+}
+
+/// Returns prod e(a_i, b_i)
+fn pairing_product<E: Engine>(
+    a: &[E::G1Affine],
+    b: &[E::G2Affine],
+    worker: &Worker
+) -> E::Fqk
+{
+    assert_eq!(a.len(), b.len());
+
+    let result = Mutex::new(E::Fqk::one());
+
+    worker.scope(a.len(), |scope, chunk| {
+        for (a, b) in a.chunks(chunk).zip(b.chunks(chunk)) {
+            let result = &result;
+
+            scope.spawn(move || {
+                let prepared: Vec<_> = a.iter().zip(b.iter()).map(|(a, b)| (a.prepare(), b.prepare())).collect();
+                let pairs: Vec<_> = prepared.iter().map(|&(ref a, ref b)| (a, b)).collect();
+                let ml = E::miller_loop(pairs.iter());
+
+                result.lock().unwrap().mul_assign(&ml);
+            });
+        }
+    });
+
+    let result = result.into_inner().unwrap();
+
+    E::final_exponentiation(&result).unwrap()
+}
+
+/// Returns the vector of left_i + x * right_i
+fn fold<G: CurveAffine>(left: &[G], right: &[G], x: &G::Scalar, worker: &Worker) -> Vec<G> {
+    assert_eq!(left.len(), right.len());
+
+    let mut result: Vec<_> = left.iter().map(|l| l.into_projective()).collect();
+    let x = x.into_repr();
+
+    worker.scope(result.len(), |scope, chunk| {
+        for (result, right) in result.chunks_mut(chunk).zip(right.chunks(chunk)) {
+            scope.spawn(move || {
+                for (r, right) in result.iter_mut().zip(right.iter()) {
+                    r.add_assign(&right.mul(x));
+                }
+
+                G::Projective::batch_normalization(result);
+            });
+        }
+    });
+
+    result.into_iter().map(|r| r.into_affine()).collect()
+}
+
+/// Commitment key of vectors in G1, with powers in G2
+#[derive(Clone)]
+struct VKey<E: Engine> {
+    a: Vec<E::G2Affine>,
+    b: Vec<E::G2Affine>
+}
+
+impl<E: Engine> VKey<E> {
+    fn commit(&self, m: &[E::G1Affine], worker: &Worker) -> Commitment<E> {
+        Commitment {
+            t: pairing_product::<E>(m, &self.a, worker),
+            u: pairing_product::<E>(m, &self.b, worker)
+        }
+    }
+
+    fn split(&self, at: usize) -> (VKey<E>, VKey<E>) {
+        (
+            VKey { a: self.a[..at].to_vec(), b: self.b[..at].to_vec() },
+            VKey { a: self.a[at..].to_vec(), b: self.b[at..].to_vec() }
+        )
+    }
+
+    fn fold(&self, right: &Self, x: &E::Fr, worker: &Worker) -> Self {
+        VKey {
+            a: fold(&self.a, &right.a, x, worker),
+            b: fold(&self.b, &right.b, x, worker)
+        }
+    }
+}
+
+/// Commitment key of vectors in G2, with powers in G1
+#[derive(Clone)]
+struct WKey<E: Engine> {
+    a: Vec<E::G1Affine>,
+    b: Vec<E::G1Affine>
+}
+
+impl<E: Engine> WKey<E> {
+    fn commit(&self, m: &[E::G2Affine], worker: &Worker) -> Commitment<E> {
+        Commitment {
+            t: pairing_product::<E>(&self.a, m, worker),
+            u: pairing_product::<E>(&self.b, m, worker)
+        }
+    }
+
+    fn split(&self, at: usize) -> (WKey<E>, WKey<E>) {
+        (
+            WKey { a: self.a[..at].to_vec(), b: self.b[..at].to_vec() },
+            WKey { a: self.a[at..].to_vec(), b: self.b[at..].to_vec() }
+        )
+    }
+
+    fn fold(&self, right: &Self, x: &E::Fr, worker: &Worker) -> Self {
+        WKey {
+            a: fold(&self.a, &right.a, x, worker),
+            b: fold(&self.b, &right.b, x, worker)
+        }
+    }
+}
+
+/// Coefficients of prod_j (1 + c_j * X^(n / 2^(j + 1))), such that the
+/// key folded with the challenges `c_j` of the rounds is the sum of the
+/// elements of the key times the coefficients
+fn key_polynomial<F: Field>(challenges: &[F]) -> Vec<F> {
+    let mut coeffs = vec![F::one()];
+
+    for c in challenges.iter().rev() {
+        let high: Vec<_> = coeffs.iter().map(|coeff| {
+            let mut coeff = *coeff;
+            coeff.mul_assign(c);
+            coeff
+        }).collect();
+
+        coeffs.extend(high);
+    }
+
+    coeffs
+}
+
+/// Evaluates the key polynomial at `z`
+fn evaluate_key_polynomial<F: Field>(challenges: &[F], z: &F) -> F {
+    let mut result = F::one();
+    let mut z_power = *z;
+
+    for c in challenges.iter().rev() {
+        let mut tmp = z_power;
+        tmp.mul_assign(c);
+        tmp.add_assign(&F::one());
+        result.mul_assign(&tmp);
+
+        z_power.square();
+    }
+
+    result
+}
+
+/// Commits to the polynomial with the powers of a secret
+fn commit_with_powers<G: CurveAffine>(
+    powers: &Arc<Vec<G>>,
+    coeffs: &[G::Scalar],
+    worker: &Worker
+) -> Result<G, SynthesisError>
+{
+    assert!(coeffs.len() <= powers.len());
+
+    let exponents = Arc::new(coeffs.iter().map(|c| c.into_repr()).collect::<Vec<_>>());
+
+    Ok(multiexp(worker, (powers.clone(), 0), FullDensity, exponents).wait()?.into_affine())
+}
+
+#[cfg(test)]
+mod test_with_bls12_381 {
+    use super::*;
+    use super::super::*;
+    use kzg;
+    use tests::MySillyCircuit;
+
+    use rand::{Rand, thread_rng};
+    use ff::Field;
+    use pairing::bls12_381::{Bls12, Fr};
+
+    #[test]
+    fn key_polynomial_matches_folding() {
+        let rng = &mut thread_rng();
+
+        let challenges: Vec<_> = (0..3).map(|_| Fr::rand(rng)).collect();
+        let coeffs = key_polynomial(&challenges);
+        assert_eq!(coeffs.len(), 8);
+
+        // Fold the vector of the powers of z like a key
+        let z = Fr::rand(rng);
+        let mut key: Vec<_> = (0..8).map(|i| z.pow(&[i as u64])).collect();
+        for c in challenges.iter() {
+            let half = key.len() / 2;
+            let right = key.split_off(half);
+            for (l, r) in key.iter_mut().zip(right.iter()) {
+                let mut tmp = *r;
+                tmp.mul_assign(c);
+                l.add_assign(&tmp);
+            }
+        }
+
+        assert_eq!(key[0], ::kzg::evaluate(&coeffs, &z));
+        assert_eq!(key[0], evaluate_key_polynomial(&challenges, &z));
+    }
+
+    #[test]
+    fn aggregate_and_verify() {
+        let rng = &mut thread_rng();
+
+        let params = generate_random_parameters::<Bls12, _, _>(
+            MySillyCircuit { a: None, b: None },
+            rng
+        ).unwrap();
+
+        let alpha = kzg::generate_random_srs::<Bls12, _>(16, 8, rng);
+        let beta = kzg::generate_random_srs::<Bls12, _>(16, 8, rng);
+        let (prover_srs, verifier_srs) = specialize_srs(&alpha, &beta, 8).unwrap();
+
+        // Any number of proofs up to the size of the reference string
+        for &num_proofs in [1, 5, 8].iter() {
+            let mut proofs = vec![];
+            let mut inputs = vec![];
+
+            for _ in 0..num_proofs {
+                let a = Fr::rand(rng);
+                let b = Fr::rand(rng);
+                let mut c = a;
+                c.mul_assign(&b);
+
+                proofs.push(create_random_proof(
+                    MySillyCircuit {
+                        a: Some(a),
+                        b: Some(b)
+                    },
+                    &params,
+                    rng
+                ).unwrap());
+                inputs.push(vec![c]);
+            }
+
+            let aggregate = aggregate_proofs(&prover_srs, &params.vk, &inputs, &proofs).unwrap();
+            assert_eq!(aggregate.rounds.len(), padded_num_proofs(num_proofs).trailing_zeros() as usize);
+
+            assert!(verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &aggregate).unwrap());
+
+            // Wrong public inputs of a single proof
+            let mut wrong_inputs = inputs.clone();
+            wrong_inputs[num_proofs - 1][0].add_assign(&Fr::one());
+            assert!(!verify_aggregate_proof(&verifier_srs, &params.vk, &wrong_inputs, &aggregate).unwrap());
+
+            // Tampered messages
+            let mut wrong = aggregate.clone();
+            wrong.agg_c = wrong.final_c;
+            assert!(!verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &wrong).unwrap());
+
+            let mut wrong = aggregate.clone();
+            wrong.rounds[0].ip_ab_x = wrong.rounds[0].ip_ab_x_inv;
+            assert!(!verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &wrong).unwrap());
+
+            let mut wrong = aggregate.clone();
+            wrong.final_vkey.0 = wrong.final_vkey.1;
+            assert!(!verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &wrong).unwrap());
+        }
+
+        // An invalid proof among valid ones
+        let mut proofs = vec![];
+        let mut inputs = vec![];
+        for _ in 0..4 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            proofs.push(create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b)
+                },
+                &params,
+                rng
+            ).unwrap());
+            inputs.push(vec![c]);
+        }
+        proofs[2].c = proofs[1].c;
+
+        let aggregate = aggregate_proofs(&prover_srs, &params.vk, &inputs, &proofs).unwrap();
+        assert!(!verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &aggregate).unwrap());
+
+        // Too many proofs for the reference string
+        let too_many = vec![proofs[0].clone(); 9];
+        let too_many_inputs = vec![inputs[0].clone(); 9];
+        assert!(aggregate_proofs(&prover_srs, &params.vk, &too_many_inputs, &too_many).is_err());
+        assert!(aggregate_proofs(&prover_srs, &params.vk, &[], &[]).is_err());
+
+        // Public inputs that don't match the proofs
+        assert!(aggregate_proofs(&prover_srs, &params.vk, &inputs[1..], &proofs).is_err());
+
+        // The aggregate is bound to the verifying key
+        let other = generate_random_parameters::<Bls12, _, _>(
+            MySillyCircuit { a: None, b: None },
+            rng
+        ).unwrap();
+        let proofs = proofs[0..2].to_vec();
+        let inputs = inputs[0..2].to_vec();
+        let aggregate = aggregate_proofs(&prover_srs, &params.vk, &inputs, &proofs).unwrap();
+        assert!(verify_aggregate_proof(&verifier_srs, &params.vk, &inputs, &aggregate).unwrap());
+        assert!(!verify_aggregate_proof(&verifier_srs, &other.vk, &inputs, &aggregate).unwrap());
+    }
+}
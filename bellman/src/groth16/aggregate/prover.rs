@@ -0,0 +1,257 @@
+use pairing::{
+    Engine,
+    CurveProjective,
+    CurveAffine
+};
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use super::{
+    ProverSrs,
+    AggregateProof,
+    Round,
+    VKey,
+    WKey,
+    padded_num_proofs,
+    start_transcript,
+    invertible_challenge,
+    pairing_product,
+    fold,
+    key_polynomial,
+    commit_with_powers
+};
+
+use super::super::{
+    Proof,
+    VerifyingKey
+};
+
+use ::{
+    SynthesisError
+};
+
+use ::kzg::divide_by_linear;
+
+use ::multicore::Worker;
+
+/// Aggregates the proofs, which must be for the verifying key and
+/// at most as many as the reference string supports, with the public
+/// inputs, which are given for every proof
+pub fn aggregate_proofs<E: Engine>(
+    srs: &ProverSrs<E>,
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::Fr>],
+    proofs: &[Proof<E>]
+) -> Result<AggregateProof<E>, SynthesisError>
+{
+    // There is nothing to aggregate
+    if proofs.is_empty() {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+
+    if public_inputs.len() != proofs.len() {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+
+    if public_inputs.iter().any(|inputs| inputs.len() + 1 != vk.ic.len()) {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let m = padded_num_proofs(proofs.len());
+    if m > srs.n {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    let worker = Worker::new();
+
+    let padding = proofs.last().unwrap();
+    let proofs = proofs.iter().chain(::std::iter::repeat(padding)).take(m);
+
+    let mut a = Vec::with_capacity(m);
+    let mut b = Vec::with_capacity(m);
+    let mut c = Vec::with_capacity(m);
+    for proof in proofs {
+        a.push(proof.a);
+        b.push(proof.b);
+        c.push(proof.c);
+    }
+
+    let vkey = VKey::<E> {
+        a: srs.h_alpha_powers[0..m].to_vec(),
+        b: srs.h_beta_powers[0..m].to_vec()
+    };
+    let mut wkey = WKey::<E> {
+        a: srs.g_alpha_powers[m..(2 * m)].to_vec(),
+        b: srs.g_beta_powers[m..(2 * m)].to_vec()
+    };
+
+    let mut com_ab = vkey.commit(&a, &worker);
+    com_ab.mul_assign(&wkey.commit(&b, &worker));
+    let com_c = vkey.commit(&c, &worker);
+
+    let mut transcript = start_transcript(vk, public_inputs, m, &com_ab, &com_c);
+    let (r, r_inv) = invertible_challenge::<E::Fr>(&mut transcript);
+
+    // Scale the elements in G1 by r^i, and their key by r^-i,
+    // so the commitments stay the same
+    let mut a = scale_by_powers(&a, &r, &worker);
+    let mut c = scale_by_powers(&c, &r, &worker);
+    let mut vkey = VKey::<E> {
+        a: scale_by_powers(&vkey.a, &r_inv, &worker),
+        b: scale_by_powers(&vkey.b, &r_inv, &worker)
+    };
+
+    let ip_ab = pairing_product::<E>(&a, &b, &worker);
+    let agg_c = scaled_sum(&c, &E::Fr::one());
+
+    transcript.commit_extension(&ip_ab);
+    transcript.commit_point(&agg_c);
+
+    // The C elements are summed with the coefficients of a vector, which
+    // starts with ones, so that it stays constant while being folded
+    let mut c_coeff = E::Fr::one();
+
+    let mut rounds = vec![];
+    let mut challenges = vec![];
+    let mut challenges_inv = vec![];
+
+    while a.len() > 1 {
+        let half = a.len() / 2;
+
+        let (a_left, a_right) = a.split_at(half);
+        let (b_left, b_right) = b.split_at(half);
+        let (c_left, c_right) = c.split_at(half);
+        let (vkey_left, vkey_right) = vkey.split(half);
+        let (wkey_left, wkey_right) = wkey.split(half);
+
+        let mut com_ab_x = vkey_left.commit(a_right, &worker);
+        com_ab_x.mul_assign(&wkey_right.commit(b_left, &worker));
+        let mut com_ab_x_inv = vkey_right.commit(a_left, &worker);
+        com_ab_x_inv.mul_assign(&wkey_left.commit(b_right, &worker));
+
+        let round = Round {
+            com_ab_x: com_ab_x,
+            com_ab_x_inv: com_ab_x_inv,
+            com_c_x: vkey_left.commit(c_right, &worker),
+            com_c_x_inv: vkey_right.commit(c_left, &worker),
+            ip_ab_x: pairing_product::<E>(a_right, b_left, &worker),
+            ip_ab_x_inv: pairing_product::<E>(a_left, b_right, &worker),
+            agg_c_x: scaled_sum(c_right, &c_coeff),
+            agg_c_x_inv: scaled_sum(c_left, &c_coeff)
+        };
+
+        round.absorb_into(&mut transcript);
+        let (x, x_inv) = invertible_challenge::<E::Fr>(&mut transcript);
+
+        let next_a = fold(a_left, a_right, &x, &worker);
+        let next_b = fold(b_left, b_right, &x_inv, &worker);
+        let next_c = fold(c_left, c_right, &x, &worker);
+        vkey = vkey_left.fold(&vkey_right, &x_inv, &worker);
+        wkey = wkey_left.fold(&wkey_right, &x, &worker);
+
+        a = next_a;
+        b = next_b;
+        c = next_c;
+
+        let mut tmp = x_inv;
+        tmp.add_assign(&E::Fr::one());
+        c_coeff.mul_assign(&tmp);
+
+        rounds.push(round);
+        challenges.push(x);
+        challenges_inv.push(x_inv);
+    }
+
+    let final_vkey = (vkey.a[0], vkey.b[0]);
+    let final_wkey = (wkey.a[0], wkey.b[0]);
+
+    transcript.commit_point(&a[0]);
+    transcript.commit_point(&b[0]);
+    transcript.commit_point(&c[0]);
+    transcript.commit_point(&final_vkey.0);
+    transcript.commit_point(&final_vkey.1);
+    transcript.commit_point(&final_wkey.0);
+    transcript.commit_point(&final_wkey.1);
+
+    let z: E::Fr = transcript.challenge();
+
+    // The final key of the elements in G1 is the commitment in G2 to the
+    // polynomial f_v(X / r), and the one of the elements in G2 is the
+    // commitment in G1 to X^m * f_w(X), with the key polynomials of the
+    // inverses of the challenges and of the challenges respectively
+    let vkey_quotient = {
+        let mut coeffs = key_polynomial(&challenges_inv);
+        let mut r_inv_power = E::Fr::one();
+        for coeff in coeffs.iter_mut() {
+            coeff.mul_assign(&r_inv_power);
+            r_inv_power.mul_assign(&r_inv);
+        }
+
+        divide_by_linear(&coeffs, &z)
+    };
+
+    let wkey_quotient = {
+        let mut coeffs = vec![E::Fr::zero(); m];
+        coeffs.extend(key_polynomial(&challenges));
+
+        divide_by_linear(&coeffs, &z)
+    };
+
+    Ok(AggregateProof {
+        com_ab: com_ab,
+        com_c: com_c,
+        ip_ab: ip_ab,
+        agg_c: agg_c,
+        rounds: rounds,
+        final_a: a[0],
+        final_b: b[0],
+        final_c: c[0],
+        final_vkey: final_vkey,
+        final_wkey: final_wkey,
+        vkey_opening: (
+            commit_with_powers(&srs.h_alpha_powers, &vkey_quotient, &worker)?,
+            commit_with_powers(&srs.h_beta_powers, &vkey_quotient, &worker)?
+        ),
+        wkey_opening: (
+            commit_with_powers(&srs.g_alpha_powers, &wkey_quotient, &worker)?,
+            commit_with_powers(&srs.g_beta_powers, &wkey_quotient, &worker)?
+        )
+    })
+}
+
+/// Returns the vector of r^i * v_i
+fn scale_by_powers<G: CurveAffine>(v: &[G], r: &G::Scalar, worker: &Worker) -> Vec<G> {
+    let mut result: Vec<_> = v.iter().map(|p| p.into_projective()).collect();
+
+    worker.scope(result.len(), |scope, chunk| {
+        for (i, result) in result.chunks_mut(chunk).enumerate() {
+            scope.spawn(move || {
+                let mut r_power = r.pow(&[(i * chunk) as u64]);
+
+                for p in result.iter_mut() {
+                    p.mul_assign(r_power.into_repr());
+                    r_power.mul_assign(r);
+                }
+
+                G::Projective::batch_normalization(result);
+            });
+        }
+    });
+
+    result.into_iter().map(|p| p.into_affine()).collect()
+}
+
+/// Returns coeff * sum v_i
+fn scaled_sum<G: CurveAffine>(v: &[G], coeff: &G::Scalar) -> G {
+    let mut result = G::Projective::zero();
+
+    for p in v {
+        result.add_assign_mixed(p);
+    }
+    result.mul_assign(coeff.into_repr());
+
+    result.into_affine()
+}
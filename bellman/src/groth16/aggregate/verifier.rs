@@ -0,0 +1,239 @@
+use pairing::{
+    Engine,
+    CurveProjective,
+    CurveAffine
+};
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use super::{
+    VerifierSrs,
+    AggregateProof,
+    Commitment,
+    padded_num_proofs,
+    start_transcript,
+    invertible_challenge,
+    pairing_product,
+    evaluate_key_polynomial
+};
+
+use super::super::{
+    VerifyingKey,
+    prepare_verifying_key
+};
+
+use ::{
+    SynthesisError
+};
+
+use ::multicore::Worker;
+
+/// Verifies the aggregate of the proofs with the public inputs,
+/// which are given for every proof
+pub fn verify_aggregate_proof<E: Engine>(
+    srs: &VerifierSrs<E>,
+    vk: &VerifyingKey<E>,
+    public_inputs: &[Vec<E::Fr>],
+    proof: &AggregateProof<E>
+) -> Result<bool, SynthesisError>
+{
+    // There is nothing to verify
+    if public_inputs.is_empty() {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+
+    if public_inputs.iter().any(|inputs| inputs.len() + 1 != vk.ic.len()) {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let m = padded_num_proofs(public_inputs.len());
+    if m > srs.n {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    if proof.rounds.len() != m.trailing_zeros() as usize {
+        return Ok(false);
+    }
+
+    let worker = Worker::new();
+
+    let mut transcript = start_transcript(vk, public_inputs, m, &proof.com_ab, &proof.com_c);
+    let (r, r_inv) = invertible_challenge::<E::Fr>(&mut transcript);
+
+    transcript.commit_extension(&proof.ip_ab);
+    transcript.commit_point(&proof.agg_c);
+
+    // Fold the values the messages of the rounds
+    let mut com_ab = proof.com_ab.clone();
+    let mut com_c = proof.com_c.clone();
+    let mut ip_ab = proof.ip_ab;
+    let mut agg_c = proof.agg_c.into_projective();
+    let mut c_coeff = E::Fr::one();
+
+    let mut challenges = vec![];
+    let mut challenges_inv = vec![];
+
+    for round in proof.rounds.iter() {
+        round.absorb_into(&mut transcript);
+        let (x, x_inv) = invertible_challenge::<E::Fr>(&mut transcript);
+
+        com_ab.mul_assign(&round.com_ab_x.pow(&x));
+        com_ab.mul_assign(&round.com_ab_x_inv.pow(&x_inv));
+        com_c.mul_assign(&round.com_c_x.pow(&x));
+        com_c.mul_assign(&round.com_c_x_inv.pow(&x_inv));
+        ip_ab.mul_assign(&round.ip_ab_x.pow(x.into_repr()));
+        ip_ab.mul_assign(&round.ip_ab_x_inv.pow(x_inv.into_repr()));
+        agg_c.add_assign(&round.agg_c_x.mul(x));
+        agg_c.add_assign(&round.agg_c_x_inv.mul(x_inv));
+
+        let mut tmp = x_inv;
+        tmp.add_assign(&E::Fr::one());
+        c_coeff.mul_assign(&tmp);
+
+        challenges.push(x);
+        challenges_inv.push(x_inv);
+    }
+
+    transcript.commit_point(&proof.final_a);
+    transcript.commit_point(&proof.final_b);
+    transcript.commit_point(&proof.final_c);
+    transcript.commit_point(&proof.final_vkey.0);
+    transcript.commit_point(&proof.final_vkey.1);
+    transcript.commit_point(&proof.final_wkey.0);
+    transcript.commit_point(&proof.final_wkey.1);
+
+    let z: E::Fr = transcript.challenge();
+
+    // The folded values are the ones of the final elements and keys
+    let (va, vb) = proof.final_vkey;
+    let (wa, wb) = proof.final_wkey;
+
+    if ip_ab != pairing_product::<E>(&[proof.final_a], &[proof.final_b], &worker) {
+        return Ok(false);
+    }
+
+    let final_com_ab = Commitment::<E> {
+        t: pairing_product::<E>(&[proof.final_a, wa], &[va, proof.final_b], &worker),
+        u: pairing_product::<E>(&[proof.final_a, wb], &[vb, proof.final_b], &worker)
+    };
+    if com_ab != final_com_ab {
+        return Ok(false);
+    }
+
+    let final_com_c = Commitment::<E> {
+        t: pairing_product::<E>(&[proof.final_c], &[va], &worker),
+        u: pairing_product::<E>(&[proof.final_c], &[vb], &worker)
+    };
+    if com_c != final_com_c {
+        return Ok(false);
+    }
+
+    if agg_c != proof.final_c.mul(c_coeff) {
+        return Ok(false);
+    }
+
+    // The final keys are the commitments to the key polynomials,
+    // opened at z with e(x - z, opening) = e(1, key - f(z))
+    let g = E::G1Affine::one();
+    let h = E::G2Affine::one();
+    let mut neg_g = g;
+    neg_g.negate();
+
+    let vkey_eval = {
+        let mut z_r_inv = z;
+        z_r_inv.mul_assign(&r_inv);
+
+        evaluate_key_polynomial(&challenges_inv, &z_r_inv)
+    };
+
+    for &(g_x, key, opening) in [
+        (srs.g_alpha, va, proof.vkey_opening.0),
+        (srs.g_beta, vb, proof.vkey_opening.1)
+    ].iter() {
+        let mut g_x_minus_z = g_x.into_projective();
+        g_x_minus_z.sub_assign(&g.mul(z));
+
+        let mut key_minus_eval = key.into_projective();
+        key_minus_eval.sub_assign(&h.mul(vkey_eval));
+
+        let result = pairing_product::<E>(
+            &[g_x_minus_z.into_affine(), neg_g],
+            &[opening, key_minus_eval.into_affine()],
+            &worker
+        );
+        if result != E::Fqk::one() {
+            return Ok(false);
+        }
+    }
+
+    let wkey_eval = {
+        let mut eval = evaluate_key_polynomial(&challenges, &z);
+        eval.mul_assign(&z.pow(&[m as u64]));
+
+        eval
+    };
+
+    for &(h_x, key, opening) in [
+        (srs.h_alpha, wa, proof.wkey_opening.0),
+        (srs.h_beta, wb, proof.wkey_opening.1)
+    ].iter() {
+        let mut h_x_minus_z = h_x.into_projective();
+        h_x_minus_z.sub_assign(&h.mul(z));
+
+        let mut key_minus_eval = key.into_projective();
+        key_minus_eval.sub_assign(&g.mul(wkey_eval));
+        key_minus_eval.negate();
+
+        let result = pairing_product::<E>(
+            &[opening, key_minus_eval.into_affine()],
+            &[h_x_minus_z.into_affine(), h],
+            &worker
+        );
+        if result != E::Fqk::one() {
+            return Ok(false);
+        }
+    }
+
+    // The sum of the verification equations of the proofs times r^i is
+    // prod e(A_i, B_i)^(r^i) = e(alpha, beta)^(sum r^i)
+    //                          * e(sum r^i * (ic_0 + sum x_ij * ic_j), gamma)
+    //                          * e(sum r^i * C_i, delta)
+    let pvk = prepare_verifying_key(vk);
+
+    let mut r_sum = E::Fr::zero();
+    let mut input_sums = vec![E::Fr::zero(); pvk.ic.len() - 1];
+    {
+        let padding = public_inputs.last().unwrap();
+        let mut r_power = E::Fr::one();
+
+        for inputs in public_inputs.iter().chain(::std::iter::repeat(padding)).take(m) {
+            r_sum.add_assign(&r_power);
+
+            for (sum, input) in input_sums.iter_mut().zip(inputs.iter()) {
+                let mut tmp = *input;
+                tmp.mul_assign(&r_power);
+                sum.add_assign(&tmp);
+            }
+
+            r_power.mul_assign(&r);
+        }
+    }
+
+    let mut acc = pvk.ic[0].mul(r_sum);
+    for (sum, b) in input_sums.iter().zip(pvk.ic.iter().skip(1)) {
+        acc.add_assign(&b.mul(*sum));
+    }
+
+    let mut result = E::final_exponentiation(
+        &E::miller_loop([
+            (&acc.into_affine().prepare(), &pvk.neg_gamma_g2),
+            (&proof.agg_c.prepare(), &pvk.neg_delta_g2)
+        ].iter())
+    ).unwrap();
+    result.mul_assign(&proof.ip_ab);
+
+    Ok(result == pvk.alpha_g1_beta_g2.pow(r_sum.into_repr()))
+}
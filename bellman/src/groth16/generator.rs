@@ -29,6 +29,7 @@ use ::{
 };
 
 use ::domain::{
+    Domain,
     EvaluationDomain,
     Scalar
 };
@@ -214,8 +215,11 @@ pub fn generate_parameters<E, C>(
     }
 
     // Create bases for blind evaluation of polynomials at tau
+    // over the smallest evaluation domain with enough roots of unity,
+    // which the proofs will share
+    let domain = Arc::new(Domain::new_mixed_radix(assembly.num_constraints)?);
     let powers_of_tau = vec![Scalar::<E>(E::Fr::zero()); assembly.num_constraints];
-    let mut powers_of_tau = EvaluationDomain::from_coeffs(powers_of_tau)?;
+    let mut powers_of_tau = EvaluationDomain::from_coeffs_in(powers_of_tau, &domain)?;
 
     // Compute G1 window table
     let mut g1_wnaf = Wnaf::new();
@@ -480,6 +484,7 @@ pub fn generate_parameters<E, C>(
         // Filter points at infinity away from A/B queries
         a: Arc::new(a.into_iter().filter(|e| !e.is_zero()).map(|e| e.into_affine()).collect()),
         b_g1: Arc::new(b_g1.into_iter().filter(|e| !e.is_zero()).map(|e| e.into_affine()).collect()),
-        b_g2: Arc::new(b_g2.into_iter().filter(|e| !e.is_zero()).map(|e| e.into_affine()).collect())
+        b_g2: Arc::new(b_g2.into_iter().filter(|e| !e.is_zero()).map(|e| e.into_affine()).collect()),
+        domain: domain
     })
 }
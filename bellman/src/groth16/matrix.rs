@@ -0,0 +1,665 @@
+use pairing::Engine;
+
+use ff::{
+    Field,
+    PrimeField,
+    PrimeFieldRepr
+};
+
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+use byteorder::{BigEndian, WriteBytesExt, ReadBytesExt};
+
+use ::{
+    SynthesisError,
+    Circuit,
+    ConstraintSystem,
+    LinearCombination,
+    Variable,
+    Index
+};
+
+use ::multiexp::DensityTracker;
+
+/// The A, B and C matrices of a circuit, one linear combination
+/// per constraint, including the constraints of the form
+/// x * 0 = 0 added for every input. The densities of the queries
+/// are derived once, so that they don't have to be tracked again
+/// for every proof.
+#[derive(Clone)]
+pub struct ConstraintMatrices<E: Engine> {
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    pub a: Vec<LinearCombination<E>>,
+    pub b: Vec<LinearCombination<E>>,
+    pub c: Vec<LinearCombination<E>>,
+
+    /// Number of aux variables in the assignment of the circuit
+    pub(crate) num_witness_aux: usize,
+    /// Index in the assignment of the circuit of every aux
+    /// variable, if some of them were eliminated by `optimize`
+    pub(crate) witness_aux: Option<Vec<usize>>,
+
+    pub(crate) a_aux_density: DensityTracker,
+    pub(crate) b_input_density: DensityTracker,
+    pub(crate) b_aux_density: DensityTracker
+}
+
+fn write_lc<E: Engine, W: Write>(
+    lc: &LinearCombination<E>,
+    writer: &mut W
+) -> io::Result<()>
+{
+    writer.write_u32::<BigEndian>(lc.0.len() as u32)?;
+
+    for &(var, coeff) in lc.0.iter() {
+        match var {
+            Variable(Index::Input(i)) => {
+                writer.write_u8(0)?;
+                writer.write_u32::<BigEndian>(i as u32)?;
+            },
+            Variable(Index::Aux(i)) => {
+                writer.write_u8(1)?;
+                writer.write_u32::<BigEndian>(i as u32)?;
+            }
+        }
+
+        coeff.into_repr().write_be(&mut *writer)?;
+    }
+
+    Ok(())
+}
+
+fn read_lc<E: Engine, R: Read>(
+    reader: &mut R,
+    num_inputs: usize,
+    num_aux: usize
+) -> io::Result<LinearCombination<E>>
+{
+    let len = reader.read_u32::<BigEndian>()? as usize;
+
+    let mut terms = vec![];
+
+    for _ in 0..len {
+        let tag = reader.read_u8()?;
+        let index = reader.read_u32::<BigEndian>()? as usize;
+
+        let var = match tag {
+            0 if index < num_inputs => Variable(Index::Input(index)),
+            1 if index < num_aux => Variable(Index::Aux(index)),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid variable"))
+        };
+
+        let mut repr = <E::Fr as PrimeField>::Repr::default();
+        repr.read_be(&mut *reader)?;
+        let coeff = E::Fr::from_repr(repr).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        terms.push((var, coeff));
+    }
+
+    Ok(LinearCombination(terms))
+}
+
+impl<E: Engine> ConstraintMatrices<E> {
+    /// Synthesizes the structure of the circuit, without
+    /// computing any assignments.
+    pub fn synthesize<C: Circuit<E>>(circuit: C) -> Result<Self, SynthesisError> {
+        let mut assembly = ConstraintMatrices {
+            num_inputs: 0,
+            num_aux: 0,
+            a: vec![],
+            b: vec![],
+            c: vec![],
+            num_witness_aux: 0,
+            witness_aux: None,
+            a_aux_density: DensityTracker::new(),
+            b_input_density: DensityTracker::new(),
+            b_aux_density: DensityTracker::new()
+        };
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        circuit.synthesize(&mut assembly)?;
+
+        // Input constraints to ensure full density of IC query
+        // x * 0 = 0
+        for i in 0..assembly.num_inputs {
+            assembly.enforce(|| "",
+                |lc| lc + Variable(Index::Input(i)),
+                |lc| lc,
+                |lc| lc,
+            );
+        }
+
+        assembly.num_witness_aux = assembly.num_aux;
+        assembly.compute_densities();
+
+        Ok(assembly)
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.a.len()
+    }
+
+    fn compute_densities(&mut self) {
+        self.a_aux_density = DensityTracker::new();
+        self.b_input_density = DensityTracker::new();
+        self.b_aux_density = DensityTracker::new();
+
+        for _ in 0..self.num_inputs {
+            self.b_input_density.add_element();
+        }
+
+        for _ in 0..self.num_aux {
+            self.a_aux_density.add_element();
+            self.b_aux_density.add_element();
+        }
+
+        // Inputs have full density in the A query
+        // because of the constraints of the form
+        // x * 0 = 0, and there is no C query.
+        for lc in self.a.iter() {
+            for &(var, _) in lc.0.iter() {
+                if let Variable(Index::Aux(i)) = var {
+                    self.a_aux_density.inc(i);
+                }
+            }
+        }
+
+        for lc in self.b.iter() {
+            for &(var, _) in lc.0.iter() {
+                match var {
+                    Variable(Index::Input(i)) => self.b_input_density.inc(i),
+                    Variable(Index::Aux(i)) => self.b_aux_density.inc(i)
+                }
+            }
+        }
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        writer.write_u32::<BigEndian>(self.num_inputs as u32)?;
+        writer.write_u32::<BigEndian>(self.num_aux as u32)?;
+        writer.write_u32::<BigEndian>(self.num_constraints() as u32)?;
+
+        for lc in self.a.iter().chain(self.b.iter()).chain(self.c.iter()) {
+            write_lc(lc, &mut writer)?;
+        }
+
+        writer.write_u32::<BigEndian>(self.num_witness_aux as u32)?;
+
+        match self.witness_aux {
+            Some(ref indices) => {
+                writer.write_u8(1)?;
+                for i in indices {
+                    writer.write_u32::<BigEndian>(*i as u32)?;
+                }
+            },
+            None => {
+                writer.write_u8(0)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(
+        mut reader: R
+    ) -> io::Result<Self>
+    {
+        let num_inputs = reader.read_u32::<BigEndian>()? as usize;
+        let num_aux = reader.read_u32::<BigEndian>()? as usize;
+        let num_constraints = reader.read_u32::<BigEndian>()? as usize;
+
+        let read_matrix = |reader: &mut R| -> io::Result<Vec<LinearCombination<E>>> {
+            (0..num_constraints).map(|_| read_lc(reader, num_inputs, num_aux)).collect()
+        };
+
+        let a = read_matrix(&mut reader)?;
+        let b = read_matrix(&mut reader)?;
+        let c = read_matrix(&mut reader)?;
+
+        let num_witness_aux = reader.read_u32::<BigEndian>()? as usize;
+
+        let witness_aux = match reader.read_u8()? {
+            0 if num_witness_aux == num_aux => None,
+            1 => {
+                let mut indices = vec![];
+                for _ in 0..num_aux {
+                    let i = reader.read_u32::<BigEndian>()? as usize;
+                    if i >= num_witness_aux {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid witness index"));
+                    }
+                    indices.push(i);
+                }
+
+                Some(indices)
+            },
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid witness indices"))
+        };
+
+        let mut matrices = ConstraintMatrices {
+            num_inputs: num_inputs,
+            num_aux: num_aux,
+            a: a,
+            b: b,
+            c: c,
+            num_witness_aux: num_witness_aux,
+            witness_aux: witness_aux,
+            a_aux_density: DensityTracker::new(),
+            b_input_density: DensityTracker::new(),
+            b_aux_density: DensityTracker::new()
+        };
+
+        matrices.compute_densities();
+
+        Ok(matrices)
+    }
+}
+
+/// If the linear combination is `c * ONE`, returns `c`
+fn as_constant<E: Engine>(lc: &LinearCombination<E>) -> Option<E::Fr> {
+    match lc.0.as_slice() {
+        &[(Variable(Index::Input(0)), coeff)] => Some(coeff),
+        _ => None
+    }
+}
+
+/// If the linear combination is `c * var` for an aux
+/// variable, returns its index and `c`
+fn as_aux<E: Engine>(lc: &LinearCombination<E>) -> Option<(usize, E::Fr)> {
+    match lc.0.as_slice() {
+        &[(Variable(Index::Aux(i)), coeff)] => Some((i, coeff)),
+        _ => None
+    }
+}
+
+fn coeff_of<E: Engine>(lc: &LinearCombination<E>, index: usize) -> Option<E::Fr> {
+    lc.0.iter().find(|&&(var, _)| var.0 == Index::Aux(index)).map(|&(_, coeff)| coeff)
+}
+
+/// Replaces the aux variable by `value` in the linear combination
+fn substitute<E: Engine>(
+    lc: &mut LinearCombination<E>,
+    index: usize,
+    value: &LinearCombination<E>
+)
+{
+    if let Some(coeff) = coeff_of(lc, index) {
+        let mut tmp = LinearCombination(
+            lc.0.drain(..).filter(|&(var, _)| var.0 != Index::Aux(index)).collect()
+        );
+        tmp = tmp + (coeff, value);
+
+        *lc = tmp.normalize();
+    }
+}
+
+impl<E: Engine> ConstraintMatrices<E> {
+    /// Eliminates the constraints of the form `lc * c = var` (or
+    /// `c * lc = var`) for a constant `c` and an aux variable `var`,
+    /// by substituting `lc * c` for `var` in the other constraints.
+    /// The parameters have to be generated from the optimized matrices,
+    /// e.g. by wrapping the circuit in `OptimizedCircuit`, and proofs
+    /// created with `create_proof_with_assignment` still take the
+    /// assignment of the original circuit.
+    pub fn optimize(&mut self) {
+        // Constraints that every aux variable appears in
+        let mut occurrences = vec![vec![]; self.num_aux];
+        for (row, ((a, b), c)) in self.a.iter().zip(self.b.iter()).zip(self.c.iter()).enumerate() {
+            for &(var, _) in a.0.iter().chain(b.0.iter()).chain(c.0.iter()) {
+                if let Variable(Index::Aux(i)) = var {
+                    occurrences[i].push(row);
+                }
+            }
+        }
+
+        let mut removed_rows = vec![false; self.a.len()];
+        let mut removed_aux = vec![false; self.num_aux];
+
+        for row in 0..self.a.len() {
+            let (index, value) = {
+                let (lc, constant) = match (as_constant(&self.a[row]), as_constant(&self.b[row])) {
+                    (_, Some(constant)) => (&self.a[row], constant),
+                    (Some(constant), None) => (&self.b[row], constant),
+                    (None, None) => continue
+                };
+
+                let (index, coeff) = match as_aux(&self.c[row]) {
+                    Some(v) => v,
+                    None => continue
+                };
+
+                // A zero coefficient, which the matrices read from a
+                // reader may have, doesn't determine the variable
+                let coeff_inverse = match coeff.inverse() {
+                    Some(inverse) => inverse,
+                    None => continue
+                };
+
+                // The variable must be used elsewhere, so that
+                // the variables of `lc` stay constrained
+                if coeff_of(lc, index).is_some() ||
+                   !occurrences[index].iter().any(|&r| r != row && !removed_rows[r])
+                {
+                    continue;
+                }
+
+                // var = lc * constant / coeff
+                let mut scale = coeff_inverse;
+                scale.mul_assign(&constant);
+
+                (index, (LinearCombination::zero() + (scale, lc)).normalize())
+            };
+
+            removed_rows[row] = true;
+            removed_aux[index] = true;
+
+            let rows = ::std::mem::replace(&mut occurrences[index], vec![]);
+            for r in rows {
+                if removed_rows[r] {
+                    continue;
+                }
+
+                substitute(&mut self.a[r], index, &value);
+                substitute(&mut self.b[r], index, &value);
+                substitute(&mut self.c[r], index, &value);
+
+                for &(var, _) in value.0.iter() {
+                    if let Variable(Index::Aux(i)) = var {
+                        occurrences[i].push(r);
+                    }
+                }
+            }
+        }
+
+        // Renumber the remaining aux variables
+        let mut new_index = vec![0; self.num_aux];
+        let mut witness_aux = vec![];
+        for i in 0..self.num_aux {
+            if !removed_aux[i] {
+                new_index[i] = witness_aux.len();
+                witness_aux.push(match self.witness_aux {
+                    Some(ref indices) => indices[i],
+                    None => i
+                });
+            }
+        }
+
+        let renumber = |lc: LinearCombination<E>| {
+            LinearCombination(lc.0.into_iter().map(|(var, coeff)| match var {
+                Variable(Index::Aux(i)) => (Variable(Index::Aux(new_index[i])), coeff),
+                var => (var, coeff)
+            }).collect())
+        };
+
+        let keep = |m: &mut Vec<LinearCombination<E>>| {
+            let rows = m.drain(..).zip(removed_rows.iter())
+                        .filter(|&(_, removed)| !removed)
+                        .map(|(lc, _)| renumber(lc))
+                        .collect();
+            *m = rows;
+        };
+
+        keep(&mut self.a);
+        keep(&mut self.b);
+        keep(&mut self.c);
+
+        self.num_aux = witness_aux.len();
+        self.witness_aux = Some(witness_aux);
+        self.compute_densities();
+    }
+}
+
+impl<E: Engine> ConstraintMatrices<E> {
+    /// Enforces the constraints of the matrices in the constraint system,
+    /// with the assignment of the original circuit if there is one.
+    fn synthesize_into<CS: ConstraintSystem<E>>(
+        &self,
+        cs: &mut CS,
+        witness: Option<&WitnessCalculator<E>>
+    ) -> Result<(), SynthesisError>
+    {
+        let mut inputs = vec![CS::one()];
+        for i in 1..self.num_inputs {
+            inputs.push(cs.alloc_input(|| format!("input {}", i), || {
+                witness.and_then(|w| w.input_assignment.get(i).cloned())
+                       .ok_or(SynthesisError::AssignmentMissing)
+            })?);
+        }
+
+        let mut aux = vec![];
+        for i in 0..self.num_aux {
+            let index = match self.witness_aux {
+                Some(ref indices) => indices[i],
+                None => i
+            };
+
+            aux.push(cs.alloc(|| format!("aux {}", i), || {
+                witness.and_then(|w| w.aux_assignment.get(index).cloned())
+                       .ok_or(SynthesisError::AssignmentMissing)
+            })?);
+        }
+
+        let lc = |lc: &LinearCombination<E>, mut acc: LinearCombination<E>| {
+            for &(var, coeff) in lc.0.iter() {
+                acc = acc + (coeff, match var {
+                    Variable(Index::Input(i)) => inputs[i],
+                    Variable(Index::Aux(i)) => aux[i]
+                });
+            }
+
+            acc
+        };
+
+        // The constraints of the inputs are added again
+        // when generating the parameters or proving
+        let num_constraints = self.num_constraints() - self.num_inputs;
+
+        for i in 0..num_constraints {
+            cs.enforce(
+                || format!("constraint {}", i),
+                |acc| lc(&self.a[i], acc),
+                |acc| lc(&self.b[i], acc),
+                |acc| lc(&self.c[i], acc)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The matrices can be synthesized as a circuit without an assignment,
+/// e.g. to generate the parameters for optimized matrices.
+impl<'a, E: Engine> Circuit<E> for &'a ConstraintMatrices<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        self.synthesize_into(cs, None)
+    }
+}
+
+/// A circuit whose constraint system is optimized with
+/// `ConstraintMatrices::optimize` whenever it is synthesized, so
+/// that `generate_parameters` and `create_proof` both see the same
+/// optimized constraints. The circuit is synthesized twice, once
+/// for its structure and once for its assignment.
+#[derive(Clone)]
+pub struct OptimizedCircuit<C>(pub C);
+
+impl<E: Engine, C: Circuit<E> + Clone> Circuit<E> for OptimizedCircuit<C> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        let mut matrices = ConstraintMatrices::synthesize(self.0.clone())?;
+        matrices.optimize();
+
+        // There is no assignment when generating the parameters
+        let witness = match WitnessCalculator::calculate(self.0) {
+            Ok(witness) => Some(witness),
+            Err(SynthesisError::AssignmentMissing) => None,
+            Err(e) => return Err(e)
+        };
+
+        matrices.synthesize_into(cs, witness.as_ref())
+    }
+}
+
+impl<E: Engine> ConstraintSystem<E> for ConstraintMatrices<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        // There is no assignment, so we don't even invoke the
+        // function for obtaining one.
+
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        Ok(Variable(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        // There is no assignment, so we don't even invoke the
+        // function for obtaining one.
+
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        Ok(Variable(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        a: LA,
+        b: LB,
+        c: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        self.a.push(a(LinearCombination::zero()).normalize());
+        self.b.push(b(LinearCombination::zero()).normalize());
+        self.c.push(c(LinearCombination::zero()).normalize());
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+        where NR: Into<String>, N: FnOnce() -> NR
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self)
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
+
+/// Computes the assignment of a circuit without
+/// constructing any of its linear combinations.
+pub struct WitnessCalculator<E: Engine> {
+    pub input_assignment: Vec<E::Fr>,
+    pub aux_assignment: Vec<E::Fr>,
+    _marker: PhantomData<E>
+}
+
+impl<E: Engine> WitnessCalculator<E> {
+    pub fn calculate<C: Circuit<E>>(circuit: C) -> Result<Self, SynthesisError> {
+        let mut calculator = WitnessCalculator {
+            input_assignment: vec![],
+            aux_assignment: vec![],
+            _marker: PhantomData
+        };
+
+        calculator.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        circuit.synthesize(&mut calculator)?;
+
+        Ok(calculator)
+    }
+}
+
+impl<E: Engine> ConstraintSystem<E> for WitnessCalculator<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        self.aux_assignment.push(f()?);
+
+        Ok(Variable(Index::Aux(self.aux_assignment.len() - 1)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        self.input_assignment.push(f()?);
+
+        Ok(Variable(Index::Input(self.input_assignment.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        _: LA,
+        _: LB,
+        _: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        // Only the assignment is needed, so the linear
+        // combinations are never constructed.
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+        where NR: Into<String>, N: FnOnce() -> NR
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self)
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
@@ -8,19 +8,26 @@ use ::{
     SynthesisError
 };
 
-use multiexp::SourceBuilder;
+use domain::Domain;
+use multiexp::{SourceBuilder, FixedBaseTable};
+use multicore::Worker;
 use std::io::{self, Read, Write};
+use std::mem;
 use std::sync::Arc;
 use byteorder::{BigEndian, WriteBytesExt, ReadBytesExt};
 
 #[cfg(test)]
 mod tests;
 
+pub mod aggregate;
+
 mod generator;
+mod matrix;
 mod prover;
 mod verifier;
 
 pub use self::generator::*;
+pub use self::matrix::*;
 pub use self::prover::*;
 pub use self::verifier::*;
 
@@ -234,7 +241,11 @@ pub struct Parameters<E: Engine> {
     // G1 and G2 for C/B queries, respectively. Never contains points at
     // infinity for the same reason as the "A" polynomials.
     pub b_g1: Arc<Vec<E::G1Affine>>,
-    pub b_g2: Arc<Vec<E::G2Affine>>
+    pub b_g2: Arc<Vec<E::G2Affine>>,
+
+    // The evaluation domain of the constraints, of size m. It is determined
+    // by the H query, and kept so that proofs share its powers.
+    pub domain: Arc<Domain<E>>
 }
 
 impl<E: Engine> PartialEq for Parameters<E> {
@@ -370,13 +381,20 @@ impl<E: Engine> Parameters<E> {
             }
         }
 
+        let domain = Domain::new_mixed_radix(h.len() + 1)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if domain.size() != h.len() + 1 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "size of the H query doesn't match an evaluation domain"));
+        }
+
         Ok(Parameters {
             vk: vk,
             h: Arc::new(h),
             l: Arc::new(l),
             a: Arc::new(a),
             b_g1: Arc::new(b_g1),
-            b_g2: Arc::new(b_g2)
+            b_g2: Arc::new(b_g2),
+            domain: Arc::new(domain)
         })
     }
 }
@@ -400,6 +418,10 @@ pub trait ParameterSource<E: Engine> {
         &mut self,
         num_ic: usize
     ) -> Result<VerifyingKey<E>, SynthesisError>;
+    fn get_domain(
+        &mut self,
+        num_constraints: usize
+    ) -> Result<Arc<Domain<E>>, SynthesisError>;
     fn get_h(
         &mut self,
         num_h: usize
@@ -437,6 +459,168 @@ impl<'a, E: Engine> ParameterSource<E> for &'a Parameters<E> {
         Ok(self.vk.clone())
     }
 
+    fn get_domain(
+        &mut self,
+        _: usize
+    ) -> Result<Arc<Domain<E>>, SynthesisError>
+    {
+        Ok(self.domain.clone())
+    }
+
+    fn get_h(
+        &mut self,
+        _: usize
+    ) -> Result<Self::G1Builder, SynthesisError>
+    {
+        Ok((self.h.clone(), 0))
+    }
+
+    fn get_l(
+        &mut self,
+        _: usize
+    ) -> Result<Self::G1Builder, SynthesisError>
+    {
+        Ok((self.l.clone(), 0))
+    }
+
+    fn get_a(
+        &mut self,
+        num_inputs: usize,
+        _: usize
+    ) -> Result<(Self::G1Builder, Self::G1Builder), SynthesisError>
+    {
+        Ok(((self.a.clone(), 0), (self.a.clone(), num_inputs)))
+    }
+
+    fn get_b_g1(
+        &mut self,
+        num_inputs: usize,
+        _: usize
+    ) -> Result<(Self::G1Builder, Self::G1Builder), SynthesisError>
+    {
+        Ok(((self.b_g1.clone(), 0), (self.b_g1.clone(), num_inputs)))
+    }
+
+    fn get_b_g2(
+        &mut self,
+        num_inputs: usize,
+        _: usize
+    ) -> Result<(Self::G2Builder, Self::G2Builder), SynthesisError>
+    {
+        Ok(((self.b_g2.clone(), 0), (self.b_g2.clone(), num_inputs)))
+    }
+}
+
+/// A query of the parameters, with the table of the multiples
+/// of its bases if it has been precomputed.
+#[derive(Clone)]
+pub struct PrecomputedQuery<G: CurveAffine> {
+    bases: Arc<Vec<G>>,
+    table: Option<FixedBaseTable<G>>
+}
+
+impl<G: CurveAffine> PrecomputedQuery<G> {
+    fn new(
+        worker: &Worker,
+        bases: &Arc<Vec<G>>,
+        memory_budget: &mut usize
+    ) -> Result<PrecomputedQuery<G>, SynthesisError>
+    {
+        let point_size = mem::size_of::<G>();
+        let window = FixedBaseTable::<G>::recommended_window(bases.len(), *memory_budget / point_size);
+
+        let table = match window {
+            Some(c) => {
+                let table = FixedBaseTable::new(worker, bases, c)?;
+                *memory_budget -= table.num_points() * point_size;
+
+                Some(table)
+            },
+            None => None
+        };
+
+        Ok(PrecomputedQuery {
+            bases: bases.clone(),
+            table: table
+        })
+    }
+
+    /// Returns true if the table of the query has been precomputed.
+    pub fn is_precomputed(&self) -> bool {
+        self.table.is_some()
+    }
+}
+
+impl<G: CurveAffine> SourceBuilder<G> for (PrecomputedQuery<G>, usize) {
+    type Source = (Arc<Vec<G>>, usize);
+
+    fn new(self) -> (Arc<Vec<G>>, usize) {
+        (self.0.bases, self.1)
+    }
+
+    fn fixed_base_table(&self) -> Option<(&FixedBaseTable<G>, usize)> {
+        self.0.table.as_ref().map(|table| (table, self.1))
+    }
+}
+
+/// Parameters whose queries have tables of the multiples of their bases,
+/// which make the multi-exponentiations of proofs faster.
+#[derive(Clone)]
+pub struct PrecomputedParameters<E: Engine> {
+    pub vk: VerifyingKey<E>,
+    pub h: PrecomputedQuery<E::G1Affine>,
+    pub l: PrecomputedQuery<E::G1Affine>,
+    pub a: PrecomputedQuery<E::G1Affine>,
+    pub b_g1: PrecomputedQuery<E::G1Affine>,
+    pub b_g2: PrecomputedQuery<E::G2Affine>,
+    pub domain: Arc<Domain<E>>
+}
+
+impl<E: Engine> Parameters<E> {
+    /// Precomputes the tables of the queries using at most `memory_budget`
+    /// bytes for them, in addition to the parameters they share the bases
+    /// with. The queries are considered in the order in which they appear
+    /// in the parameters, and those which don't fit are left as they are.
+    pub fn precompute(
+        &self,
+        memory_budget: usize
+    ) -> Result<PrecomputedParameters<E>, SynthesisError>
+    {
+        let worker = Worker::new();
+        let mut budget = memory_budget;
+
+        Ok(PrecomputedParameters {
+            vk: self.vk.clone(),
+            h: PrecomputedQuery::new(&worker, &self.h, &mut budget)?,
+            l: PrecomputedQuery::new(&worker, &self.l, &mut budget)?,
+            a: PrecomputedQuery::new(&worker, &self.a, &mut budget)?,
+            b_g1: PrecomputedQuery::new(&worker, &self.b_g1, &mut budget)?,
+            b_g2: PrecomputedQuery::new(&worker, &self.b_g2, &mut budget)?,
+            domain: self.domain.clone()
+        })
+    }
+}
+
+impl<'a, E: Engine> ParameterSource<E> for &'a PrecomputedParameters<E> {
+    type G1Builder = (PrecomputedQuery<E::G1Affine>, usize);
+    type G2Builder = (PrecomputedQuery<E::G2Affine>, usize);
+
+    fn get_vk(
+        &mut self,
+        _: usize
+    ) -> Result<VerifyingKey<E>, SynthesisError>
+    {
+        Ok(self.vk.clone())
+    }
+
+    fn get_domain(
+        &mut self,
+        _: usize
+    ) -> Result<Arc<Domain<E>>, SynthesisError>
+    {
+        Ok(self.domain.clone())
+    }
+
     fn get_h(
         &mut self,
         _: usize
@@ -484,46 +668,14 @@ impl<'a, E: Engine> ParameterSource<E> for &'a Parameters<E> {
 #[cfg(test)]
 mod test_with_bls12_381 {
     use super::*;
-    use {Circuit, SynthesisError, ConstraintSystem};
+    use tests::MySillyCircuit;
 
     use rand::{Rand, thread_rng};
-    use pairing::{Field};
+    use ff::Field;
     use pairing::bls12_381::{Bls12, Fr};
 
     #[test]
     fn serialization() {
-        struct MySillyCircuit<E: Engine> {
-            a: Option<E::Fr>,
-            b: Option<E::Fr>
-        }
-
-        impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
-            fn synthesize<CS: ConstraintSystem<E>>(
-                self,
-                cs: &mut CS
-            ) -> Result<(), SynthesisError>
-            {
-                let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
-                let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
-                let c = cs.alloc_input(|| "c", || {
-                    let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
-                    let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
-
-                    a.mul_assign(&b);
-                    Ok(a)
-                })?;
-
-                cs.enforce(
-                    || "a*b=c",
-                    |lc| lc + a,
-                    |lc| lc + b,
-                    |lc| lc + c
-                );
-
-                Ok(())
-            }
-        }
-
         let rng = &mut thread_rng();
 
         let params = generate_random_parameters::<Bls12, _, _>(
@@ -535,7 +687,8 @@ mod test_with_bls12_381 {
             let mut v = vec![];
 
             params.write(&mut v).unwrap();
-            assert_eq!(v.len(), 2136);
+            // The three constraints have a domain of size 3
+            assert_eq!(v.len(), 2040);
 
             let de_params = Parameters::read(&v[..], true).unwrap();
             assert!(params == de_params);
@@ -573,4 +726,175 @@ mod test_with_bls12_381 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
+
+    #[test]
+    fn mixed_radix_domain() {
+        use {Circuit, ConstraintSystem, SynthesisError};
+
+        // Proves knowledge of x such that x^(n+1) = y
+        struct PowerCircuit {
+            x: Option<Fr>,
+            n: usize
+        }
+
+        impl Circuit<Bls12> for PowerCircuit {
+            fn synthesize<CS: ConstraintSystem<Bls12>>(
+                self,
+                cs: &mut CS
+            ) -> Result<(), SynthesisError>
+            {
+                let x = self.x;
+                let x_var = cs.alloc(|| "x", || x.ok_or(SynthesisError::AssignmentMissing))?;
+
+                let mut acc = x;
+                let mut acc_var = x_var;
+                for i in 0..self.n {
+                    let next = acc.map(|mut acc| { acc.mul_assign(&x.unwrap()); acc });
+                    let next_var = if i + 1 == self.n {
+                        cs.alloc_input(|| "y", || next.ok_or(SynthesisError::AssignmentMissing))?
+                    } else {
+                        cs.alloc(|| "acc", || next.ok_or(SynthesisError::AssignmentMissing))?
+                    };
+
+                    cs.enforce(
+                        || "acc*x=next",
+                        |lc| lc + acc_var,
+                        |lc| lc + x_var,
+                        |lc| lc + next_var
+                    );
+
+                    acc = next;
+                    acc_var = next_var;
+                }
+
+                Ok(())
+            }
+        }
+
+        let rng = &mut thread_rng();
+
+        // 4 constraints and the ones of the two inputs fit in 2 * 3 roots of unity
+        let params = generate_random_parameters::<Bls12, _, _>(
+            PowerCircuit { x: None, n: 4 },
+            rng
+        ).unwrap();
+        assert_eq!(params.domain.size(), 6);
+        assert_eq!(params.h.len(), 5);
+
+        let mut v = vec![];
+        params.write(&mut v).unwrap();
+        let de_params = Parameters::read(&v[..], false).unwrap();
+        assert_eq!(de_params.domain.size(), 6);
+        assert_eq!(de_params.domain.omega(), params.domain.omega());
+
+        let pvk = prepare_verifying_key::<Bls12>(&params.vk);
+
+        for params in [&params, &de_params].iter() {
+            let x = Fr::rand(rng);
+            let y = x.pow(&[5]);
+
+            let proof = create_random_proof(
+                PowerCircuit { x: Some(x), n: 4 },
+                *params,
+                rng
+            ).unwrap();
+
+            assert!(verify_proof(&pvk, &proof, &[y]).unwrap());
+            assert!(!verify_proof(&pvk, &proof, &[x]).unwrap());
+        }
+    }
+
+    #[test]
+    fn rerandomization() {
+        let rng = &mut thread_rng();
+
+        let params = generate_random_parameters::<Bls12, _, _>(
+            MySillyCircuit { a: None, b: None },
+            rng
+        ).unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12>(&params.vk);
+
+        for _ in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b)
+                },
+                &params,
+                rng
+            ).unwrap();
+
+            let rerandomized = rerandomize_proof(&params.vk, &proof, rng);
+
+            assert!(verify_proof(&pvk, &rerandomized, &[c]).unwrap());
+            assert!(!verify_proof(&pvk, &rerandomized, &[a]).unwrap());
+            assert!(rerandomized.a != proof.a);
+            assert!(rerandomized.b != proof.b);
+            assert!(rerandomized.c != proof.c);
+
+            let again = rerandomize_proof(&params.vk, &rerandomized, rng);
+            assert!(verify_proof(&pvk, &again, &[c]).unwrap());
+            assert!(again != rerandomized);
+
+            // An invalid proof stays invalid
+            let invalid = Proof {
+                a: proof.a,
+                b: proof.b,
+                c: rerandomized.c
+            };
+            assert!(!verify_proof(&pvk, &invalid, &[c]).unwrap());
+            assert!(!verify_proof(&pvk, &rerandomize_proof(&params.vk, &invalid, rng), &[c]).unwrap());
+        }
+    }
+
+    #[test]
+    fn precomputed_parameters() {
+        let rng = &mut thread_rng();
+
+        let params = generate_random_parameters::<Bls12, _, _>(
+            MySillyCircuit { a: None, b: None },
+            rng
+        ).unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12>(&params.vk);
+
+        // Everything, nothing, and only some of the queries fit
+        let all = params.precompute(1 << 30).unwrap();
+        assert!(all.h.is_precomputed() && all.l.is_precomputed() && all.a.is_precomputed());
+        assert!(all.b_g1.is_precomputed() && all.b_g2.is_precomputed());
+
+        let none = params.precompute(0).unwrap();
+        assert!(!none.h.is_precomputed() && !none.b_g2.is_precomputed());
+
+        let h_size = {
+            let c = FixedBaseTable::<<Bls12 as Engine>::G1Affine>::recommended_window(params.h.len(), 1 << 30).unwrap();
+            FixedBaseTable::new(&Worker::new(), &params.h, c).unwrap().num_points() * mem::size_of::<<Bls12 as Engine>::G1Affine>()
+        };
+        let some = params.precompute(h_size).unwrap();
+        assert!(some.h.is_precomputed() && !some.b_g2.is_precomputed());
+
+        for _ in 0..5 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            let r = Fr::rand(rng);
+            let s = Fr::rand(rng);
+
+            let expected = create_proof(MySillyCircuit { a: Some(a), b: Some(b) }, &params, r, s).unwrap();
+            assert!(verify_proof(&pvk, &expected, &[c]).unwrap());
+
+            for precomputed in [&all, &none, &some].iter() {
+                let proof = create_proof(MySillyCircuit { a: Some(a), b: Some(b) }, *precomputed, r, s).unwrap();
+                assert!(proof == expected);
+            }
+        }
+    }
 }
@@ -17,7 +17,9 @@ use ff::{
 
 use super::{
     ParameterSource,
-    Proof
+    Proof,
+    VerifyingKey,
+    ConstraintMatrices
 };
 
 use ::{
@@ -206,7 +208,7 @@ pub fn create_random_proof<E, C, R, P: ParameterSource<E>>(
 
 pub fn create_proof<E, C, P: ParameterSource<E>>(
     circuit: C,
-    mut params: P,
+    params: P,
     r: E::Fr,
     s: E::Fr
 ) -> Result<Proof<E>, SynthesisError>
@@ -235,14 +237,128 @@ pub fn create_proof<E, C, P: ParameterSource<E>>(
         );
     }
 
+    prove(prover, params, r, s)
+}
+
+pub fn create_random_proof_with_assignment<E, R, P: ParameterSource<E>>(
+    matrices: &ConstraintMatrices<E>,
+    input_assignment: Vec<E::Fr>,
+    aux_assignment: Vec<E::Fr>,
+    params: P,
+    rng: &mut R
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine, R: Rng
+{
+    let r = rng.gen();
+    let s = rng.gen();
+
+    create_proof_with_assignment::<E, P>(matrices, input_assignment, aux_assignment, params, r, s)
+}
+
+/// Creates a proof from the constraint matrices of the circuit and a
+/// precomputed assignment, e.g. by `WitnessCalculator`, so that the
+/// circuit does not have to be synthesized again. The input assignment
+/// starts with the "one" input. The assignment is the one of the original
+/// circuit even if the matrices were optimized.
+pub fn create_proof_with_assignment<E, P: ParameterSource<E>>(
+    matrices: &ConstraintMatrices<E>,
+    input_assignment: Vec<E::Fr>,
+    aux_assignment: Vec<E::Fr>,
+    params: P,
+    r: E::Fr,
+    s: E::Fr
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine
+{
+    if input_assignment.len() != matrices.num_inputs || aux_assignment.len() != matrices.num_witness_aux {
+        return Err(SynthesisError::AssignmentMissing);
+    }
+
+    let aux_assignment = match matrices.witness_aux {
+        Some(ref indices) => indices.iter().map(|&i| aux_assignment[i]).collect(),
+        None => aux_assignment
+    };
+
+    let mut prover = ProvingAssignment {
+        a_aux_density: matrices.a_aux_density.clone(),
+        b_input_density: matrices.b_input_density.clone(),
+        b_aux_density: matrices.b_aux_density.clone(),
+        a: Vec::with_capacity(matrices.num_constraints()),
+        b: Vec::with_capacity(matrices.num_constraints()),
+        c: Vec::with_capacity(matrices.num_constraints()),
+        input_assignment: input_assignment,
+        aux_assignment: aux_assignment
+    };
+
+    // The densities are already known,
+    // so they are not tracked again.
+    for ((a, b), c) in matrices.a.iter().zip(matrices.b.iter()).zip(matrices.c.iter()) {
+        prover.a.push(Scalar(eval(a, None, None, &prover.input_assignment, &prover.aux_assignment)));
+        prover.b.push(Scalar(eval(b, None, None, &prover.input_assignment, &prover.aux_assignment)));
+        prover.c.push(Scalar(eval(c, None, None, &prover.input_assignment, &prover.aux_assignment)));
+    }
+
+    prove(prover, params, r, s)
+}
+
+/// Creates another proof of the same statement that can not be linked
+/// to the given one, without knowing the witness. The result is valid
+/// under the verifying key if and only if the original proof is.
+pub fn rerandomize_proof<E, R>(
+    vk: &VerifyingKey<E>,
+    proof: &Proof<E>,
+    rng: &mut R
+) -> Proof<E>
+    where E: Engine, R: Rng
+{
+    let (r1, r1_inverse) = loop {
+        let r1: E::Fr = rng.gen();
+
+        if let Some(inverse) = r1.inverse() {
+            break (r1, inverse);
+        }
+    };
+    let r2: E::Fr = rng.gen();
+
+    // A' = A / r1
+    let a = proof.a.mul(r1_inverse);
+
+    // B' = r1 * B + r1 * r2 * delta
+    let mut b = proof.b.mul(r1);
+    let mut r1_r2 = r1;
+    r1_r2.mul_assign(&r2);
+    b.add_assign(&vk.delta_g2.mul(r1_r2));
+
+    // C' = C + r2 * A, so that A' * B' - C' * delta = A * B - C * delta
+    let mut c = proof.a.mul(r2);
+    c.add_assign_mixed(&proof.c);
+
+    Proof {
+        a: a.into_affine(),
+        b: b.into_affine(),
+        c: c.into_affine()
+    }
+}
+
+fn prove<E, P: ParameterSource<E>>(
+    prover: ProvingAssignment<E>,
+    mut params: P,
+    r: E::Fr,
+    s: E::Fr
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine
+{
     let worker = Worker::new();
 
     let vk = params.get_vk(prover.input_assignment.len())?;
 
     let h = {
-        let mut a = EvaluationDomain::from_coeffs(prover.a)?;
-        let mut b = EvaluationDomain::from_coeffs(prover.b)?;
-        let mut c = EvaluationDomain::from_coeffs(prover.c)?;
+        // The three polynomials share the powers of the domain
+        let domain = params.get_domain(prover.a.len())?;
+
+        let mut a = EvaluationDomain::from_coeffs_in(prover.a, &domain)?;
+        let mut b = EvaluationDomain::from_coeffs_in(prover.b, &domain)?;
+        let mut c = EvaluationDomain::from_coeffs_in(prover.c, &domain)?;
         a.ifft(&worker);
         a.coset_fft(&worker);
         b.ifft(&worker);
@@ -1,19 +1,24 @@
-use pairing::{
-    Engine,
+use ff::{
     PrimeField,
     PrimeFieldRepr,
     Field,
     SqrtField,
     LegendreSymbol,
+    PrimeFieldDecodingError,
+    ScalarEngine
+};
+use pairing::{
+    Engine,
     CurveProjective,
     CurveAffine,
-    PrimeFieldDecodingError,
     GroupDecodingError,
-    EncodedPoint
+    EncodedPoint,
+    ExtensionField
 };
 
 use std::cmp::Ordering;
 use std::fmt;
+use std::io::{self, Write};
 use rand::{Rand, Rng};
 use std::num::Wrapping;
 
@@ -34,6 +39,12 @@ impl Rand for Fr {
     }
 }
 
+impl ExtensionField for Fr {
+    fn write_be<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.into_repr().write_be(writer)
+    }
+}
+
 impl Field for Fr {
     fn zero() -> Self {
         Fr(Wrapping(0))
@@ -263,8 +274,11 @@ impl PrimeField for Fr {
 #[derive(Clone)]
 pub struct DummyEngine;
 
-impl Engine for DummyEngine {
+impl ScalarEngine for DummyEngine {
     type Fr = Fr;
+}
+
+impl Engine for DummyEngine {
     type G1 = Fr;
     type G1Affine = Fr;
     type G2 = Fr;
@@ -437,6 +451,11 @@ impl CurveAffine for Fr {
         res
     }
 
+    fn mul_secret(&self, scalar: &Fr) -> Self::Projective
+    {
+        self.mul(scalar.into_repr())
+    }
+
     fn prepare(&self) -> Self::Prepared {
         *self
     }
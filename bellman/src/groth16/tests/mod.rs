@@ -1,8 +1,8 @@
-use pairing::{
-    Engine,
+use ff::{
     Field,
     PrimeField
 };
+use pairing::Engine;
 
 mod dummy_engine;
 use self::dummy_engine::*;
@@ -12,14 +12,22 @@ use std::marker::PhantomData;
 use ::{
     Circuit,
     ConstraintSystem,
-    SynthesisError
+    Index,
+    LinearCombination,
+    SynthesisError,
+    Variable
 };
 
 use super::{
     generate_parameters,
     prepare_verifying_key,
     create_proof,
-    verify_proof
+    create_proof_with_assignment,
+    verify_proof,
+    ConstraintMatrices,
+    OptimizedCircuit,
+    Parameters,
+    WitnessCalculator
 };
 
 struct XORDemo<E: Engine> {
@@ -135,29 +143,34 @@ fn test_xordemo() {
     //     (a_0) * 0 = 0
     //     (a_1) * 0 = 0
 
-    // The evaluation domain is 8. The H query should
-    // have 7 elements (it's a quotient polynomial)
-    assert_eq!(7, params.h.len());
+    // The evaluation domain is the smallest mixed-radix one, 6 = 2 * 3.
+    // The H query should have 5 elements (it's a quotient polynomial)
+    assert_eq!(5, params.h.len());
 
     let mut root_of_unity = Fr::root_of_unity();
 
     // We expect this to be a 2^10 root of unity
     assert_eq!(Fr::one(), root_of_unity.pow(&[1 << 10]));
 
-    // Let's turn it into a 2^3 root of unity.
-    root_of_unity = root_of_unity.pow(&[1 << 7]);
-    assert_eq!(Fr::one(), root_of_unity.pow(&[1 << 3]));
-    assert_eq!(Fr::from_str("20201").unwrap(), root_of_unity);
+    // Let's turn it into a 2 * 3 root of unity with a cube root of unity.
+    root_of_unity = root_of_unity.pow(&[1 << 9]);
+    let cube_root_of_unity = Fr::multiplicative_generator().pow(&[64512 / 3]);
+    assert_eq!(Fr::from_str("47198").unwrap(), cube_root_of_unity);
+    root_of_unity.mul_assign(&cube_root_of_unity);
+    assert_eq!(Fr::one(), root_of_unity.pow(&[6]));
+    assert!(root_of_unity.pow(&[2]) != Fr::one());
+    assert!(root_of_unity.pow(&[3]) != Fr::one());
+    assert_eq!(Fr::from_str("17315").unwrap(), root_of_unity);
 
     // Let's compute all the points in our evaluation domain.
-    let mut points = Vec::with_capacity(8);
-    for i in 0..8 {
+    let mut points = Vec::with_capacity(6);
+    for i in 0..6 {
         points.push(root_of_unity.pow(&[i]));
     }
 
     // Let's compute t(tau) = (tau - p_0)(tau - p_1)...
-    //                      = tau^8 - 1
-    let mut t_at_tau = tau.pow(&[8]);
+    //                      = tau^6 - 1
+    let mut t_at_tau = tau.pow(&[6]);
     t_at_tau.sub_assign(&Fr::one());
     {
         let mut tmp = Fr::one();
@@ -169,7 +182,7 @@ fn test_xordemo() {
         assert_eq!(tmp, t_at_tau);
     }
 
-    // We expect our H query to be 7 elements of the form...
+    // We expect our H query to be 5 elements of the form...
     // {tau^i t(tau) / delta}
     let delta_inverse = delta.inverse().unwrap();
     let gamma_inverse = gamma.inverse().unwrap();
@@ -219,23 +232,23 @@ fn test_xordemo() {
 
     sage: r = 64513
     sage: Fr = GF(r)
-    sage: omega = (Fr(5)^63)^(2^7)
+    sage: omega = (Fr(5)^63)^(2^9) * Fr(5)^(64512 / 3)
     sage: tau = Fr(3673)
     sage: R.<x> = PolynomialRing(Fr, 'x')
     sage: def eval(tau, c0, c1, c2, c3, c4):
-    ....:     p = R.lagrange_polynomial([(omega^0, c0), (omega^1, c1), (omega^2, c2), (omega^3, c3), (omega^4, c4), (omega^5, 0), (omega^6, 0), (omega^7, 0)])
+    ....:     p = R.lagrange_polynomial([(omega^0, c0), (omega^1, c1), (omega^2, c2), (omega^3, c3), (omega^4, c4), (omega^5, 0)])
     ....:     return p.substitute(tau)
     sage: eval(tau, 1, 1, 0, 1, 0)
-    59158
+    22431
     */
 
-    let u_i = [59158, 48317, 21767, 10402].iter().map(|e| {
+    let u_i = [22431, 12140, 7687, 42639].iter().map(|e| {
         Fr::from_str(&format!("{}", e)).unwrap()
     }).collect::<Vec<Fr>>();
-    let v_i = [0, 0, 60619, 30791].iter().map(|e| {
+    let v_i = [0, 0, 55514, 21218].iter().map(|e| {
         Fr::from_str(&format!("{}", e)).unwrap()
     }).collect::<Vec<Fr>>();
-    let w_i = [0, 23320, 41193, 41193].iter().map(|e| {
+    let w_i = [0, 656, 63857, 63857].iter().map(|e| {
         Fr::from_str(&format!("{}", e)).unwrap()
     }).collect::<Vec<Fr>>();
 
@@ -311,10 +324,10 @@ fn test_xordemo() {
     };
 
     // A(x) =
-    //  a_0 * (44865*x^7 + 56449*x^6 + 44865*x^5 + 8064*x^4 + 3520*x^3 + 56449*x^2 + 3520*x + 40321) +
-    //  a_1 * (8064*x^7 + 56449*x^6 + 8064*x^5 + 56449*x^4 + 8064*x^3 + 56449*x^2 + 8064*x + 56449) +
-    //  a_2 * (16983*x^7 + 24192*x^6 + 63658*x^5 + 56449*x^4 + 16983*x^3 + 24192*x^2 + 63658*x + 56449) +
-    //  a_3 * (5539*x^7 + 27797*x^6 + 6045*x^5 + 56449*x^4 + 58974*x^3 + 36716*x^2 + 58468*x + 8064) +
+    //  a_0 * (13638*x^5 + 2886*x^4 + 10752*x^3 + 29371*x^2 + 40123*x + 32257) +
+    //  a_1 * (50875*x^5 + 24390*x^4 + 53761*x^3 + 50875*x^2 + 24390*x + 53761) +
+    //  a_2 * (59532*x^5 + 47989*x^4 + 53761*x^3 + 59532*x^2 + 47989*x + 53761) +
+    //  a_3 * (50875*x^5 + 40123*x^4 + 53761*x^3 + 13638*x^2 + 24390*x + 10752) +
     {
         // proof A = alpha + A(tau) + delta * r
         let mut expected_a = delta;
@@ -330,8 +343,8 @@ fn test_xordemo() {
     // B(x) =
     // a_0 * (0) +
     // a_1 * (0) +
-    // a_2 * (56449*x^7 + 56449*x^6 + 56449*x^5 + 56449*x^4 + 56449*x^3 + 56449*x^2 + 56449*x + 56449) +
-    // a_3 * (31177*x^7 + 44780*x^6 + 21752*x^5 + 42255*x^3 + 35861*x^2 + 33842*x + 48385)
+    // a_2 * (53761*x^5 + 53761*x^4 + 53761*x^3 + 53761*x^2 + 53761*x + 53761) +
+    // a_3 * (38028*x^5 + 10752*x^4 + 10752*x^2 + 26485*x + 43009)
     {
         // proof B = beta + B(tau) + delta * s
         let mut expected_b = delta;
@@ -346,17 +359,17 @@ fn test_xordemo() {
 
     // C(x) =
     // a_0 * (0) +
-    // a_1 * (27797*x^7 + 56449*x^6 + 36716*x^5 + 8064*x^4 + 27797*x^3 + 56449*x^2 + 36716*x + 8064) +
-    // a_2 * (36716*x^7 + 8064*x^6 + 27797*x^5 + 56449*x^4 + 36716*x^3 + 8064*x^2 + 27797*x + 56449) +
-    // a_3 * (36716*x^7 + 8064*x^6 + 27797*x^5 + 56449*x^4 + 36716*x^3 + 8064*x^2 + 27797*x + 56449)
+    // a_1 * (40123*x^5 + 13638*x^4 + 10752*x^3 + 40123*x^2 + 13638*x + 10752) +
+    // a_2 * (24390*x^5 + 50875*x^4 + 53761*x^3 + 24390*x^2 + 50875*x + 53761) +
+    // a_3 * (24390*x^5 + 50875*x^4 + 53761*x^3 + 24390*x^2 + 50875*x + 53761)
     //
     // If A * B = C at each point in the domain, then the following polynomial...
     // P(x) = A(x) * B(x) - C(x)
-    //      = 49752*x^14 + 13914*x^13 + 29243*x^12 + 27227*x^11 + 62362*x^10 + 35703*x^9 + 4032*x^8 + 14761*x^6 + 50599*x^5 + 35270*x^4 + 37286*x^3 + 2151*x^2 + 28810*x + 60481
+    //      = 9922*x^10 + 11714*x^9 + 9922*x^8 + 11714*x^7 + 8960*x^6 + 54591*x^4 + 52799*x^3 + 54591*x^2 + 52799*x + 55553
     //
     // ... should be divisible by t(x), producing the quotient polynomial:
     // h(x) = P(x) / t(x)
-    //      = 49752*x^6 + 13914*x^5 + 29243*x^4 + 27227*x^3 + 62362*x^2 + 35703*x + 4032
+    //      = 9922*x^4 + 11714*x^3 + 9922*x^2 + 11714*x + 8960
     {
         let mut expected_c = Fr::zero();
 
@@ -381,7 +394,7 @@ fn test_xordemo() {
         expected_c.add_assign(&params.l[0]);
 
         // H query answer
-        for (i, coeff) in [5040, 11763, 10755, 63633, 128, 9747, 8739].iter().enumerate() {
+        for (i, coeff) in [8960, 11714, 9922, 11714, 9922].iter().enumerate() {
             let coeff = Fr::from_str(&format!("{}", coeff)).unwrap();
 
             let mut tmp = params.h[i];
@@ -398,3 +411,222 @@ fn test_xordemo() {
         &[Fr::one()]
     ).unwrap());
 }
+
+#[test]
+fn test_xordemo_with_assignment() {
+    let params = {
+        let c = XORDemo::<DummyEngine> {
+            a: None,
+            b: None,
+            _marker: PhantomData
+        };
+
+        generate_parameters(
+            c,
+            Fr::one(),
+            Fr::one(),
+            Fr::from_str("48577").unwrap(),
+            Fr::from_str("22580").unwrap(),
+            Fr::from_str("53332").unwrap(),
+            Fr::from_str("5481").unwrap(),
+            Fr::from_str("3673").unwrap()
+        ).unwrap()
+    };
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // The structure is synthesized only once
+    let matrices = ConstraintMatrices::synthesize(XORDemo::<DummyEngine> {
+        a: None,
+        b: None,
+        _marker: PhantomData
+    }).unwrap();
+
+    assert_eq!(matrices.num_inputs, 2);
+    assert_eq!(matrices.num_aux, 2);
+    assert_eq!(matrices.num_constraints(), 5);
+
+    // The matrices can be stored alongside the parameters
+    let matrices = {
+        let mut v = vec![];
+        matrices.write(&mut v).unwrap();
+
+        let de_matrices = ConstraintMatrices::<DummyEngine>::read(&v[..]).unwrap();
+        assert_eq!(de_matrices.num_inputs, matrices.num_inputs);
+        assert_eq!(de_matrices.num_aux, matrices.num_aux);
+        assert_eq!(de_matrices.num_constraints(), matrices.num_constraints());
+
+        assert!(ConstraintMatrices::<DummyEngine>::read(&v[..v.len() - 1]).is_err());
+
+        de_matrices
+    };
+
+    let r = Fr::from_str("27134").unwrap();
+    let s = Fr::from_str("17146").unwrap();
+
+    for &(a, b) in &[(true, false), (true, true), (false, false)] {
+        let witness = WitnessCalculator::calculate(XORDemo::<DummyEngine> {
+            a: Some(a),
+            b: Some(b),
+            _marker: PhantomData
+        }).unwrap();
+
+        let expected = create_proof(
+            XORDemo {
+                a: Some(a),
+                b: Some(b),
+                _marker: PhantomData
+            },
+            &params,
+            r,
+            s
+        ).unwrap();
+
+        let input = witness.input_assignment[1];
+
+        let proof = create_proof_with_assignment(
+            &matrices,
+            witness.input_assignment,
+            witness.aux_assignment,
+            &params,
+            r,
+            s
+        ).unwrap();
+
+        assert!(proof == expected);
+        assert!(verify_proof(&pvk, &proof, &[input]).unwrap());
+    }
+
+    // The assignment has to match the matrices
+    assert!(create_proof_with_assignment(
+        &matrices,
+        vec![Fr::one()],
+        vec![Fr::one(), Fr::zero()],
+        &params,
+        r,
+        s
+    ).is_err());
+}
+
+// Proves knowledge of x such that (3 * (x^2 + x) + 5) * x = out
+#[derive(Clone)]
+struct MyLinearCircuit<E: Engine> {
+    x: Option<E::Fr>
+}
+
+impl<E: Engine> Circuit<E> for MyLinearCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        let x_value = self.x;
+        let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+
+        let y_value = x_value.map(|mut x| { let tmp = x; x.mul_assign(&tmp); x });
+        let y = cs.alloc(|| "y", || y_value.ok_or(SynthesisError::AssignmentMissing))?;
+        cs.enforce(|| "x*x=y", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+
+        // Linear constraints that the optimizer eliminates
+        let z_value = y_value.and_then(|mut y| x_value.map(|x| { y.add_assign(&x); y }));
+        let z = cs.alloc(|| "z", || z_value.ok_or(SynthesisError::AssignmentMissing))?;
+        cs.enforce(|| "(y+x)*1=z", |lc| lc + y + x + x - x, |lc| lc + CS::one(), |lc| lc + z);
+
+        let three = E::Fr::from_str("3").unwrap();
+        let five = E::Fr::from_str("5").unwrap();
+        let w_value = z_value.map(|mut z| { z.mul_assign(&three); z.add_assign(&five); z });
+        let w = cs.alloc(|| "w", || w_value.ok_or(SynthesisError::AssignmentMissing))?;
+        cs.enforce(|| "1*(3z+5)=w", |lc| lc + CS::one(), |lc| lc + (three, z) + (five, CS::one()), |lc| lc + w);
+
+        let out = cs.alloc_input(|| "out", || {
+            let mut w = w_value.ok_or(SynthesisError::AssignmentMissing)?;
+            w.mul_assign(&x_value.ok_or(SynthesisError::AssignmentMissing)?);
+            Ok(w)
+        })?;
+        cs.enforce(|| "w*x=out", |lc| lc + w, |lc| lc + x, |lc| lc + out);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_optimized_circuit() {
+    use pairing::CurveProjective;
+    use pairing::bls12_381::{Bls12, Fr, G1, G2};
+    use rand::{Rand, thread_rng};
+
+    let rng = &mut thread_rng();
+
+    let mut matrices = ConstraintMatrices::<Bls12>::synthesize(MyLinearCircuit { x: None }).unwrap();
+    assert_eq!(matrices.num_constraints(), 6);
+    assert_eq!(matrices.num_aux, 4);
+
+    // Duplicate terms are merged
+    assert_eq!(matrices.a[1].as_ref().len(), 2);
+
+    matrices.optimize();
+    assert_eq!(matrices.num_constraints(), 4);
+    assert_eq!(matrices.num_aux, 2);
+
+    let matrices = {
+        let mut v = vec![];
+        matrices.write(&mut v).unwrap();
+        ConstraintMatrices::<Bls12>::read(&v[..]).unwrap()
+    };
+
+    // A zero coefficient of the aux variable of `(y+x)*1=z` survives writing
+    // and reading, and leaves the constraint in place
+    let mut zero_coeff = ConstraintMatrices::<Bls12>::synthesize(MyLinearCircuit { x: None }).unwrap();
+    zero_coeff.c[1] = LinearCombination::zero() + (Fr::zero(), Variable::new_unchecked(Index::Aux(2)));
+    let mut zero_coeff = {
+        let mut v = vec![];
+        zero_coeff.write(&mut v).unwrap();
+        ConstraintMatrices::<Bls12>::read(&v[..]).unwrap()
+    };
+    zero_coeff.optimize();
+    assert_eq!(zero_coeff.num_constraints(), 5);
+    assert_eq!(zero_coeff.num_aux, 3);
+
+    fn generate<C: Circuit<Bls12>>(circuit: C) -> Parameters<Bls12> {
+        generate_parameters(
+            circuit,
+            G1::one(),
+            G2::one(),
+            Fr::from_str("48577").unwrap(),
+            Fr::from_str("22580").unwrap(),
+            Fr::from_str("53332").unwrap(),
+            Fr::from_str("5481").unwrap(),
+            Fr::from_str("3673").unwrap()
+        ).unwrap()
+    }
+
+    // The optimized circuit has the parameters of the optimized matrices
+    let params = generate(OptimizedCircuit(MyLinearCircuit { x: None }));
+    assert!(params == generate(&matrices));
+
+    let pvk = prepare_verifying_key::<Bls12>(&params.vk);
+
+    for _ in 0..10 {
+        let x = Fr::rand(rng);
+        let r = Fr::rand(rng);
+        let s = Fr::rand(rng);
+
+        let proof = create_proof(OptimizedCircuit(MyLinearCircuit { x: Some(x) }), &params, r, s).unwrap();
+
+        // Proving from the assignment of the original circuit agrees
+        let witness = WitnessCalculator::<Bls12>::calculate(MyLinearCircuit { x: Some(x) }).unwrap();
+        let out = witness.input_assignment[1];
+
+        assert!(proof == create_proof_with_assignment(
+            &matrices,
+            witness.input_assignment,
+            witness.aux_assignment,
+            &params,
+            r,
+            s
+        ).unwrap());
+
+        assert!(verify_proof(&pvk, &proof, &[out]).unwrap());
+        assert!(!verify_proof(&pvk, &proof, &[Fr::one()]).unwrap());
+    }
+}
@@ -0,0 +1,660 @@
+//! KZG commitments to polynomials over the scalar field.
+//!
+//! A structured reference string of the powers of a secret `x` in G1
+//! and G2 allows to commit to a polynomial `p` as `[p(x)]_1`, and to
+//! prove its value at a point with a single group element, which is
+//! the commitment to the quotient `(p(X) - p(z)) / (X - z)`. Openings
+//! at several points are proven with a single group element too, by
+//! dividing by the polynomial vanishing at all of them instead.
+//!
+//! Polynomials are `Vec<E::Fr>` in coefficient form, starting from
+//! the constant coefficient. Vectors can be committed to by
+//! interpolating them over the powers of a root of unity first.
+
+use rand::Rng;
+
+use pairing::{
+    Engine,
+    Wnaf,
+    CurveProjective,
+    CurveAffine,
+    EncodedPoint
+};
+
+use ff::{
+    Field,
+    PrimeField,
+    PrimeFieldRepr
+};
+
+use futures::Future;
+
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use byteorder::{BigEndian, WriteBytesExt, ReadBytesExt};
+
+use super::{
+    SynthesisError
+};
+
+use super::domain::{
+    EvaluationDomain,
+    Scalar
+};
+
+use super::multiexp::{
+    FullDensity,
+    multiexp
+};
+
+use super::multicore::Worker;
+
+/// Structured reference string of the powers of a secret `x`, taken
+/// of the standard generators. The powers in G1 bound the degree of
+/// the polynomials, and the powers in G2 the number of points of an
+/// opening.
+#[derive(Clone)]
+pub struct Srs<E: Engine> {
+    // x^i in G1, starting from the generator
+    pub powers_g1: Arc<Vec<E::G1Affine>>,
+
+    // x^i in G2, starting from the generator
+    pub powers_g2: Arc<Vec<E::G2Affine>>
+}
+
+impl<E: Engine> PartialEq for Srs<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.powers_g1 == other.powers_g1 &&
+        self.powers_g2 == other.powers_g2
+    }
+}
+
+impl<E: Engine> Srs<E> {
+    /// Returns the reference string with only the first powers,
+    /// which is enough for smaller polynomials and openings
+    pub fn trim(&self, num_powers_g1: usize, num_powers_g2: usize) -> Result<Self, SynthesisError> {
+        if num_powers_g1 > self.powers_g1.len() || num_powers_g2 > self.powers_g2.len() {
+            return Err(SynthesisError::PolynomialDegreeTooLarge);
+        }
+
+        Ok(Srs {
+            powers_g1: Arc::new(self.powers_g1[0..num_powers_g1].to_vec()),
+            powers_g2: Arc::new(self.powers_g2[0..num_powers_g2].to_vec())
+        })
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        writer.write_u32::<BigEndian>(self.powers_g1.len() as u32)?;
+        for g in self.powers_g1.iter() {
+            writer.write_all(g.into_uncompressed().as_ref())?;
+        }
+        writer.write_u32::<BigEndian>(self.powers_g2.len() as u32)?;
+        for g in self.powers_g2.iter() {
+            writer.write_all(g.into_uncompressed().as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the reference string, checking that the points are
+    /// in the prime order subgroup only if `checked` is set
+    pub fn read<R: Read>(
+        mut reader: R,
+        checked: bool
+    ) -> io::Result<Self>
+    {
+        fn read_powers<G: CurveAffine, R: Read>(reader: &mut R, checked: bool) -> io::Result<Vec<G>> {
+            let len = reader.read_u32::<BigEndian>()? as usize;
+
+            let mut powers = vec![];
+            for _ in 0..len {
+                let mut repr = G::Uncompressed::empty();
+                reader.read_exact(repr.as_mut())?;
+
+                let p = if checked {
+                    repr.into_affine()
+                } else {
+                    repr.into_affine_unchecked()
+                }
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                if p.is_zero() {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"));
+                }
+
+                powers.push(p);
+            }
+
+            if powers.first() != Some(&G::one()) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "powers do not start with the generator"));
+            }
+
+            Ok(powers)
+        }
+
+        let powers_g1 = read_powers(&mut reader, checked)?;
+        let powers_g2 = read_powers(&mut reader, checked)?;
+
+        if powers_g2.len() < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too few powers in G2 to verify openings"));
+        }
+
+        Ok(Srs {
+            powers_g1: Arc::new(powers_g1),
+            powers_g2: Arc::new(powers_g2)
+        })
+    }
+}
+
+/// Generates a random reference string with the given numbers of
+/// powers. This is for testing only, as the knowledge of the secret
+/// allows to forge openings: a real one comes from a multi-party
+/// ceremony.
+pub fn generate_random_srs<E, R>(
+    num_powers_g1: usize,
+    num_powers_g2: usize,
+    rng: &mut R
+) -> Srs<E>
+    where E: Engine, R: Rng
+{
+    assert!(num_powers_g1 >= 1 && num_powers_g2 >= 2);
+
+    let x: E::Fr = rng.gen();
+
+    let worker = Worker::new();
+
+    fn powers<G: CurveProjective>(x: G::Scalar, num_powers: usize, worker: &Worker) -> Vec<G::Affine> {
+        let mut wnaf = Wnaf::new();
+        let wnaf = wnaf.base(G::one(), num_powers);
+
+        let mut powers = vec![G::zero(); num_powers];
+
+        worker.scope(powers.len(), |scope, chunk| {
+            for (i, powers) in powers.chunks_mut(chunk).enumerate()
+            {
+                let mut wnaf = wnaf.shared();
+
+                scope.spawn(move || {
+                    let mut current_x_power = x.pow(&[(i*chunk) as u64]);
+
+                    for p in powers.iter_mut() {
+                        *p = wnaf.scalar(current_x_power.into_repr());
+                        current_x_power.mul_assign(&x);
+                    }
+
+                    // Batch normalize
+                    G::batch_normalization(powers);
+                });
+            }
+        });
+
+        powers.into_iter().map(|p| p.into_affine()).collect()
+    }
+
+    Srs {
+        powers_g1: Arc::new(powers::<E::G1>(x, num_powers_g1, &worker)),
+        powers_g2: Arc::new(powers::<E::G2>(x, num_powers_g2, &worker))
+    }
+}
+
+/// Opening of a committed polynomial at a point
+#[derive(Clone)]
+pub struct Opening<E: Engine> {
+    pub value: E::Fr,
+    pub witness: E::G1Affine
+}
+
+impl<E: Engine> PartialEq for Opening<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value &&
+        self.witness == other.witness
+    }
+}
+
+impl<E: Engine> Opening<E> {
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        self.value.into_repr().write_be(&mut writer)?;
+        writer.write_all(self.witness.into_compressed().as_ref())?;
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(
+        mut reader: R
+    ) -> io::Result<Self>
+    {
+        let value = read_scalar::<E, _>(&mut reader)?;
+        let witness = read_witness::<E, _>(&mut reader)?;
+
+        Ok(Opening {
+            value: value,
+            witness: witness
+        })
+    }
+}
+
+/// Opening of a committed polynomial at several points at once
+#[derive(Clone)]
+pub struct BatchOpening<E: Engine> {
+    pub values: Vec<E::Fr>,
+    pub witness: E::G1Affine
+}
+
+impl<E: Engine> PartialEq for BatchOpening<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values &&
+        self.witness == other.witness
+    }
+}
+
+impl<E: Engine> BatchOpening<E> {
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        writer.write_u32::<BigEndian>(self.values.len() as u32)?;
+        for value in self.values.iter() {
+            value.into_repr().write_be(&mut writer)?;
+        }
+        writer.write_all(self.witness.into_compressed().as_ref())?;
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(
+        mut reader: R
+    ) -> io::Result<Self>
+    {
+        let len = reader.read_u32::<BigEndian>()? as usize;
+
+        let mut values = vec![];
+        for _ in 0..len {
+            values.push(read_scalar::<E, _>(&mut reader)?);
+        }
+
+        let witness = read_witness::<E, _>(&mut reader)?;
+
+        Ok(BatchOpening {
+            values: values,
+            witness: witness
+        })
+    }
+}
+
+fn read_scalar<E: Engine, R: Read>(reader: &mut R) -> io::Result<E::Fr> {
+    let mut repr = <E::Fr as PrimeField>::Repr::default();
+    repr.read_be(reader)?;
+
+    E::Fr::from_repr(repr).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_witness<E: Engine, R: Read>(reader: &mut R) -> io::Result<E::G1Affine> {
+    let mut repr = <E::G1Affine as CurveAffine>::Compressed::empty();
+    reader.read_exact(repr.as_mut())?;
+
+    // The witness of the opening of a polynomial of
+    // degree at most one is the point at infinity
+    repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn evaluate<F: Field>(coeffs: &[F], x: &F) -> F {
+    let mut result = F::zero();
+
+    for c in coeffs.iter().rev() {
+        result.mul_assign(x);
+        result.add_assign(c);
+    }
+
+    result
+}
+
+/// Divides the polynomial by `X - z`. The remainder, which
+/// is the value at `z`, is dropped.
+pub fn divide_by_linear<F: Field>(coeffs: &[F], z: &F) -> Vec<F> {
+    let mut quotient = vec![F::zero(); coeffs.len().saturating_sub(1)];
+    let mut acc = F::zero();
+
+    for i in (1..coeffs.len()).rev() {
+        acc.mul_assign(z);
+        acc.add_assign(&coeffs[i]);
+        quotient[i - 1] = acc;
+    }
+
+    quotient
+}
+
+/// Returns the coefficients of the polynomial vanishing at the points
+fn vanishing_polynomial<F: Field>(points: &[F]) -> Vec<F> {
+    let mut coeffs = vec![F::one()];
+
+    for z in points {
+        // Multiply by X - z
+        coeffs.insert(0, F::zero());
+        for i in 0..(coeffs.len() - 1) {
+            let mut tmp = coeffs[i + 1];
+            tmp.mul_assign(z);
+            coeffs[i].sub_assign(&tmp);
+        }
+    }
+
+    coeffs
+}
+
+/// Returns the coefficients of the polynomial of the lowest
+/// degree taking the values at the points, which must be distinct
+fn lagrange_interpolation<F: Field>(points: &[F], values: &[F]) -> Option<Vec<F>> {
+    let vanishing = vanishing_polynomial(points);
+    let mut coeffs = vec![F::zero(); points.len()];
+
+    for (z, value) in points.iter().zip(values.iter()) {
+        // The polynomial vanishing at the other points,
+        // scaled to take the value at z
+        let basis = divide_by_linear(&vanishing, z);
+        let mut scale = match evaluate(&basis, z).inverse() {
+            Some(inverse) => inverse,
+            None => return None
+        };
+        scale.mul_assign(value);
+
+        for (c, b) in coeffs.iter_mut().zip(basis.iter()) {
+            let mut tmp = *b;
+            tmp.mul_assign(&scale);
+            c.add_assign(&tmp);
+        }
+    }
+
+    Some(coeffs)
+}
+
+/// Interpolates the values at the powers of the root of unity of the
+/// domain of their size, so that the `i`-th element of a vector is
+/// the value of the polynomial at `omega^i`
+pub fn interpolate<E: Engine>(values: Vec<E::Fr>, worker: &Worker) -> Result<Vec<E::Fr>, SynthesisError> {
+    let mut domain = EvaluationDomain::<E, _>::from_coeffs(values.into_iter().map(Scalar).collect())?;
+    domain.ifft(worker);
+
+    Ok(domain.into_coeffs().into_iter().map(|s| s.0).collect())
+}
+
+/// Commits to the polynomial, whose number of coefficients
+/// is at most the number of powers in G1
+pub fn commit<E: Engine>(
+    srs: &Srs<E>,
+    coeffs: &[E::Fr],
+    worker: &Worker
+) -> Result<E::G1Affine, SynthesisError>
+{
+    if coeffs.len() > srs.powers_g1.len() {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    if coeffs.is_empty() {
+        return Ok(E::G1Affine::zero());
+    }
+
+    let exponents = Arc::new(coeffs.iter().map(|c| c.into_repr()).collect::<Vec<_>>());
+
+    Ok(multiexp(worker, (srs.powers_g1.clone(), 0), FullDensity, exponents).wait()?.into_affine())
+}
+
+/// Opens the polynomial at the point
+pub fn open<E: Engine>(
+    srs: &Srs<E>,
+    coeffs: &[E::Fr],
+    point: &E::Fr,
+    worker: &Worker
+) -> Result<Opening<E>, SynthesisError>
+{
+    Ok(Opening {
+        value: evaluate(coeffs, point),
+        witness: commit(srs, &divide_by_linear(coeffs, point), worker)?
+    })
+}
+
+/// Opens the polynomial at the points, which must be distinct and
+/// fewer than the powers in G2, with a single group element
+pub fn open_batch<E: Engine>(
+    srs: &Srs<E>,
+    coeffs: &[E::Fr],
+    points: &[E::Fr],
+    worker: &Worker
+) -> Result<BatchOpening<E>, SynthesisError>
+{
+    if points.len() >= srs.powers_g2.len() {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    for (i, a) in points.iter().enumerate() {
+        if points[(i + 1)..].contains(a) {
+            return Err(SynthesisError::DivisionByZero);
+        }
+    }
+
+    // Dividing by every X - z in turn gives the quotient by the
+    // vanishing polynomial, as the dropped remainders only add up
+    // to a polynomial of lower degree than it
+    let mut quotient = coeffs.to_vec();
+    for z in points {
+        quotient = divide_by_linear(&quotient, z);
+    }
+
+    Ok(BatchOpening {
+        values: points.iter().map(|z| evaluate(coeffs, z)).collect(),
+        witness: commit(srs, &quotient, worker)?
+    })
+}
+
+/// Verifies the opening of the committed polynomial at the point, with
+/// e(C - [v]_1 + z * W, [1]_2) = e(W, [x]_2)
+pub fn verify<E: Engine>(
+    srs: &Srs<E>,
+    commitment: &E::G1Affine,
+    point: &E::Fr,
+    opening: &Opening<E>
+) -> bool
+{
+    let mut lhs = commitment.into_projective();
+    lhs.sub_assign(&E::G1Affine::one().mul(opening.value));
+    lhs.add_assign(&opening.witness.mul(*point));
+
+    let mut witness = opening.witness;
+    witness.negate();
+
+    E::final_exponentiation(
+        &E::miller_loop([
+            (&lhs.into_affine().prepare(), &srs.powers_g2[0].prepare()),
+            (&witness.prepare(), &srs.powers_g2[1].prepare())
+        ].iter())
+    ).unwrap() == E::Fqk::one()
+}
+
+/// Verifies the opening of the committed polynomial at the points,
+/// with e(C - [r(x)]_1, [1]_2) = e(W, [Z(x)]_2), where `r` interpolates
+/// the values and `Z` vanishes at the points
+pub fn verify_batch<E: Engine>(
+    srs: &Srs<E>,
+    commitment: &E::G1Affine,
+    points: &[E::Fr],
+    opening: &BatchOpening<E>
+) -> Result<bool, SynthesisError>
+{
+    if points.len() >= srs.powers_g2.len() || points.len() > srs.powers_g1.len() {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    if points.len() != opening.values.len() {
+        return Ok(false);
+    }
+
+    let remainder = match lagrange_interpolation(points, &opening.values) {
+        Some(remainder) => remainder,
+        None => return Ok(false)
+    };
+
+    let mut lhs = commitment.into_projective();
+    for (c, g) in remainder.iter().zip(srs.powers_g1.iter()) {
+        lhs.sub_assign(&g.mul(*c));
+    }
+
+    let mut vanishing = E::G2::zero();
+    for (c, g) in vanishing_polynomial(points).iter().zip(srs.powers_g2.iter()) {
+        vanishing.add_assign(&g.mul(*c));
+    }
+
+    let mut witness = opening.witness;
+    witness.negate();
+
+    Ok(E::final_exponentiation(
+        &E::miller_loop([
+            (&lhs.into_affine().prepare(), &srs.powers_g2[0].prepare()),
+            (&witness.prepare(), &vanishing.into_affine().prepare())
+        ].iter())
+    ).unwrap() == E::Fqk::one())
+}
+
+#[test]
+fn open_and_verify() {
+    use pairing::bls12_381::Bls12;
+    use rand::{self, Rand};
+
+    fn test_open<E: Engine, R: rand::Rng>(rng: &mut R)
+    {
+        let worker = Worker::new();
+        let srs = generate_random_srs::<E, _>(32, 2, rng);
+
+        for num_coeffs in 0..33 {
+            let coeffs: Vec<_> = (0..num_coeffs).map(|_| E::Fr::rand(rng)).collect();
+            let commitment = commit(&srs, &coeffs, &worker).unwrap();
+
+            let point = E::Fr::rand(rng);
+            let opening = open(&srs, &coeffs, &point, &worker).unwrap();
+
+            assert!(opening.value == evaluate(&coeffs, &point));
+            assert!(verify(&srs, &commitment, &point, &opening));
+
+            let mut wrong_value = opening.clone();
+            wrong_value.value.add_assign(&E::Fr::one());
+            assert!(!verify(&srs, &commitment, &point, &wrong_value));
+
+            // Constant polynomials take the value everywhere
+            if num_coeffs > 1 {
+                let mut wrong_point = point;
+                wrong_point.add_assign(&E::Fr::one());
+                assert!(!verify(&srs, &commitment, &wrong_point, &opening));
+            }
+        }
+
+        let coeffs: Vec<_> = (0..33).map(|_| E::Fr::rand(rng)).collect();
+        assert!(commit(&srs, &coeffs, &worker).is_err());
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_open::<Bls12, _>(rng);
+}
+
+#[test]
+fn batch_open_and_verify() {
+    use pairing::bls12_381::Bls12;
+    use rand::{self, Rand};
+
+    fn test_batch_open<E: Engine, R: rand::Rng>(rng: &mut R)
+    {
+        let worker = Worker::new();
+        let srs = generate_random_srs::<E, _>(16, 5, rng);
+
+        // Commit to a vector, and open some of its elements
+        let vector: Vec<_> = (0..16).map(|_| E::Fr::rand(rng)).collect();
+        let coeffs = interpolate::<E>(vector.clone(), &worker).unwrap();
+        let commitment = commit(&srs, &coeffs, &worker).unwrap();
+
+        let mut omega = E::Fr::root_of_unity();
+        for _ in 4..E::Fr::S {
+            omega.square();
+        }
+
+        let positions = [0, 3, 7, 15];
+        let points: Vec<_> = positions.iter().map(|&i| omega.pow(&[i as u64])).collect();
+
+        let opening = open_batch(&srs, &coeffs, &points, &worker).unwrap();
+
+        for (&i, value) in positions.iter().zip(opening.values.iter()) {
+            assert!(*value == vector[i]);
+        }
+        assert!(verify_batch(&srs, &commitment, &points, &opening).unwrap());
+
+        let mut wrong_value = opening.clone();
+        wrong_value.values[2].add_assign(&E::Fr::one());
+        assert!(!verify_batch(&srs, &commitment, &points, &wrong_value).unwrap());
+
+        assert!(!verify_batch(&srs, &commitment, &points[1..], &opening).unwrap());
+
+        let mut wrong_points = points.clone();
+        wrong_points.swap(0, 1);
+        assert!(!verify_batch(&srs, &commitment, &wrong_points, &opening).unwrap());
+
+        // Openings at a single point agree with the batch
+        let single = open_batch(&srs, &coeffs, &points[1..2], &worker).unwrap();
+        assert!(single.witness == open(&srs, &coeffs, &points[1], &worker).unwrap().witness);
+
+        let mut repeated = points.clone();
+        repeated[3] = repeated[0];
+        assert!(open_batch(&srs, &coeffs, &repeated, &worker).is_err());
+
+        let too_many: Vec<_> = (0..5).map(|_| E::Fr::rand(rng)).collect();
+        assert!(open_batch(&srs, &coeffs, &too_many, &worker).is_err());
+        assert!(verify_batch(&srs, &commitment, &too_many, &opening).is_err());
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_batch_open::<Bls12, _>(rng);
+}
+
+#[test]
+fn serialization() {
+    use pairing::bls12_381::Bls12;
+    use rand::{self, Rand};
+
+    fn test_serialization<E: Engine, R: rand::Rng>(rng: &mut R)
+    {
+        let worker = Worker::new();
+        let srs = generate_random_srs::<E, _>(8, 3, rng);
+
+        let mut v = vec![];
+        srs.write(&mut v).unwrap();
+
+        assert!(srs == Srs::read(&v[..], true).unwrap());
+        assert!(srs == Srs::read(&v[..], false).unwrap());
+
+        let trimmed = srs.trim(4, 2).unwrap();
+        assert_eq!(trimmed.powers_g1.len(), 4);
+        assert!(srs.trim(9, 2).is_err());
+
+        let coeffs: Vec<_> = (0..8).map(|_| E::Fr::rand(rng)).collect();
+        let points: Vec<_> = (0..2).map(|_| E::Fr::rand(rng)).collect();
+
+        let opening = open(&srs, &coeffs, &points[0], &worker).unwrap();
+        let mut v = vec![];
+        opening.write(&mut v).unwrap();
+        assert!(opening == Opening::read(&v[..]).unwrap());
+
+        let opening = open_batch(&srs, &coeffs, &points, &worker).unwrap();
+        let mut v = vec![];
+        opening.write(&mut v).unwrap();
+        assert!(opening == BatchOpening::read(&v[..]).unwrap());
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_serialization::<Bls12, _>(rng);
+}
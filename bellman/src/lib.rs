@@ -3,6 +3,7 @@ extern crate rand;
 extern crate futures;
 extern crate bit_vec;
 extern crate byteorder;
+extern crate blake2_rfc;
 extern crate ff;
 
 #[cfg(feature = "multithread")]
@@ -12,10 +13,16 @@ extern crate num_cpus;
 #[cfg(feature = "multithread")]
 extern crate crossbeam;
 
+#[cfg(test)]
+mod tests;
+
 pub mod multicore;
-mod multiexp;
+pub mod multiexp;
+mod transcript;
 pub mod domain;
+pub mod kzg;
 pub mod groth16;
+pub mod plonk;
 
 use ff::{Field};
 use pairing::{Engine};
@@ -79,6 +86,34 @@ impl<E: Engine> LinearCombination<E> {
     pub fn zero() -> LinearCombination<E> {
         LinearCombination(vec![])
     }
+
+    /// Merges the terms of the same variable, drops the terms with a
+    /// zero coefficient and sorts the rest by variable, inputs first.
+    /// The result evaluates to the same value under any assignment.
+    pub fn normalize(self) -> LinearCombination<E> {
+        let mut terms = self.0;
+        terms.sort_by_key(|&(var, _)| match var.0 {
+            Index::Input(i) => (0, i),
+            Index::Aux(i) => (1, i)
+        });
+
+        let mut result: Vec<(Variable, E::Fr)> = Vec::with_capacity(terms.len());
+
+        for (var, coeff) in terms {
+            if let Some(last) = result.last_mut() {
+                if (last.0).0 == var.0 {
+                    last.1.add_assign(&coeff);
+                    continue;
+                }
+            }
+
+            result.push((var, coeff));
+        }
+
+        result.retain(|&(_, ref coeff)| !coeff.is_zero());
+
+        LinearCombination(result)
+    }
 }
 
 impl<E: Engine> Add<(E::Fr, Variable)> for LinearCombination<E> {
@@ -8,7 +8,7 @@ use ff::{
     PrimeFieldRepr,
     ScalarEngine,
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::io;
 use bit_vec::{self, BitVec};
 use std::iter;
@@ -22,6 +22,12 @@ pub trait SourceBuilder<G: CurveAffine>: Send + Sync + 'static + Clone {
     type Source: Source<G>;
 
     fn new(self) -> Self::Source;
+
+    /// Returns the precomputed table of the bases, and the index in it of
+    /// the first base of the source, if the bases have one.
+    fn fixed_base_table(&self) -> Option<(&FixedBaseTable<G>, usize)> {
+        None
+    }
 }
 
 /// A source of bases, like an iterator.
@@ -31,6 +37,15 @@ pub trait Source<G: CurveAffine> {
 
     /// Skips `amt` elements from the source, avoiding deserialization.
     fn skip(&mut self, amt: usize) -> Result<(), SynthesisError>;
+
+    /// Parses the element from the source and returns it. Fails if the
+    /// point is at infinity.
+    fn read(&mut self) -> Result<G, SynthesisError> {
+        let mut p = G::Projective::zero();
+        self.add_assign_mixed(&mut p)?;
+
+        Ok(p.into_affine())
+    }
 }
 
 impl<G: CurveAffine> SourceBuilder<G> for (Arc<Vec<G>>, usize) {
@@ -67,6 +82,20 @@ impl<G: CurveAffine> Source<G> for (Arc<Vec<G>>, usize) {
 
         Ok(())
     }
+
+    fn read(&mut self) -> Result<G, SynthesisError> {
+        if self.0.len() <= self.1 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "expected more bases from source").into());
+        }
+
+        if self.0[self.1].is_zero() {
+            return Err(SynthesisError::UnexpectedIdentity)
+        }
+
+        self.1 += 1;
+
+        Ok(self.0[self.1 - 1])
+    }
 }
 
 pub trait QueryDensity {
@@ -98,6 +127,7 @@ impl<'a> QueryDensity for &'a FullDensity {
     }
 }
 
+#[derive(Clone)]
 pub struct DensityTracker {
     bv: BitVec,
     total_density: usize
@@ -146,6 +176,7 @@ fn multiexp_inner<Q, D, G, S>(
     exponents: Arc<Vec<<<G::Engine as ScalarEngine>::Fr as PrimeField>::Repr>>,
     mut skip: u32,
     c: u32,
+    num_bits: u32,
     handle_trivial: bool
 ) -> Box<Future<Item=<G as CurveAffine>::Projective, Error=SynthesisError>>
     where for<'a> &'a Q: QueryDensity,
@@ -213,14 +244,14 @@ fn multiexp_inner<Q, D, G, S>(
 
     skip += c;
 
-    if skip >= <G::Engine as ScalarEngine>::Fr::NUM_BITS {
+    if skip >= num_bits {
         // There isn't another region.
         Box::new(this)
     } else {
         // There's another region more significant. Calculate and join it with
         // this region recursively.
         Box::new(
-            this.join(multiexp_inner(pool, bases, density_map, exponents, skip, c, false))
+            this.join(multiexp_inner(pool, bases, density_map, exponents, skip, c, num_bits, false))
                 .map(move |(this, mut higher)| {
                     for _ in 0..c {
                         higher.double();
@@ -247,12 +278,6 @@ pub fn multiexp<Q, D, G, S>(
           G: CurveAffine,
           S: SourceBuilder<G>
 {
-    let c = if exponents.len() < 32 {
-        3u32
-    } else {
-        (f64::from(exponents.len() as u32)).ln().ceil() as u32
-    };
-
     if let Some(query_size) = density_map.as_ref().get_query_size() {
         // If the density map has a known query size, it should not be
         // inconsistent with the number of exponents.
@@ -260,7 +285,574 @@ pub fn multiexp<Q, D, G, S>(
         assert!(query_size == exponents.len());
     }
 
-    multiexp_inner(pool, bases, density_map, exponents, 0, c, true)
+    if let Some((table, offset)) = bases.fixed_base_table() {
+        return table.multiexp_at(pool, offset, density_map, exponents);
+    }
+
+    // With an endomorphism of the group, every term splits into two
+    // with exponents of half of the bits, which need half of the windows
+    if has_endomorphism::<G>() {
+        return split_multiexp(pool, bases, density_map, exponents);
+    }
+
+    let c = window_size(exponents.len());
+    let num_bits = <G::Engine as ScalarEngine>::Fr::NUM_BITS;
+
+    multiexp_inner(pool, bases, density_map, exponents, 0, c, num_bits, true)
+}
+
+/// The size of the unsigned windows for a multi-exponentiation of n bases
+fn window_size(n: usize) -> u32 {
+    if n < 32 {
+        3
+    } else {
+        (f64::from(n as u32)).ln().ceil() as u32
+    }
+}
+
+/// Collects the bases of the nonzero exponents with their exponents
+fn collect_terms<Q, D, G, S>(
+    bases: S,
+    density_map: D,
+    exponents: &[<<G::Engine as ScalarEngine>::Fr as PrimeField>::Repr]
+) -> Result<(Vec<G>, Vec<<<G::Engine as ScalarEngine>::Fr as PrimeField>::Repr>), SynthesisError>
+    where for<'a> &'a Q: QueryDensity,
+          D: AsRef<Q>,
+          G: CurveAffine,
+          S: SourceBuilder<G>
+{
+    let mut bases = bases.new();
+
+    let zero = <G::Engine as ScalarEngine>::Fr::zero().into_repr();
+
+    let mut points = vec![];
+    let mut scalars = vec![];
+    for (&exp, density) in exponents.iter().zip(density_map.as_ref().iter()) {
+        if density {
+            if exp == zero {
+                bases.skip(1)?;
+            } else {
+                points.push(bases.read()?);
+                scalars.push(exp);
+            }
+        }
+    }
+
+    Ok((points, scalars))
+}
+
+/// Performs the multi-exponentiation with unsigned windows after splitting
+/// every term with the endomorphism of the group.
+fn split_multiexp<Q, D, G, S>(
+    pool: &Worker,
+    bases: S,
+    density_map: D,
+    exponents: Arc<Vec<<<G::Engine as ScalarEngine>::Fr as PrimeField>::Repr>>
+) -> Box<Future<Item=<G as CurveAffine>::Projective, Error=SynthesisError>>
+    where for<'a> &'a Q: QueryDensity,
+          D: Send + Sync + 'static + Clone + AsRef<Q>,
+          G: CurveAffine,
+          S: SourceBuilder<G>
+{
+    let worker = pool.clone();
+
+    let split = pool.compute(move || {
+        let (points, scalars) = collect_terms(bases, density_map, &exponents)?;
+
+        Ok(split_terms(&worker, &points, &scalars).expect("the group has an endomorphism"))
+    });
+
+    let pool = pool.clone();
+
+    Box::new(split.and_then(move |(points, scalars)| {
+        let c = window_size(scalars.len());
+        let num_bits = scalars.iter().map(|s| s.num_bits()).max().unwrap_or(0);
+
+        multiexp_inner::<FullDensity, _, _, _>(
+            &pool,
+            (Arc::new(points), 0),
+            FullDensity,
+            Arc::new(scalars),
+            0,
+            c,
+            num_bits,
+            true
+        )
+    }))
+}
+
+/// The algorithm of a multi-exponentiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiexpAlgorithm {
+    /// Pippenger's algorithm with unsigned windows, which are computed
+    /// separately and sum their buckets in projective coordinates.
+    Unsigned,
+    /// Pippenger's algorithm with signed digits, which halve the number
+    /// of buckets, summing the points of the buckets with batches of
+    /// affine additions sharing a single inversion.
+    SignedBatchAffine
+}
+
+/// Perform multi-exponentiation with the algorithm. The caller is responsible
+/// for ensuring the query size is the same as the number of exponents.
+pub fn multiexp_with<Q, D, G, S>(
+    pool: &Worker,
+    bases: S,
+    density_map: D,
+    exponents: Arc<Vec<<<G::Engine as ScalarEngine>::Fr as PrimeField>::Repr>>,
+    algorithm: MultiexpAlgorithm
+) -> Box<Future<Item=<G as CurveAffine>::Projective, Error=SynthesisError>>
+    where for<'a> &'a Q: QueryDensity,
+          D: Send + Sync + 'static + Clone + AsRef<Q>,
+          G: CurveAffine,
+          S: SourceBuilder<G>
+{
+    if algorithm == MultiexpAlgorithm::Unsigned || bases.fixed_base_table().is_some() {
+        return multiexp(pool, bases, density_map, exponents);
+    }
+
+    if let Some(query_size) = density_map.as_ref().get_query_size() {
+        assert!(query_size == exponents.len());
+    }
+
+    let worker = pool.clone();
+
+    Box::new(pool.compute(move || {
+        let (points, scalars) = collect_terms(bases, density_map, &exponents)?;
+
+        Ok(signed_multiexp(&worker, &points, &scalars))
+    }))
+}
+
+/// The number of windows of the signed digits of scalars of `num_bits`
+/// bits, which have room for the carry into the most significant window
+fn num_signed_windows(num_bits: u32, c: u32) -> usize {
+    (num_bits / c + 1) as usize
+}
+
+/// Writes the signed digits of the scalar in windows of c bits, which
+/// are in [-2^(c-1), 2^(c-1)], so that the scalar is sum d_j * 2^(c * j)
+fn signed_digits<R: PrimeFieldRepr>(scalar: &R, c: u32, digits: &mut [i32]) {
+    let limbs = scalar.as_ref();
+    let num_windows = digits.len();
+    let mut carry = 0;
+
+    for (j, d) in digits.iter_mut().enumerate() {
+        let offset = j as u32 * c;
+        let limb = (offset / 64) as usize;
+        let shift = offset % 64;
+
+        let mut bits = 0;
+        if limb < limbs.len() {
+            bits = limbs[limb] >> shift;
+            if shift + c > 64 && limb + 1 < limbs.len() {
+                bits |= limbs[limb + 1] << (64 - shift);
+            }
+        }
+
+        // The most significant window is at most 2^(c-1) with the carry,
+        // since the windows have room for one more bit than the scalars
+        let w = (bits % (1 << c)) as i32 + carry;
+        if w >= 1 << (c - 1) && j + 1 < num_windows {
+            *d = w - (1 << c);
+            carry = 1;
+        } else {
+            *d = w;
+            carry = 0;
+        }
+    }
+}
+
+/// Returns sum d_i * p_i for the signed digits d_i, which are at most
+/// `num_buckets` in absolute value, by summing the points of each bucket
+/// with batches of affine additions
+fn bucket_sum<G, I>(terms: I, num_buckets: usize) -> G::Projective
+    where G: CurveAffine,
+          I: Iterator<Item=(i32, G)> + Clone
+{
+    // The batches of additions are worth their inversion
+    // only with enough additions
+    const MIN_BATCH_SIZE: usize = 16;
+
+    // Sort the points into the buckets of |d| - 1, negated for negative digits
+    let mut offsets = vec![0; num_buckets + 1];
+    for (d, _) in terms.clone() {
+        if d != 0 {
+            offsets[d.abs() as usize] += 1;
+        }
+    }
+    for b in 0..num_buckets {
+        offsets[b + 1] += offsets[b];
+    }
+
+    let mut points = vec![G::zero(); offsets[num_buckets]];
+    {
+        let mut positions = offsets.clone();
+        for (d, p) in terms {
+            if d != 0 {
+                let position = &mut positions[(d.abs() - 1) as usize];
+                points[*position] = p;
+                if d < 0 {
+                    points[*position].negate();
+                }
+                *position += 1;
+            }
+        }
+    }
+
+    // Add the points of each bucket in pairs, halving them every time
+    loop {
+        let mut lhs = Vec::with_capacity(points.len() / 2);
+        let mut rhs = Vec::with_capacity(points.len() / 2);
+        for b in 0..num_buckets {
+            let mut i = offsets[b];
+            while i + 1 < offsets[b + 1] {
+                lhs.push(points[i]);
+                rhs.push(points[i + 1]);
+                i += 2;
+            }
+        }
+
+        if lhs.len() < MIN_BATCH_SIZE {
+            break;
+        }
+
+        G::batch_add_assign(&mut lhs, &rhs);
+
+        let mut sums = lhs.into_iter();
+        let mut next_points = Vec::with_capacity(points.len() - rhs.len());
+        let mut next_offsets = Vec::with_capacity(num_buckets + 1);
+        for b in 0..num_buckets {
+            next_offsets.push(next_points.len());
+
+            let len = offsets[b + 1] - offsets[b];
+            next_points.extend(sums.by_ref().take(len / 2));
+            if len % 2 == 1 {
+                next_points.push(points[offsets[b + 1] - 1]);
+            }
+        }
+        next_offsets.push(next_points.len());
+
+        points = next_points;
+        offsets = next_offsets;
+    }
+
+    // Summation by parts, as in `multiexp_inner`
+    let mut acc = G::Projective::zero();
+    let mut running_sum = G::Projective::zero();
+    for b in (0..num_buckets).rev() {
+        for p in &points[offsets[b]..offsets[b + 1]] {
+            running_sum.add_assign_mixed(p);
+        }
+        acc.add_assign(&running_sum);
+    }
+
+    acc
+}
+
+/// The size of the windows of the signed digits for a multi-exponentiation
+/// of n bases, with half as many buckets as with unsigned windows
+fn signed_window_size(n: usize) -> u32 {
+    if n < 32 {
+        3
+    } else {
+        (f64::from(n as u32)).ln().ceil() as u32 + 1
+    }
+}
+
+/// Whether the group has an endomorphism to split the terms with
+fn has_endomorphism<G: CurveAffine>() -> bool {
+    // The endomorphism is a property of the group, so that
+    // splitting any term tells whether the group has one
+    let one = G::Scalar::one().into_repr();
+    G::one().split_mul(&one).is_some()
+}
+
+/// Splits every term s * p into two terms with scalars of half of the bits
+/// with the endomorphism of the group, or returns `None` if it has none.
+fn split_terms<G: CurveAffine>(
+    worker: &Worker,
+    points: &[G],
+    scalars: &[<G::Scalar as PrimeField>::Repr]
+) -> Option<(Vec<G>, Vec<<G::Scalar as PrimeField>::Repr>)>
+{
+    if !has_endomorphism::<G>() {
+        return None;
+    }
+
+    let zero = G::Scalar::zero().into_repr();
+    let mut split_points = vec![G::zero(); 2 * points.len()];
+    let mut split_scalars = vec![zero; 2 * scalars.len()];
+
+    worker.scope(points.len(), |scope, chunk| {
+        for ((split_points, split_scalars), (points, scalars)) in split_points.chunks_mut(2 * chunk)
+            .zip(split_scalars.chunks_mut(2 * chunk))
+            .zip(points.chunks(chunk).zip(scalars.chunks(chunk)))
+        {
+            scope.spawn(move || {
+                for (i, (p, s)) in points.iter().zip(scalars.iter()).enumerate() {
+                    // The scalars which are not reduced stay whole
+                    let terms = p.split_mul(s).unwrap_or([(*p, *s), (G::zero(), zero)]);
+
+                    for (j, &(q, k)) in terms.iter().enumerate() {
+                        split_points[2 * i + j] = q;
+                        split_scalars[2 * i + j] = k;
+                    }
+                }
+            });
+        }
+    });
+
+    Some((split_points, split_scalars))
+}
+
+/// Returns sum s_i * p_i, summing the buckets of the
+/// windows of the signed digits separately
+fn signed_multiexp<G: CurveAffine>(
+    worker: &Worker,
+    points: &[G],
+    scalars: &[<G::Scalar as PrimeField>::Repr]
+) -> G::Projective
+{
+    if scalars.is_empty() {
+        return G::Projective::zero();
+    }
+
+    // With an endomorphism of the group, every term splits into two
+    // with scalars of half of the bits, which need half of the windows
+    let split;
+    let (points, scalars) = match split_terms(worker, points, scalars) {
+        Some(terms) => {
+            split = terms;
+            (&split.0[..], &split.1[..])
+        },
+        None => (points, scalars)
+    };
+
+    let c = signed_window_size(scalars.len());
+    let num_bits = scalars.iter().map(|s| s.num_bits()).max().unwrap();
+    let num_windows = num_signed_windows(num_bits, c);
+
+    // The digits of the scalars are consecutive
+    let mut digits = vec![0; scalars.len() * num_windows];
+    worker.scope(scalars.len(), |scope, chunk| {
+        for (digits, scalars) in digits.chunks_mut(chunk * num_windows).zip(scalars.chunks(chunk)) {
+            scope.spawn(move || {
+                for (digits, scalar) in digits.chunks_mut(num_windows).zip(scalars.iter()) {
+                    signed_digits(scalar, c, digits);
+                }
+            });
+        }
+    });
+
+    let mut window_sums = vec![G::Projective::zero(); num_windows];
+    worker.scope(num_windows, |scope, chunk| {
+        let digits = &digits;
+
+        for (i, window_sums) in window_sums.chunks_mut(chunk).enumerate() {
+            scope.spawn(move || {
+                for (j, window_sum) in window_sums.iter_mut().enumerate() {
+                    let terms = digits[(i * chunk + j)..].iter()
+                        .step_by(num_windows)
+                        .cloned()
+                        .zip(points.iter().cloned());
+
+                    *window_sum = bucket_sum(terms, 1 << (c - 1));
+                }
+            });
+        }
+    });
+
+    let mut acc = G::Projective::zero();
+    for window_sum in window_sums.iter().rev() {
+        for _ in 0..c {
+            acc.double();
+        }
+
+        acc.add_assign(window_sum);
+    }
+
+    acc
+}
+
+/// The multiples 2^(c * j) * P of the bases P for every window j of the
+/// signed digits, so that multi-exponentiations with the fixed bases sum
+/// all of the windows in the same buckets, without any doublings.
+#[derive(Clone)]
+pub struct FixedBaseTable<G: CurveAffine> {
+    window: u32,
+    num_windows: usize,
+    table: Arc<Vec<G>>
+}
+
+impl<G: CurveAffine> FixedBaseTable<G> {
+    /// Precomputes the table of the bases for windows of c bits, which
+    /// has about 256 / c points for every base, and fails if a base is
+    /// the point at infinity. Larger windows need fewer additions, until
+    /// the 2^c additions of summing the buckets dominate.
+    pub fn new(worker: &Worker, bases: &[G], c: u32) -> Result<FixedBaseTable<G>, SynthesisError> {
+        assert!(c >= 2 && c <= 24);
+
+        if bases.iter().any(|p| p.is_zero()) {
+            return Err(SynthesisError::UnexpectedIdentity);
+        }
+
+        let num_windows = num_signed_windows(G::Scalar::NUM_BITS, c);
+
+        let mut table = vec![G::Projective::zero(); bases.len() * num_windows];
+        worker.scope(bases.len(), |scope, chunk| {
+            for (table, bases) in table.chunks_mut(chunk * num_windows).zip(bases.chunks(chunk)) {
+                scope.spawn(move || {
+                    for (multiples, base) in table.chunks_mut(num_windows).zip(bases.iter()) {
+                        let mut p = base.into_projective();
+                        for multiple in multiples.iter_mut() {
+                            *multiple = p;
+                            for _ in 0..c {
+                                p.double();
+                            }
+                        }
+                    }
+
+                    G::Projective::batch_normalization(table);
+                });
+            }
+        });
+
+        Ok(FixedBaseTable {
+            window: c,
+            num_windows: num_windows,
+            table: Arc::new(table.into_iter().map(|p| p.into_affine()).collect())
+        })
+    }
+
+    /// Returns the size of the windows which minimizes the number of additions
+    /// of multi-exponentiations with `num_bases` bases, among the tables of at
+    /// most `max_points` points, or `None` if none of them is worth it.
+    pub fn recommended_window(num_bases: usize, max_points: usize) -> Option<u32> {
+        if num_bases == 0 {
+            return None;
+        }
+
+        // Every window of every exponent is an addition, and summing the
+        // buckets needs twice as many additions as there are buckets, which
+        // is done for every window without a table
+        let c = signed_window_size(num_bases);
+        let mut best_cost = num_signed_windows(G::Scalar::NUM_BITS, c) as u64 * (num_bases as u64 + (1 << c));
+        let mut best = None;
+
+        for c in 2..25 {
+            let num_windows = num_signed_windows(G::Scalar::NUM_BITS, c);
+            if num_bases.checked_mul(num_windows).map_or(true, |size| size > max_points) {
+                continue;
+            }
+
+            let cost = (num_bases * num_windows) as u64 + (1 << c);
+            if cost < best_cost {
+                best_cost = cost;
+                best = Some(c);
+            }
+        }
+
+        best
+    }
+
+    /// Returns the number of bases.
+    pub fn len(&self) -> usize {
+        self.table.len() / self.num_windows
+    }
+
+    /// Returns the number of points of the table.
+    pub fn num_points(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Perform multi-exponentiation with the bases of the table, which are
+    /// the bases of the exponents in the density map. The caller is
+    /// responsible for ensuring the query size is the same as the number
+    /// of exponents.
+    pub fn multiexp<Q, D>(
+        &self,
+        pool: &Worker,
+        density_map: D,
+        exponents: Arc<Vec<<G::Scalar as PrimeField>::Repr>>
+    ) -> Box<Future<Item=G::Projective, Error=SynthesisError>>
+        where for<'a> &'a Q: QueryDensity,
+              D: Send + Sync + 'static + Clone + AsRef<Q>
+    {
+        if let Some(query_size) = density_map.as_ref().get_query_size() {
+            assert!(query_size == exponents.len());
+        }
+
+        self.multiexp_at(pool, 0, density_map, exponents)
+    }
+
+    /// Perform multi-exponentiation with the bases of the table
+    /// starting at the base with index `offset`.
+    fn multiexp_at<Q, D>(
+        &self,
+        pool: &Worker,
+        offset: usize,
+        density_map: D,
+        exponents: Arc<Vec<<G::Scalar as PrimeField>::Repr>>
+    ) -> Box<Future<Item=G::Projective, Error=SynthesisError>>
+        where for<'a> &'a Q: QueryDensity,
+              D: Send + Sync + 'static + Clone + AsRef<Q>
+    {
+        let table = self.clone();
+        let worker = pool.clone();
+
+        Box::new(pool.compute(move || {
+            let zero = G::Scalar::zero().into_repr();
+
+            // Collect the indices of the bases of the nonzero exponents
+            let mut terms = vec![];
+            let mut index = offset;
+            for (&exp, density) in exponents.iter().zip(density_map.as_ref().iter()) {
+                if density {
+                    if index >= table.len() {
+                        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "expected more bases from source").into());
+                    }
+
+                    if exp != zero {
+                        terms.push((index, exp));
+                    }
+                    index += 1;
+                }
+            }
+
+            let c = table.window;
+            let num_windows = table.num_windows;
+            let result = Mutex::new(G::Projective::zero());
+
+            // Every thread sums its own buckets
+            let chunk = (terms.len() >> worker.log_num_cpus()) + 1;
+
+            worker.scope(0, |scope, _| {
+                for terms in terms.chunks(chunk) {
+                    let table = &table.table;
+                    let result = &result;
+
+                    scope.spawn(move || {
+                        let mut digits = vec![0; terms.len() * num_windows];
+                        for (digits, &(_, ref exp)) in digits.chunks_mut(num_windows).zip(terms.iter()) {
+                            signed_digits(exp, c, digits);
+                        }
+
+                        let terms = terms.iter()
+                            .flat_map(|&(index, _)| table[(index * num_windows)..((index + 1) * num_windows)].iter())
+                            .zip(digits.iter())
+                            .map(|(&p, &d)| (d, p));
+
+                        let sum = bucket_sum(terms, 1 << (c - 1));
+
+                        result.lock().unwrap().add_assign(&sum);
+                    });
+                }
+            });
+
+            Ok(result.into_inner().unwrap())
+        }))
+    }
 }
 
 #[test]
@@ -283,6 +875,7 @@ fn test_with_bls12() {
 
     use rand::{self, Rand};
     use pairing::bls12_381::Bls12;
+    use pairing::Engine;
 
     const SAMPLES: usize = 1 << 14;
 
@@ -303,3 +896,130 @@ fn test_with_bls12() {
 
     assert_eq!(naive, fast);
 }
+
+#[test]
+fn test_with_bn256() {
+    use rand::{self, Rand};
+    use pairing::bn256::Bn256;
+    use pairing::Engine;
+
+    fn test_group<G: CurveAffine, R: rand::Rng>(rng: &mut R) {
+        const SAMPLES: usize = 1 << 10;
+
+        let pool = Worker::new();
+
+        let g: Vec<G> = (0..SAMPLES).map(|_| G::Projective::rand(rng).into_affine()).collect();
+        let mut v: Vec<_> = (0..SAMPLES).map(|_| G::Scalar::rand(rng).into_repr()).collect();
+        v[0] = G::Scalar::zero().into_repr();
+        v[1] = G::Scalar::one().into_repr();
+
+        let mut density = DensityTracker::new();
+        let mut naive = G::Projective::zero();
+        let mut sparse = vec![];
+        for i in 0..SAMPLES {
+            density.add_element();
+            if i % 3 != 0 {
+                density.inc(i);
+                sparse.push(g[i]);
+                naive.add_assign(&g[i].mul(v[i]));
+            }
+        }
+
+        // The terms are split with the endomorphism of the group
+        let fast = multiexp(&pool, (Arc::new(sparse), 0), Arc::new(density), Arc::new(v)).wait().unwrap();
+
+        assert_eq!(naive, fast);
+    }
+
+    let rng = &mut rand::thread_rng();
+    test_group::<<Bn256 as Engine>::G1Affine, _>(rng);
+    test_group::<<Bn256 as Engine>::G2Affine, _>(rng);
+}
+
+#[test]
+fn test_signed_batch_affine() {
+    use rand::{self, Rand};
+    use pairing::bls12_381::Bls12;
+    use pairing::bn256::Bn256;
+    use pairing::Engine;
+
+    fn test_group<G: CurveAffine, R: rand::Rng>(rng: &mut R) {
+        let pool = Worker::new();
+
+        for &samples in [1, 2, 31, 100, 1 << 10].iter() {
+            let mut g: Vec<G> = (0..samples).map(|_| G::Projective::rand(rng).into_affine()).collect();
+            let mut v: Vec<_> = (0..samples).map(|_| G::Scalar::rand(rng).into_repr()).collect();
+
+            // Equal and inverse bases with the same exponents, which end up in the
+            // same buckets, and trivial exponents
+            if samples > 10 {
+                g[1] = g[0];
+                v[1] = v[0];
+                g[3] = g[2];
+                g[3].negate();
+                v[3] = v[2];
+                v[4] = G::Scalar::zero().into_repr();
+                v[5] = G::Scalar::one().into_repr();
+            }
+
+            let mut density = DensityTracker::new();
+            for i in 0..samples {
+                density.add_element();
+                if i % 3 != 0 {
+                    density.inc(i);
+                }
+            }
+            let sparse: Vec<_> = g.iter().enumerate().filter(|&(i, _)| i % 3 != 0).map(|(_, p)| *p).collect();
+
+            let g = Arc::new(g);
+            let v = Arc::new(v);
+            let sparse = Arc::new(sparse);
+            let density = Arc::new(density);
+
+            let full = multiexp(&pool, (g.clone(), 0), FullDensity, v.clone()).wait().unwrap();
+            let signed = multiexp_with(
+                &pool,
+                (g.clone(), 0),
+                FullDensity,
+                v.clone(),
+                MultiexpAlgorithm::SignedBatchAffine
+            ).wait().unwrap();
+            assert_eq!(full, signed);
+
+            let partial = multiexp(&pool, (sparse.clone(), 0), density.clone(), v.clone()).wait().unwrap();
+            let signed = multiexp_with(
+                &pool,
+                (sparse.clone(), 0),
+                density.clone(),
+                v.clone(),
+                MultiexpAlgorithm::SignedBatchAffine
+            ).wait().unwrap();
+            assert_eq!(partial, signed);
+
+            for &c in [2, 5, 8].iter() {
+                let table = FixedBaseTable::new(&pool, &g, c).unwrap();
+                assert_eq!(table.len(), samples);
+                assert_eq!(full, table.multiexp(&pool, FullDensity, v.clone()).wait().unwrap());
+
+                let table = FixedBaseTable::new(&pool, &sparse, c).unwrap();
+                assert_eq!(partial, table.multiexp(&pool, density.clone(), v.clone()).wait().unwrap());
+            }
+        }
+
+        assert!(FixedBaseTable::new(&pool, &[G::one(), G::zero()], 4).is_err());
+
+        // There must be a base for every exponent
+        let table = FixedBaseTable::new(&pool, &[G::one()], 4).unwrap();
+        let v = Arc::new(vec![G::Scalar::one().into_repr(); 2]);
+        assert!(table.multiexp(&pool, FullDensity, v).wait().is_err());
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    test_group::<<Bls12 as Engine>::G1Affine, _>(rng);
+    test_group::<<Bls12 as Engine>::G2Affine, _>(rng);
+
+    // The groups of BN256 split the terms with their endomorphisms
+    test_group::<<Bn256 as Engine>::G1Affine, _>(rng);
+    test_group::<<Bn256 as Engine>::G2Affine, _>(rng);
+}
@@ -0,0 +1,293 @@
+//! Conversion of rank-1 constraints into PLONK gates.
+//!
+//! Every gate constrains three wires with
+//! `q_L * a + q_R * b + q_O * c + q_M * a * b + q_C = 0`. A constraint
+//! `A * B = C` becomes a single gate if each of the linear combinations
+//! is a multiple of a variable plus a constant. Longer combinations are
+//! summed into temporary variables first, with a gate per addition.
+//! The gates of the public inputs come first, checking the wire
+//! `a` against the public input polynomial of the verifier.
+
+use pairing::Engine;
+
+use ff::Field;
+
+use std::collections::HashMap;
+
+use ::{
+    SynthesisError,
+    Circuit,
+    ConstraintSystem,
+    LinearCombination,
+    Variable,
+    Index
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Wire {
+    Input(usize),
+    Aux(usize),
+    Temp(usize),
+    // Wires of gates with a zero coefficient, which
+    // are not copies of any variable
+    Unused
+}
+
+pub struct Gate<E: Engine> {
+    pub wires: [Wire; 3],
+    pub q_l: E::Fr,
+    pub q_r: E::Fr,
+    pub q_o: E::Fr,
+    pub q_m: E::Fr,
+    pub q_c: E::Fr
+}
+
+pub struct Assembly<E: Engine> {
+    // Whether the values of the variables are computed
+    witness: bool,
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    num_temp: usize,
+    pub gates: Vec<Gate<E>>,
+    pub input_assignment: Vec<E::Fr>,
+    aux_assignment: Vec<E::Fr>,
+    temp_assignment: Vec<E::Fr>
+}
+
+impl<E: Engine> Assembly<E> {
+    /// Converts the circuit into gates, with the values
+    /// of the wires only if `witness` is set
+    pub fn synthesize<C: Circuit<E>>(circuit: C, witness: bool) -> Result<Self, SynthesisError> {
+        let mut assembly = Assembly {
+            witness: witness,
+            num_inputs: 0,
+            num_aux: 0,
+            num_temp: 0,
+            gates: vec![],
+            input_assignment: vec![],
+            aux_assignment: vec![],
+            temp_assignment: vec![]
+        };
+
+        assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        circuit.synthesize(&mut assembly)?;
+
+        // a - x_i = 0, with x_i added by the verifier
+        let mut gates: Vec<_> = (1..assembly.num_inputs).map(|i| Gate {
+            wires: [Wire::Input(i), Wire::Unused, Wire::Unused],
+            q_l: E::Fr::one(),
+            q_r: E::Fr::zero(),
+            q_o: E::Fr::zero(),
+            q_m: E::Fr::zero(),
+            q_c: E::Fr::zero()
+        }).collect();
+        gates.append(&mut assembly.gates);
+        assembly.gates = gates;
+
+        Ok(assembly)
+    }
+
+    fn value(&self, wire: Wire) -> E::Fr {
+        match wire {
+            Wire::Input(i) => self.input_assignment[i],
+            Wire::Aux(i) => self.aux_assignment[i],
+            Wire::Temp(i) => self.temp_assignment[i],
+            Wire::Unused => E::Fr::zero()
+        }
+    }
+
+    /// Values of the wires of the gates, padded with zeroes to `n`
+    pub fn wire_values(&self, n: usize) -> [Vec<E::Fr>; 3] {
+        assert!(self.witness);
+
+        let column = |j: usize| {
+            let mut values: Vec<_> = self.gates.iter().map(|g| self.value(g.wires[j])).collect();
+            values.resize(n, E::Fr::zero());
+
+            values
+        };
+
+        [column(0), column(1), column(2)]
+    }
+
+    /// Returns the permutation of the wires of the first `n` gates, which
+    /// maps every wire to the next one that is a copy of the same variable.
+    /// Wire `j` of gate `i` is numbered `j * n + i`.
+    pub fn permutation(&self, n: usize) -> Vec<usize> {
+        let mut permutation: Vec<usize> = (0..(3 * n)).collect();
+        let mut cycles: HashMap<Wire, Vec<usize>> = HashMap::new();
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            for (j, wire) in gate.wires.iter().enumerate() {
+                if *wire != Wire::Unused {
+                    cycles.entry(*wire).or_insert_with(|| vec![]).push(j * n + i);
+                }
+            }
+        }
+
+        for cycle in cycles.values() {
+            for (k, &position) in cycle.iter().enumerate() {
+                permutation[position] = cycle[(k + 1) % cycle.len()];
+            }
+        }
+
+        permutation
+    }
+
+    fn wire(var: Variable) -> Wire {
+        match var.get_unchecked() {
+            Index::Input(i) => Wire::Input(i),
+            Index::Aux(i) => Wire::Aux(i)
+        }
+    }
+
+    /// Reduces the linear combination to `coeff * wire + constant`
+    fn reduce(&mut self, lc: LinearCombination<E>) -> (Wire, E::Fr, E::Fr) {
+        let mut constant = E::Fr::zero();
+        let mut terms = vec![];
+
+        for &(var, coeff) in lc.normalize().as_ref() {
+            match var.get_unchecked() {
+                Index::Input(0) => constant = coeff,
+                _ => terms.push((Self::wire(var), coeff))
+            }
+        }
+
+        match terms.len() {
+            0 => return (Wire::Unused, E::Fr::zero(), constant),
+            1 => return (terms[0].0, terms[0].1, constant),
+            _ => {}
+        }
+
+        let (mut sum, mut sum_coeff) = terms[0];
+
+        for &(wire, coeff) in terms[1..].iter() {
+            let next = Wire::Temp(self.num_temp);
+            self.num_temp += 1;
+
+            if self.witness {
+                let mut value = self.value(sum);
+                value.mul_assign(&sum_coeff);
+                let mut tmp = self.value(wire);
+                tmp.mul_assign(&coeff);
+                value.add_assign(&tmp);
+
+                self.temp_assignment.push(value);
+            }
+
+            let mut minus_one = E::Fr::one();
+            minus_one.negate();
+
+            self.gates.push(Gate {
+                wires: [sum, wire, next],
+                q_l: sum_coeff,
+                q_r: coeff,
+                q_o: minus_one,
+                q_m: E::Fr::zero(),
+                q_c: E::Fr::zero()
+            });
+
+            sum = next;
+            sum_coeff = E::Fr::one();
+        }
+
+        (sum, E::Fr::one(), constant)
+    }
+}
+
+impl<E: Engine> ConstraintSystem<E> for Assembly<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        if self.witness {
+            self.aux_assignment.push(f()?);
+        }
+
+        let index = self.num_aux;
+        self.num_aux += 1;
+
+        Ok(Variable(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        f: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        if self.witness {
+            self.input_assignment.push(f()?);
+        }
+
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+
+        Ok(Variable(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        a: LA,
+        b: LB,
+        c: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        let (a, a_coeff, a_constant) = self.reduce(a(LinearCombination::zero()));
+        let (b, b_coeff, b_constant) = self.reduce(b(LinearCombination::zero()));
+        let (c, c_coeff, c_constant) = self.reduce(c(LinearCombination::zero()));
+
+        // (a_coeff * a + a_constant) * (b_coeff * b + b_constant) = c_coeff * c + c_constant
+        let mut q_m = a_coeff;
+        q_m.mul_assign(&b_coeff);
+
+        let mut q_l = a_coeff;
+        q_l.mul_assign(&b_constant);
+
+        let mut q_r = b_coeff;
+        q_r.mul_assign(&a_constant);
+
+        let mut q_o = c_coeff;
+        q_o.negate();
+
+        let mut q_c = a_constant;
+        q_c.mul_assign(&b_constant);
+        q_c.sub_assign(&c_constant);
+
+        self.gates.push(Gate {
+            wires: [a, b, c],
+            q_l: q_l,
+            q_r: q_r,
+            q_o: q_o,
+            q_m: q_m,
+            q_c: q_c
+        });
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+        where NR: Into<String>, N: FnOnce() -> NR
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self)
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+}
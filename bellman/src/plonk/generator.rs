@@ -0,0 +1,161 @@
+use rand::Rng;
+
+use std::cmp;
+
+use pairing::Engine;
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use super::{
+    Srs,
+    ProvingKey,
+    VerifyingKey,
+    coset_generators
+};
+
+use super::adapter::{
+    Assembly,
+    Gate
+};
+use super::polynomial::root_of_unity;
+
+use ::kzg::{
+    self,
+    interpolate,
+    commit
+};
+
+use ::{
+    SynthesisError,
+    Circuit
+};
+
+use ::multicore::{
+    Worker
+};
+
+/// Smallest domain, so that the blinding
+/// of the polynomials fits into it
+const MIN_DOMAIN_SIZE: usize = 4;
+
+/// Number of powers of the reference string needed for a domain of
+/// size `n`, as the quotient polynomial has degree `3n + 5` with the
+/// blinding, and its last part is committed to separately
+fn srs_size_for_domain(n: usize) -> usize {
+    n + 6
+}
+
+fn domain_size(num_gates: usize) -> usize {
+    cmp::max(num_gates, MIN_DOMAIN_SIZE).next_power_of_two()
+}
+
+/// Generates a random reference string that supports domains up to the
+/// given size. This is for testing only, as the knowledge of the secret
+/// allows to forge proofs: a real one comes from a multi-party ceremony.
+pub fn generate_random_srs<E, R>(
+    max_domain_size: usize,
+    rng: &mut R
+) -> Srs<E>
+    where E: Engine, R: Rng
+{
+    kzg::generate_random_srs(srs_size_for_domain(max_domain_size), 2, rng)
+}
+
+/// Derives the keys of the circuit from the reference string
+pub fn generate_keys<E, C>(
+    circuit: C,
+    srs: &Srs<E>
+) -> Result<ProvingKey<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>
+{
+    let assembly = Assembly::synthesize(circuit, false)?;
+
+    let n = domain_size(assembly.gates.len());
+    if n.trailing_zeros() > E::Fr::S {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    let srs = srs.trim(srs_size_for_domain(n), 2)?;
+
+    let worker = Worker::new();
+
+    let selector = |f: fn(&Gate<E>) -> E::Fr| {
+        let mut values: Vec<_> = assembly.gates.iter().map(f).collect();
+        values.resize(n, E::Fr::zero());
+
+        interpolate::<E>(values, &worker)
+    };
+
+    let q_l = selector(|g| g.q_l)?;
+    let q_r = selector(|g| g.q_r)?;
+    let q_o = selector(|g| g.q_o)?;
+    let q_m = selector(|g| g.q_m)?;
+    let q_c = selector(|g| g.q_c)?;
+
+    // Wire j of gate i is labeled with k_j * omega^i
+    let omega = root_of_unity::<E::Fr>(n);
+    let labels = {
+        let mut omega_powers = Vec::with_capacity(n);
+        let mut cur = E::Fr::one();
+        for _ in 0..n {
+            omega_powers.push(cur);
+            cur.mul_assign(&omega);
+        }
+
+        let mut labels = Vec::with_capacity(3 * n);
+        for k in coset_generators::<E::Fr>().iter() {
+            labels.extend(omega_powers.iter().map(|w| {
+                let mut label = *w;
+                label.mul_assign(k);
+                label
+            }));
+        }
+
+        labels
+    };
+
+    let permutation = assembly.permutation(n);
+    let sigma_values = {
+        let column = |j: usize| -> Vec<E::Fr> {
+            permutation[(j * n)..((j + 1) * n)].iter().map(|&position| labels[position]).collect()
+        };
+
+        [column(0), column(1), column(2)]
+    };
+
+    let sigmas = [
+        interpolate::<E>(sigma_values[0].clone(), &worker)?,
+        interpolate::<E>(sigma_values[1].clone(), &worker)?,
+        interpolate::<E>(sigma_values[2].clone(), &worker)?
+    ];
+
+    let vk = VerifyingKey {
+        n: n,
+        num_inputs: assembly.num_inputs - 1,
+        q_l: commit(&srs, &q_l, &worker)?,
+        q_r: commit(&srs, &q_r, &worker)?,
+        q_o: commit(&srs, &q_o, &worker)?,
+        q_m: commit(&srs, &q_m, &worker)?,
+        q_c: commit(&srs, &q_c, &worker)?,
+        sigma_1: commit(&srs, &sigmas[0], &worker)?,
+        sigma_2: commit(&srs, &sigmas[1], &worker)?,
+        sigma_3: commit(&srs, &sigmas[2], &worker)?,
+        x_g2: srs.powers_g2[1]
+    };
+
+    Ok(ProvingKey {
+        vk: vk,
+        q_l: q_l,
+        q_r: q_r,
+        q_o: q_o,
+        q_m: q_m,
+        q_c: q_c,
+        sigmas: sigmas,
+        sigma_values: sigma_values,
+        srs: srs,
+        num_gates: assembly.gates.len()
+    })
+}
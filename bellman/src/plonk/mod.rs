@@ -0,0 +1,724 @@
+//! The PLONK proving system, with the polynomial commitments of `kzg`.
+//!
+//! Unlike Groth16, the structured reference string is universal: one
+//! `Srs` with enough powers serves every circuit, and the keys of a
+//! circuit are derived from it deterministically without any secret.
+//! Circuits are the same `Circuit<E>` as for Groth16; their rank-1
+//! constraints are converted into PLONK gates while synthesizing.
+
+use pairing::{
+    Engine,
+    CurveAffine,
+    EncodedPoint
+};
+
+use ff::{
+    PrimeField,
+    PrimeFieldRepr
+};
+
+use std::io::{self, Read, Write};
+use byteorder::{BigEndian, WriteBytesExt, ReadBytesExt};
+
+mod adapter;
+mod generator;
+mod polynomial;
+mod prover;
+mod verifier;
+
+pub use self::generator::*;
+pub use self::prover::*;
+pub use self::verifier::*;
+
+pub use ::kzg::Srs;
+
+use ::transcript::Transcript;
+
+/// Multipliers of the labels of the wires `a`, `b` and `c`,
+/// so that the three cosets of the domain are distinct
+fn coset_generators<F: PrimeField>() -> [F; 3] {
+    let k1 = F::multiplicative_generator();
+    let mut k2 = k1;
+    k2.square();
+
+    [F::one(), k1, k2]
+}
+
+#[derive(Clone)]
+pub struct VerifyingKey<E: Engine> {
+    // Size of the evaluation domain, a power of two
+    // larger than the number of gates
+    pub n: usize,
+
+    // Number of public inputs, without the constant one
+    pub num_inputs: usize,
+
+    // Commitments to the selector polynomials of the
+    // gates q_L * a + q_R * b + q_O * c + q_M * a * b + q_C
+    pub q_l: E::G1Affine,
+    pub q_r: E::G1Affine,
+    pub q_o: E::G1Affine,
+    pub q_m: E::G1Affine,
+    pub q_c: E::G1Affine,
+
+    // Commitments to the polynomials of the copy
+    // constraint permutation of each wire
+    pub sigma_1: E::G1Affine,
+    pub sigma_2: E::G1Affine,
+    pub sigma_3: E::G1Affine,
+
+    // x in G2 of the reference string
+    pub x_g2: E::G2Affine
+}
+
+impl<E: Engine> PartialEq for VerifyingKey<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n &&
+        self.num_inputs == other.num_inputs &&
+        self.commitments() == other.commitments() &&
+        self.x_g2 == other.x_g2
+    }
+}
+
+impl<E: Engine> VerifyingKey<E> {
+    /// Starts the transcript of a proof for the statement,
+    /// which is the circuit and its public inputs
+    fn transcript(&self, public_inputs: &[E::Fr]) -> Transcript {
+        let mut transcript = Transcript::new(b"bellman plonk");
+
+        transcript.absorb(&(self.n as u64).to_be_bytes());
+        for g in self.commitments().iter() {
+            transcript.commit_point(g);
+        }
+        for input in public_inputs {
+            transcript.commit_scalar(input);
+        }
+
+        transcript
+    }
+
+    fn commitments(&self) -> [E::G1Affine; 8] {
+        [
+            self.q_l, self.q_r, self.q_o, self.q_m, self.q_c,
+            self.sigma_1, self.sigma_2, self.sigma_3
+        ]
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        writer.write_u32::<BigEndian>(self.n as u32)?;
+        writer.write_u32::<BigEndian>(self.num_inputs as u32)?;
+        for g in self.commitments().iter() {
+            writer.write_all(g.into_uncompressed().as_ref())?;
+        }
+        writer.write_all(self.x_g2.into_uncompressed().as_ref())?;
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(
+        mut reader: R
+    ) -> io::Result<Self>
+    {
+        let n = reader.read_u32::<BigEndian>()? as usize;
+        if !n.is_power_of_two() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "domain size is not a power of two"));
+        }
+
+        let num_inputs = reader.read_u32::<BigEndian>()? as usize;
+
+        let mut g1_repr = <E::G1Affine as CurveAffine>::Uncompressed::empty();
+        let mut commitments = vec![];
+
+        // Selectors may be zero, so are their commitments
+        for _ in 0..8 {
+            reader.read_exact(g1_repr.as_mut())?;
+            commitments.push(g1_repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+
+        let mut g2_repr = <E::G2Affine as CurveAffine>::Uncompressed::empty();
+        reader.read_exact(g2_repr.as_mut())?;
+        let x_g2 = g2_repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(VerifyingKey {
+            n: n,
+            num_inputs: num_inputs,
+            q_l: commitments[0],
+            q_r: commitments[1],
+            q_o: commitments[2],
+            q_m: commitments[3],
+            q_c: commitments[4],
+            sigma_1: commitments[5],
+            sigma_2: commitments[6],
+            sigma_3: commitments[7],
+            x_g2: x_g2
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ProvingKey<E: Engine> {
+    pub vk: VerifyingKey<E>,
+
+    // Coefficients of the selector polynomials
+    q_l: Vec<E::Fr>,
+    q_r: Vec<E::Fr>,
+    q_o: Vec<E::Fr>,
+    q_m: Vec<E::Fr>,
+    q_c: Vec<E::Fr>,
+
+    // Coefficients of the permutation polynomials,
+    // and their values over the domain
+    sigmas: [Vec<E::Fr>; 3],
+    sigma_values: [Vec<E::Fr>; 3],
+
+    // Powers of the reference string needed
+    // for the commitments of the prover
+    srs: Srs<E>,
+
+    num_gates: usize
+}
+
+impl<E: Engine> PartialEq for ProvingKey<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vk == other.vk &&
+        self.polynomials() == other.polynomials() &&
+        self.srs == other.srs &&
+        self.num_gates == other.num_gates
+    }
+}
+
+fn write_scalars<E: Engine, W: Write>(
+    scalars: &[E::Fr],
+    writer: &mut W
+) -> io::Result<()>
+{
+    writer.write_u32::<BigEndian>(scalars.len() as u32)?;
+    for s in scalars {
+        s.into_repr().write_be(&mut *writer)?;
+    }
+
+    Ok(())
+}
+
+fn read_scalars<E: Engine, R: Read>(
+    reader: &mut R
+) -> io::Result<Vec<E::Fr>>
+{
+    let len = reader.read_u32::<BigEndian>()? as usize;
+
+    let mut scalars = vec![];
+    for _ in 0..len {
+        let mut repr = <E::Fr as PrimeField>::Repr::default();
+        repr.read_be(&mut *reader)?;
+        scalars.push(E::Fr::from_repr(repr).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+    }
+
+    Ok(scalars)
+}
+
+impl<E: Engine> ProvingKey<E> {
+    /// Number of gates the circuit was converted into,
+    /// including the ones of the public inputs
+    pub fn num_gates(&self) -> usize {
+        self.num_gates
+    }
+
+    /// Coefficients of the selector and permutation polynomials,
+    /// and the values of the permutation polynomials, in the
+    /// order they are serialized
+    fn polynomials(&self) -> [&Vec<E::Fr>; 11] {
+        [
+            &self.q_l, &self.q_r, &self.q_o, &self.q_m, &self.q_c,
+            &self.sigmas[0], &self.sigmas[1], &self.sigmas[2],
+            &self.sigma_values[0], &self.sigma_values[1], &self.sigma_values[2]
+        ]
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        self.vk.write(&mut writer)?;
+        writer.write_u32::<BigEndian>(self.num_gates as u32)?;
+        for p in self.polynomials().iter() {
+            write_scalars::<E, _>(p, &mut writer)?;
+        }
+        self.srs.write(&mut writer)?;
+
+        Ok(())
+    }
+
+    /// Reads the proving key, checking that the points of the
+    /// reference string are in the prime order subgroup only
+    /// if `checked` is set
+    pub fn read<R: Read>(
+        mut reader: R,
+        checked: bool
+    ) -> io::Result<Self>
+    {
+        let vk = VerifyingKey::<E>::read(&mut reader)?;
+
+        let num_gates = reader.read_u32::<BigEndian>()? as usize;
+        if num_gates > vk.n {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "too many gates for the domain"));
+        }
+
+        let mut p = vec![];
+        for _ in 0..11 {
+            let scalars = read_scalars::<E, _>(&mut reader)?;
+            if scalars.len() != vk.n {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "polynomial does not match the domain"));
+            }
+
+            p.push(scalars);
+        }
+
+        let srs = Srs::<E>::read(&mut reader, checked)?;
+        if srs.powers_g2.len() < 2 || srs.powers_g2[1] != vk.x_g2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "reference string does not match the verifying key"));
+        }
+
+        let mut p = p.into_iter();
+        let mut next = || p.next().unwrap();
+
+        Ok(ProvingKey {
+            vk: vk,
+            q_l: next(),
+            q_r: next(),
+            q_o: next(),
+            q_m: next(),
+            q_c: next(),
+            sigmas: [next(), next(), next()],
+            sigma_values: [next(), next(), next()],
+            srs: srs,
+            num_gates: num_gates
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Proof<E: Engine> {
+    // Commitments to the wire polynomials
+    pub a: E::G1Affine,
+    pub b: E::G1Affine,
+    pub c: E::G1Affine,
+
+    // Commitment to the grand product of the permutation argument
+    pub z: E::G1Affine,
+
+    // Commitments to the parts of the quotient polynomial
+    pub t_lo: E::G1Affine,
+    pub t_mid: E::G1Affine,
+    pub t_hi: E::G1Affine,
+
+    // Openings at the challenge zeta, and of z at zeta * omega
+    pub w_zeta: E::G1Affine,
+    pub w_zeta_omega: E::G1Affine,
+
+    // Values of the polynomials at zeta
+    pub a_eval: E::Fr,
+    pub b_eval: E::Fr,
+    pub c_eval: E::Fr,
+    pub q_l_eval: E::Fr,
+    pub q_r_eval: E::Fr,
+    pub q_o_eval: E::Fr,
+    pub q_m_eval: E::Fr,
+    pub q_c_eval: E::Fr,
+    pub sigma_1_eval: E::Fr,
+    pub sigma_2_eval: E::Fr,
+    pub sigma_3_eval: E::Fr,
+    pub z_eval: E::Fr,
+
+    // Value of z at zeta * omega
+    pub z_omega_eval: E::Fr
+}
+
+impl<E: Engine> Proof<E> {
+    fn commitments(&self) -> [E::G1Affine; 9] {
+        [
+            self.a, self.b, self.c, self.z,
+            self.t_lo, self.t_mid, self.t_hi,
+            self.w_zeta, self.w_zeta_omega
+        ]
+    }
+
+    /// Values at zeta, in the order the polynomials are
+    /// combined for the opening, and the value of z at zeta * omega
+    fn evaluations(&self) -> [E::Fr; 13] {
+        [
+            self.a_eval, self.b_eval, self.c_eval,
+            self.q_l_eval, self.q_r_eval, self.q_o_eval, self.q_m_eval, self.q_c_eval,
+            self.sigma_1_eval, self.sigma_2_eval, self.sigma_3_eval,
+            self.z_eval, self.z_omega_eval
+        ]
+    }
+
+    pub fn write<W: Write>(
+        &self,
+        mut writer: W
+    ) -> io::Result<()>
+    {
+        for g in self.commitments().iter() {
+            writer.write_all(g.into_compressed().as_ref())?;
+        }
+        for s in self.evaluations().iter() {
+            s.into_repr().write_be(&mut writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read<R: Read>(
+        mut reader: R
+    ) -> io::Result<Self>
+    {
+        let mut g1_repr = <E::G1Affine as CurveAffine>::Compressed::empty();
+        let mut g = vec![];
+
+        for _ in 0..9 {
+            reader.read_exact(g1_repr.as_mut())?;
+            g.push(g1_repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+
+        let mut s = vec![];
+
+        for _ in 0..13 {
+            let mut repr = <E::Fr as PrimeField>::Repr::default();
+            repr.read_be(&mut reader)?;
+            s.push(E::Fr::from_repr(repr).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?);
+        }
+
+        Ok(Proof {
+            a: g[0],
+            b: g[1],
+            c: g[2],
+            z: g[3],
+            t_lo: g[4],
+            t_mid: g[5],
+            t_hi: g[6],
+            w_zeta: g[7],
+            w_zeta_omega: g[8],
+            a_eval: s[0],
+            b_eval: s[1],
+            c_eval: s[2],
+            q_l_eval: s[3],
+            q_r_eval: s[4],
+            q_o_eval: s[5],
+            q_m_eval: s[6],
+            q_c_eval: s[7],
+            sigma_1_eval: s[8],
+            sigma_2_eval: s[9],
+            sigma_3_eval: s[10],
+            z_eval: s[11],
+            z_omega_eval: s[12]
+        })
+    }
+}
+
+impl<E: Engine> PartialEq for Proof<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.commitments() == other.commitments() &&
+        self.evaluations() == other.evaluations()
+    }
+}
+
+#[cfg(test)]
+mod test_with_bls12_381 {
+    use super::*;
+    use {Circuit, SynthesisError, ConstraintSystem};
+    use tests::MySillyCircuit;
+
+    use rand::{Rand, thread_rng};
+    use ff::Field;
+    use pairing::bls12_381::{Bls12, Fr};
+
+    /// (a + b + 1) * (2a - b) = d, and d + 5 = e, where e is public
+    /// and the witness of d can be tampered with
+    struct LinearCombinationCircuit<E: Engine> {
+        a: Option<E::Fr>,
+        b: Option<E::Fr>,
+        d_offset: E::Fr
+    }
+
+    impl<E: Engine> LinearCombinationCircuit<E> {
+        fn values(a: E::Fr, b: E::Fr) -> (E::Fr, E::Fr) {
+            let mut left = a;
+            left.add_assign(&b);
+            left.add_assign(&E::Fr::one());
+
+            let mut right = a;
+            right.double();
+            right.sub_assign(&b);
+
+            let mut d = left;
+            d.mul_assign(&right);
+
+            let mut e = d;
+            e.add_assign(&E::Fr::from_str("5").unwrap());
+
+            (d, e)
+        }
+    }
+
+    impl<E: Engine> Circuit<E> for LinearCombinationCircuit<E> {
+        fn synthesize<CS: ConstraintSystem<E>>(
+            self,
+            cs: &mut CS
+        ) -> Result<(), SynthesisError>
+        {
+            let values = match (self.a, self.b) {
+                (Some(a), Some(b)) => Some(Self::values(a, b)),
+                _ => None
+            };
+            let d_offset = self.d_offset;
+
+            let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+            let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+            let d = cs.alloc(|| "d", || {
+                let mut d = values.ok_or(SynthesisError::AssignmentMissing)?.0;
+                d.add_assign(&d_offset);
+                Ok(d)
+            })?;
+            let e = cs.alloc_input(|| "e", || Ok(values.ok_or(SynthesisError::AssignmentMissing)?.1))?;
+
+            cs.enforce(
+                || "(a + b + 1) * (2a - b) = d",
+                |lc| lc + a + b + CS::one(),
+                |lc| lc + a + a - b,
+                |lc| lc + d
+            );
+
+            cs.enforce(
+                || "(d + 5) * 1 = e",
+                |lc| lc + d + (E::Fr::from_str("5").unwrap(), CS::one()),
+                |lc| lc + CS::one(),
+                |lc| lc + e
+            );
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let rng = &mut thread_rng();
+
+        let srs = generate_random_srs::<Bls12, _>(16, rng);
+        let pk = generate_keys(MySillyCircuit { a: None, b: None }, &srs).unwrap();
+
+        for _ in 0..5 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b)
+                },
+                &pk,
+                rng
+            ).unwrap();
+
+            assert!(verify_proof(&pk.vk, &proof, &[c]).unwrap());
+            assert!(!verify_proof(&pk.vk, &proof, &[a]).unwrap());
+            assert!(verify_proof(&pk.vk, &proof, &[]).is_err());
+        }
+    }
+
+    #[test]
+    fn universal_setup() {
+        let rng = &mut thread_rng();
+
+        // One reference string for both circuits
+        let srs = generate_random_srs::<Bls12, _>(16, rng);
+
+        let silly_pk = generate_keys(MySillyCircuit { a: None, b: None }, &srs).unwrap();
+        let lc_pk = generate_keys(LinearCombinationCircuit {
+            a: None,
+            b: None,
+            d_offset: Fr::zero()
+        }, &srs).unwrap();
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let (_, e) = LinearCombinationCircuit::<Bls12>::values(a, b);
+
+        let proof = create_random_proof(
+            LinearCombinationCircuit {
+                a: Some(a),
+                b: Some(b),
+                d_offset: Fr::zero()
+            },
+            &lc_pk,
+            rng
+        ).unwrap();
+
+        assert!(verify_proof(&lc_pk.vk, &proof, &[e]).unwrap());
+        assert!(!verify_proof(&silly_pk.vk, &proof, &[e]).unwrap());
+
+        // Too few powers for the circuit
+        let small_srs = generate_random_srs::<Bls12, _>(2, rng);
+        assert!(generate_keys(MySillyCircuit::<Bls12> { a: None, b: None }, &small_srs).is_err());
+    }
+
+    #[test]
+    fn unsatisfied() {
+        let rng = &mut thread_rng();
+
+        let srs = generate_random_srs::<Bls12, _>(16, rng);
+        let pk = generate_keys(LinearCombinationCircuit {
+            a: None,
+            b: None,
+            d_offset: Fr::zero()
+        }, &srs).unwrap();
+
+        let result = create_random_proof(
+            LinearCombinationCircuit {
+                a: Some(Fr::rand(rng)),
+                b: Some(Fr::rand(rng)),
+                d_offset: Fr::one()
+            },
+            &pk,
+            rng
+        );
+
+        match result {
+            Err(SynthesisError::Unsatisfiable) => {},
+            _ => panic!("the proof of an unsatisfied circuit should fail")
+        }
+    }
+
+    #[test]
+    fn serialization() {
+        let rng = &mut thread_rng();
+
+        let srs = generate_random_srs::<Bls12, _>(4, rng);
+
+        {
+            let mut v = vec![];
+
+            srs.write(&mut v).unwrap();
+            assert_eq!(v.len(), 4 + 10 * 96 + 4 + 2 * 192);
+
+            let de_srs = Srs::read(&v[..], true).unwrap();
+            assert!(srs == de_srs);
+
+            let de_srs = Srs::read(&v[..], false).unwrap();
+            assert!(srs == de_srs);
+        }
+
+        let pk = generate_keys(MySillyCircuit { a: None, b: None }, &srs).unwrap();
+
+        {
+            let mut v = vec![];
+
+            pk.vk.write(&mut v).unwrap();
+            assert_eq!(v.len(), 8 + 8 * 96 + 192);
+
+            let de_vk = VerifyingKey::read(&v[..]).unwrap();
+            assert!(pk.vk == de_vk);
+        }
+
+        {
+            let mut v = vec![];
+
+            pk.write(&mut v).unwrap();
+
+            let de_pk = ProvingKey::read(&v[..], true).unwrap();
+            assert!(pk == de_pk);
+
+            let de_pk = ProvingKey::read(&v[..], false).unwrap();
+            assert!(pk == de_pk);
+
+            assert!(ProvingKey::<Bls12>::read(&v[..v.len() - 1], true).is_err());
+        }
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b)
+            },
+            &pk,
+            rng
+        ).unwrap();
+
+        {
+            let mut v = vec![];
+
+            proof.write(&mut v).unwrap();
+            assert_eq!(v.len(), 9 * 48 + 13 * 32);
+
+            let de_proof = Proof::read(&v[..]).unwrap();
+            assert!(proof == de_proof);
+
+            assert!(verify_proof(&pk.vk, &de_proof, &[c]).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_with_bn256 {
+    use super::*;
+    use tests::MySillyCircuit;
+
+    use rand::{Rand, thread_rng};
+    use ff::Field;
+    use pairing::bn256::{Bn256, Fr};
+
+    #[test]
+    fn prove_and_verify_serialized() {
+        let rng = &mut thread_rng();
+
+        let srs = generate_random_srs::<Bn256, _>(4, rng);
+        let pk = generate_keys(MySillyCircuit { a: None, b: None }, &srs).unwrap();
+
+        let pk = {
+            let mut v = vec![];
+            pk.write(&mut v).unwrap();
+            ProvingKey::<Bn256>::read(&v[..], true).unwrap()
+        };
+
+        let vk = {
+            let mut v = vec![];
+            pk.vk.write(&mut v).unwrap();
+            assert_eq!(v.len(), 8 + 8 * 64 + 128);
+            VerifyingKey::<Bn256>::read(&v[..]).unwrap()
+        };
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b)
+            },
+            &pk,
+            rng
+        ).unwrap();
+
+        let proof = {
+            let mut v = vec![];
+            proof.write(&mut v).unwrap();
+            assert_eq!(v.len(), 9 * 32 + 13 * 32);
+            Proof::<Bn256>::read(&v[..]).unwrap()
+        };
+
+        assert!(verify_proof(&vk, &proof, &[c]).unwrap());
+        assert!(!verify_proof(&vk, &proof, &[a]).unwrap());
+    }
+}
@@ -0,0 +1,82 @@
+//! Helpers for polynomials in coefficient form, which are
+//! `Vec<E::Fr>` starting from the constant coefficient.
+
+use pairing::Engine;
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use ::SynthesisError;
+
+use ::domain::{
+    EvaluationDomain,
+    Scalar
+};
+
+use ::multicore::Worker;
+
+/// Returns the primitive `n`-th root of unity of the
+/// radix-2 `EvaluationDomain` of size `n`
+pub fn root_of_unity<F: PrimeField>(n: usize) -> F {
+    assert!(n.is_power_of_two());
+
+    let mut omega = F::root_of_unity();
+    for _ in n.trailing_zeros()..F::S {
+        omega.square();
+    }
+
+    omega
+}
+
+/// Inverts all of the elements, which must be non-zero,
+/// with a single inversion
+pub fn batch_invert<F: Field>(v: &mut [F]) {
+    let mut products = Vec::with_capacity(v.len());
+    let mut acc = F::one();
+
+    for x in v.iter() {
+        products.push(acc);
+        acc.mul_assign(x);
+    }
+
+    let mut acc = acc.inverse().expect("elements are non-zero");
+
+    for (x, p) in v.iter_mut().zip(products.into_iter()).rev() {
+        let mut inverse = acc;
+        inverse.mul_assign(&p);
+        acc.mul_assign(x);
+        *x = inverse;
+    }
+}
+
+/// Evaluates the polynomial over the coset of the domain of the
+/// given size, which must be at least the number of coefficients
+pub fn coset_values<E: Engine>(coeffs: &[E::Fr], size: usize, worker: &Worker) -> Result<Vec<E::Fr>, SynthesisError> {
+    assert!(coeffs.len() <= size);
+
+    let mut values: Vec<_> = coeffs.iter().map(|c| Scalar::<E>(*c)).collect();
+    values.resize(size, Scalar(E::Fr::zero()));
+
+    let mut domain = EvaluationDomain::from_coeffs(values)?;
+    domain.coset_fft(worker);
+
+    Ok(domain.into_coeffs().into_iter().map(|s| s.0).collect())
+}
+
+/// Adds `blinding(X) * (X^n - 1)`, which vanishes over the domain
+/// of size `n`, so the values of the polynomial there are kept
+pub fn blind<F: Field>(mut coeffs: Vec<F>, n: usize, blinding: &[F]) -> Vec<F> {
+    assert!(coeffs.len() <= n && blinding.len() <= n);
+
+    coeffs.resize(n + blinding.len(), F::zero());
+
+    for (i, b) in blinding.iter().enumerate() {
+        coeffs[i].sub_assign(b);
+        coeffs[n + i].add_assign(b);
+    }
+
+    coeffs
+}
+
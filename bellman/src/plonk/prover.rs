@@ -0,0 +1,393 @@
+use rand::Rng;
+
+use pairing::Engine;
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use super::{
+    ProvingKey,
+    Proof,
+    coset_generators
+};
+
+use super::adapter::Assembly;
+use super::polynomial::{
+    root_of_unity,
+    batch_invert,
+    coset_values,
+    blind
+};
+
+use ::{
+    SynthesisError,
+    Circuit
+};
+
+use ::kzg::{
+    interpolate,
+    evaluate,
+    commit,
+    open
+};
+
+use ::domain::{
+    EvaluationDomain,
+    Scalar
+};
+
+use ::multicore::{
+    Worker
+};
+
+pub fn create_random_proof<E, C, R>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    rng: &mut R
+) -> Result<Proof<E>, SynthesisError>
+    where E: Engine, C: Circuit<E>, R: Rng
+{
+    let assembly = Assembly::synthesize(circuit, true)?;
+
+    // The circuit has to be the one of the keys
+    if assembly.gates.len() != pk.num_gates || assembly.num_inputs != pk.vk.num_inputs + 1 {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let worker = Worker::new();
+
+    let n = pk.vk.n;
+    let omega = root_of_unity::<E::Fr>(n);
+    let k = coset_generators::<E::Fr>();
+    let public_inputs = &assembly.input_assignment[1..];
+
+    let mut transcript = pk.vk.transcript(public_inputs);
+
+    // Round 1: commit to the wires, blinded by a multiple of X^n - 1
+    let wire_values = assembly.wire_values(n);
+    let mut wires = vec![];
+    for values in wire_values.iter() {
+        let coeffs = interpolate::<E>(values.clone(), &worker)?;
+        wires.push(blind(coeffs, n, &[rng.gen(), rng.gen()]));
+    }
+
+    let a = commit(&pk.srs, &wires[0], &worker)?;
+    let b = commit(&pk.srs, &wires[1], &worker)?;
+    let c = commit(&pk.srs, &wires[2], &worker)?;
+
+    transcript.commit_point(&a);
+    transcript.commit_point(&b);
+    transcript.commit_point(&c);
+
+    let beta: E::Fr = transcript.challenge();
+    let gamma: E::Fr = transcript.challenge();
+
+    // Round 2: commit to the grand product of the permutation argument,
+    // z(omega^(i + 1)) = z(omega^i) * prod_j (w_j + beta * k_j * omega^i + gamma)
+    //                                     / (w_j + beta * sigma_j(omega^i) + gamma)
+    let z_values = {
+        let mut numerators = vec![E::Fr::one(); n];
+        let mut denominators = vec![E::Fr::one(); n];
+
+        for j in 0..3 {
+            let mut label = k[j];
+
+            for i in 0..n {
+                let mut tmp = label;
+                tmp.mul_assign(&beta);
+                tmp.add_assign(&wire_values[j][i]);
+                tmp.add_assign(&gamma);
+                numerators[i].mul_assign(&tmp);
+
+                let mut tmp = pk.sigma_values[j][i];
+                tmp.mul_assign(&beta);
+                tmp.add_assign(&wire_values[j][i]);
+                tmp.add_assign(&gamma);
+                denominators[i].mul_assign(&tmp);
+
+                label.mul_assign(&omega);
+            }
+        }
+
+        if denominators.iter().any(|d| d.is_zero()) {
+            return Err(SynthesisError::DivisionByZero);
+        }
+        batch_invert(&mut denominators);
+
+        let mut z = Vec::with_capacity(n);
+        let mut acc = E::Fr::one();
+        for (num, den) in numerators.iter().zip(denominators.iter()) {
+            z.push(acc);
+            acc.mul_assign(num);
+            acc.mul_assign(den);
+        }
+
+        z
+    };
+
+    let z_coeffs = blind(interpolate::<E>(z_values, &worker)?, n, &[rng.gen(), rng.gen(), rng.gen()]);
+    let z = commit(&pk.srs, &z_coeffs, &worker)?;
+
+    transcript.commit_point(&z);
+
+    let alpha: E::Fr = transcript.challenge();
+
+    // Round 3: commit to the quotient polynomial, evaluated over a coset
+    // of a domain larger than the degree 4n + 5 of the numerator
+    let size = 8 * n;
+
+    let pi_coeffs = {
+        let mut values = vec![E::Fr::zero(); n];
+        for (value, input) in values.iter_mut().zip(public_inputs.iter()) {
+            *value = *input;
+            value.negate();
+        }
+
+        interpolate::<E>(values, &worker)?
+    };
+
+    let coset = |coeffs: &[E::Fr]| coset_values::<E>(coeffs, size, &worker);
+
+    let a_coset = coset(&wires[0])?;
+    let b_coset = coset(&wires[1])?;
+    let c_coset = coset(&wires[2])?;
+    let z_coset = coset(&z_coeffs)?;
+    let q_l_coset = coset(&pk.q_l)?;
+    let q_r_coset = coset(&pk.q_r)?;
+    let q_o_coset = coset(&pk.q_o)?;
+    let q_m_coset = coset(&pk.q_m)?;
+    let q_c_coset = coset(&pk.q_c)?;
+    let sigma_1_coset = coset(&pk.sigmas[0])?;
+    let sigma_2_coset = coset(&pk.sigmas[1])?;
+    let sigma_3_coset = coset(&pk.sigmas[2])?;
+    let pi_coset = coset(&pi_coeffs)?;
+
+    // The points of the coset are g * omega_8n^i, where
+    // omega_8n^8 is omega, so z(omega * x) is 8 points later
+    let omega_size = root_of_unity::<E::Fr>(size);
+    let g = E::Fr::multiplicative_generator();
+
+    // X^n - 1 takes 8 values over the coset
+    let mut zh = vec![];
+    {
+        let mut x = g;
+        for _ in 0..8 {
+            let mut tmp = x.pow(&[n as u64]);
+            tmp.sub_assign(&E::Fr::one());
+            zh.push(tmp);
+
+            x.mul_assign(&omega_size);
+        }
+    }
+    let mut zh_inverse = zh.clone();
+    batch_invert(&mut zh_inverse);
+
+    // L_0(X) = (X^n - 1) / (n * (X - 1))
+    let l_0_coset = {
+        let n_fr = E::Fr::from_str(&format!("{}", n)).unwrap();
+
+        let mut denominators = Vec::with_capacity(size);
+        let mut x = g;
+        for _ in 0..size {
+            let mut tmp = x;
+            tmp.sub_assign(&E::Fr::one());
+            tmp.mul_assign(&n_fr);
+            denominators.push(tmp);
+
+            x.mul_assign(&omega_size);
+        }
+        batch_invert(&mut denominators);
+
+        for (i, l) in denominators.iter_mut().enumerate() {
+            l.mul_assign(&zh[i % 8]);
+        }
+
+        denominators
+    };
+
+    let mut alpha_squared = alpha;
+    alpha_squared.square();
+
+    let mut t_values = vec![Scalar::<E>(E::Fr::zero()); size];
+
+    worker.scope(size, |scope, chunk| {
+        for (chunk_index, t_values) in t_values.chunks_mut(chunk).enumerate() {
+            let (a_coset, b_coset, c_coset, z_coset) = (&a_coset, &b_coset, &c_coset, &z_coset);
+            let (q_l_coset, q_r_coset, q_o_coset, q_m_coset, q_c_coset) = (&q_l_coset, &q_r_coset, &q_o_coset, &q_m_coset, &q_c_coset);
+            let (sigma_1_coset, sigma_2_coset, sigma_3_coset) = (&sigma_1_coset, &sigma_2_coset, &sigma_3_coset);
+            let (pi_coset, l_0_coset, zh_inverse, k) = (&pi_coset, &l_0_coset, &zh_inverse, &k);
+
+            scope.spawn(move || {
+                let start = chunk_index * chunk;
+                let mut x = omega_size.pow(&[start as u64]);
+                x.mul_assign(&g);
+
+                for (offset, t) in t_values.iter_mut().enumerate() {
+                    let i = start + offset;
+                    let (a, b, c, z) = (&a_coset[i], &b_coset[i], &c_coset[i], &z_coset[i]);
+
+                    // q_M * a * b + q_L * a + q_R * b + q_O * c + q_C + PI
+                    let mut gate = *a;
+                    gate.mul_assign(b);
+                    gate.mul_assign(&q_m_coset[i]);
+                    let mut tmp = *a;
+                    tmp.mul_assign(&q_l_coset[i]);
+                    gate.add_assign(&tmp);
+                    let mut tmp = *b;
+                    tmp.mul_assign(&q_r_coset[i]);
+                    gate.add_assign(&tmp);
+                    let mut tmp = *c;
+                    tmp.mul_assign(&q_o_coset[i]);
+                    gate.add_assign(&tmp);
+                    gate.add_assign(&q_c_coset[i]);
+                    gate.add_assign(&pi_coset[i]);
+
+                    // (w_j + beta * k_j * X + gamma) * z(X)
+                    let mut copies = *z;
+                    for (w, k) in [a, b, c].iter().zip(k.iter()) {
+                        let mut tmp = x;
+                        tmp.mul_assign(k);
+                        tmp.mul_assign(&beta);
+                        tmp.add_assign(w);
+                        tmp.add_assign(&gamma);
+                        copies.mul_assign(&tmp);
+                    }
+
+                    // (w_j + beta * sigma_j(X) + gamma) * z(omega * X)
+                    let mut permuted = z_coset[(i + 8) % size];
+                    for (w, sigma) in [a, b, c].iter().zip([&sigma_1_coset[i], &sigma_2_coset[i], &sigma_3_coset[i]].iter()) {
+                        let mut tmp = **sigma;
+                        tmp.mul_assign(&beta);
+                        tmp.add_assign(w);
+                        tmp.add_assign(&gamma);
+                        permuted.mul_assign(&tmp);
+                    }
+
+                    copies.sub_assign(&permuted);
+                    copies.mul_assign(&alpha);
+                    gate.add_assign(&copies);
+
+                    // (z(X) - 1) * L_0(X)
+                    let mut first = *z;
+                    first.sub_assign(&E::Fr::one());
+                    first.mul_assign(&l_0_coset[i]);
+                    first.mul_assign(&alpha_squared);
+                    gate.add_assign(&first);
+
+                    gate.mul_assign(&zh_inverse[i % 8]);
+                    t.0 = gate;
+
+                    x.mul_assign(&omega_size);
+                }
+            });
+        }
+    });
+
+    let mut t_coeffs = EvaluationDomain::from_coeffs(t_values)?;
+    t_coeffs.icoset_fft(&worker);
+    let mut t_coeffs: Vec<E::Fr> = t_coeffs.into_coeffs().into_iter().map(|s| s.0).collect();
+
+    // The numerator is divisible by X^n - 1 only if the gates
+    // and the copy constraints are satisfied
+    if t_coeffs[(3 * n + 6)..].iter().any(|c| !c.is_zero()) {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    t_coeffs.truncate(3 * n + 6);
+
+    let t_hi_coeffs = t_coeffs.split_off(2 * n);
+    let t_mid_coeffs = t_coeffs.split_off(n);
+    let t_lo_coeffs = t_coeffs;
+
+    let t_lo = commit(&pk.srs, &t_lo_coeffs, &worker)?;
+    let t_mid = commit(&pk.srs, &t_mid_coeffs, &worker)?;
+    let t_hi = commit(&pk.srs, &t_hi_coeffs, &worker)?;
+
+    transcript.commit_point(&t_lo);
+    transcript.commit_point(&t_mid);
+    transcript.commit_point(&t_hi);
+
+    let zeta: E::Fr = transcript.challenge();
+
+    // Round 4: open the polynomials at zeta, and z at zeta * omega
+    let mut zeta_omega = zeta;
+    zeta_omega.mul_assign(&omega);
+
+    let opened = [
+        &wires[0], &wires[1], &wires[2],
+        &pk.q_l, &pk.q_r, &pk.q_o, &pk.q_m, &pk.q_c,
+        &pk.sigmas[0], &pk.sigmas[1], &pk.sigmas[2],
+        &z_coeffs
+    ];
+
+    let evaluations: Vec<E::Fr> = opened.iter().map(|p| evaluate(p, &zeta)).collect();
+    let z_omega_eval = evaluate(&z_coeffs, &zeta_omega);
+
+    for s in evaluations.iter().chain(Some(&z_omega_eval)) {
+        transcript.commit_scalar(s);
+    }
+
+    let v: E::Fr = transcript.challenge();
+
+    // Round 5: the quotient polynomial is t_lo + zeta^n * t_mid + zeta^2n * t_hi
+    // at zeta, and the opened polynomials are combined with powers of v
+    let mut aggregate = t_lo_coeffs;
+    {
+        let zeta_n = zeta.pow(&[n as u64]);
+        let mut power = E::Fr::one();
+
+        for part in [&t_mid_coeffs, &t_hi_coeffs].iter() {
+            power.mul_assign(&zeta_n);
+            add_scaled(&mut aggregate, part, &power);
+        }
+
+        let mut power = E::Fr::one();
+
+        for p in opened.iter() {
+            power.mul_assign(&v);
+            add_scaled(&mut aggregate, p, &power);
+        }
+    }
+
+    let w_zeta = open(&pk.srs, &aggregate, &zeta, &worker)?.witness;
+    let w_zeta_omega = open(&pk.srs, &z_coeffs, &zeta_omega, &worker)?.witness;
+
+    Ok(Proof {
+        a: a,
+        b: b,
+        c: c,
+        z: z,
+        t_lo: t_lo,
+        t_mid: t_mid,
+        t_hi: t_hi,
+        w_zeta: w_zeta,
+        w_zeta_omega: w_zeta_omega,
+        a_eval: evaluations[0],
+        b_eval: evaluations[1],
+        c_eval: evaluations[2],
+        q_l_eval: evaluations[3],
+        q_r_eval: evaluations[4],
+        q_o_eval: evaluations[5],
+        q_m_eval: evaluations[6],
+        q_c_eval: evaluations[7],
+        sigma_1_eval: evaluations[8],
+        sigma_2_eval: evaluations[9],
+        sigma_3_eval: evaluations[10],
+        z_eval: evaluations[11],
+        z_omega_eval: z_omega_eval
+    })
+}
+
+/// acc += by * p
+fn add_scaled<F: Field>(acc: &mut Vec<F>, p: &[F], by: &F) {
+    if acc.len() < p.len() {
+        acc.resize(p.len(), F::zero());
+    }
+
+    for (a, p) in acc.iter_mut().zip(p.iter()) {
+        let mut tmp = *p;
+        tmp.mul_assign(by);
+        a.add_assign(&tmp);
+    }
+}
@@ -0,0 +1,214 @@
+use pairing::{
+    Engine,
+    CurveProjective,
+    CurveAffine
+};
+
+use ff::{
+    Field,
+    PrimeField
+};
+
+use super::{
+    Proof,
+    VerifyingKey,
+    coset_generators
+};
+
+use super::polynomial::root_of_unity;
+
+use ::{
+    SynthesisError
+};
+
+pub fn verify_proof<E: Engine>(
+    vk: &VerifyingKey<E>,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr]
+) -> Result<bool, SynthesisError>
+{
+    if public_inputs.len() != vk.num_inputs {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let n = vk.n;
+    let omega = root_of_unity::<E::Fr>(n);
+    let k = coset_generators::<E::Fr>();
+
+    let mut transcript = vk.transcript(public_inputs);
+
+    transcript.commit_point(&proof.a);
+    transcript.commit_point(&proof.b);
+    transcript.commit_point(&proof.c);
+
+    let beta: E::Fr = transcript.challenge();
+    let gamma: E::Fr = transcript.challenge();
+
+    transcript.commit_point(&proof.z);
+
+    let alpha: E::Fr = transcript.challenge();
+
+    transcript.commit_point(&proof.t_lo);
+    transcript.commit_point(&proof.t_mid);
+    transcript.commit_point(&proof.t_hi);
+
+    let zeta: E::Fr = transcript.challenge();
+
+    let evaluations = proof.evaluations();
+    for s in evaluations.iter() {
+        transcript.commit_scalar(s);
+    }
+
+    let v: E::Fr = transcript.challenge();
+
+    transcript.commit_point(&proof.w_zeta);
+    transcript.commit_point(&proof.w_zeta_omega);
+
+    let u: E::Fr = transcript.challenge();
+
+    // Z_H(zeta) = zeta^n - 1
+    let zeta_n = zeta.pow(&[n as u64]);
+    let mut zh = zeta_n;
+    zh.sub_assign(&E::Fr::one());
+
+    let zh_inverse = match zh.inverse() {
+        Some(zh_inverse) => zh_inverse,
+        None => return Ok(false)
+    };
+
+    // L_i(zeta) = omega^i * (zeta^n - 1) / (n * (zeta - omega^i))
+    let n_fr = E::Fr::from_str(&format!("{}", n)).unwrap();
+    let lagrange = |omega_i: &E::Fr| {
+        let mut den = zeta;
+        den.sub_assign(omega_i);
+        den.mul_assign(&n_fr);
+
+        let mut l = den.inverse().unwrap();
+        l.mul_assign(omega_i);
+        l.mul_assign(&zh);
+        l
+    };
+
+    let l_0 = lagrange(&E::Fr::one());
+
+    // PI(zeta) = -sum x_i * L_i(zeta)
+    let mut pi = E::Fr::zero();
+    {
+        let mut omega_i = E::Fr::one();
+        for input in public_inputs {
+            let mut tmp = lagrange(&omega_i);
+            tmp.mul_assign(input);
+            pi.sub_assign(&tmp);
+
+            omega_i.mul_assign(&omega);
+        }
+    }
+
+    // The value of the quotient polynomial at zeta, from the identity it satisfies
+    let t_eval = {
+        let mut gate = proof.a_eval;
+        gate.mul_assign(&proof.b_eval);
+        gate.mul_assign(&proof.q_m_eval);
+        for &(w, q) in [
+            (proof.a_eval, proof.q_l_eval),
+            (proof.b_eval, proof.q_r_eval),
+            (proof.c_eval, proof.q_o_eval)
+        ].iter() {
+            let mut tmp = w;
+            tmp.mul_assign(&q);
+            gate.add_assign(&tmp);
+        }
+        gate.add_assign(&proof.q_c_eval);
+        gate.add_assign(&pi);
+
+        let wires = [proof.a_eval, proof.b_eval, proof.c_eval];
+
+        let mut copies = proof.z_eval;
+        for (w, k) in wires.iter().zip(k.iter()) {
+            let mut tmp = zeta;
+            tmp.mul_assign(k);
+            tmp.mul_assign(&beta);
+            tmp.add_assign(w);
+            tmp.add_assign(&gamma);
+            copies.mul_assign(&tmp);
+        }
+
+        let mut permuted = proof.z_omega_eval;
+        for (w, sigma) in wires.iter().zip([proof.sigma_1_eval, proof.sigma_2_eval, proof.sigma_3_eval].iter()) {
+            let mut tmp = *sigma;
+            tmp.mul_assign(&beta);
+            tmp.add_assign(w);
+            tmp.add_assign(&gamma);
+            permuted.mul_assign(&tmp);
+        }
+
+        copies.sub_assign(&permuted);
+        copies.mul_assign(&alpha);
+        gate.add_assign(&copies);
+
+        let mut first = proof.z_eval;
+        first.sub_assign(&E::Fr::one());
+        first.mul_assign(&l_0);
+        first.mul_assign(&alpha);
+        first.mul_assign(&alpha);
+        gate.add_assign(&first);
+
+        gate.mul_assign(&zh_inverse);
+        gate
+    };
+
+    // Combine the commitments and the values at zeta with the powers of v,
+    // starting from the quotient polynomial t_lo + zeta^n * t_mid + zeta^2n * t_hi
+    let mut f = proof.t_lo.into_projective();
+    f.add_assign(&proof.t_mid.mul(zeta_n));
+    let mut zeta_2n = zeta_n;
+    zeta_2n.square();
+    f.add_assign(&proof.t_hi.mul(zeta_2n));
+
+    let mut e = t_eval;
+
+    let committed = [
+        proof.a, proof.b, proof.c,
+        vk.q_l, vk.q_r, vk.q_o, vk.q_m, vk.q_c,
+        vk.sigma_1, vk.sigma_2, vk.sigma_3,
+        proof.z
+    ];
+
+    let mut power = E::Fr::one();
+    for (commitment, value) in committed.iter().zip(evaluations.iter()) {
+        power.mul_assign(&v);
+
+        f.add_assign(&commitment.mul(power));
+
+        let mut tmp = *value;
+        tmp.mul_assign(&power);
+        e.add_assign(&tmp);
+    }
+
+    // Both openings are checked at once, with
+    // e(W + u * W', [x]) = e(zeta * W + u * zeta * omega * W' + F - E + u * ([z] - z(zeta * omega)), 1)
+    let mut zeta_omega = zeta;
+    zeta_omega.mul_assign(&omega);
+
+    let mut lhs = proof.w_zeta.into_projective();
+    lhs.add_assign(&proof.w_zeta_omega.mul(u));
+
+    let mut rhs = proof.w_zeta.mul(zeta);
+    let mut tmp = u;
+    tmp.mul_assign(&zeta_omega);
+    rhs.add_assign(&proof.w_zeta_omega.mul(tmp));
+    rhs.add_assign(&f);
+    rhs.sub_assign(&E::G1Affine::one().mul(e));
+    let mut z = proof.z.into_projective();
+    z.sub_assign(&E::G1Affine::one().mul(proof.z_omega_eval));
+    z.mul_assign(u);
+    rhs.add_assign(&z);
+    rhs.negate();
+
+    Ok(E::final_exponentiation(
+        &E::miller_loop([
+            (&lhs.into_affine().prepare(), &vk.x_g2.prepare()),
+            (&rhs.into_affine().prepare(), &E::G2Affine::one().prepare())
+        ].iter())
+    ).unwrap() == E::Fqk::one())
+}
@@ -0,0 +1,45 @@
+//! Circuits shared by the tests of the proving systems.
+
+use pairing::Engine;
+
+use ff::Field;
+
+use ::{
+    SynthesisError,
+    Circuit,
+    ConstraintSystem
+};
+
+/// Proves knowledge of `a` and `b` such that `a * b = c`,
+/// where `c` is the public input
+pub struct MySillyCircuit<E: Engine> {
+    pub a: Option<E::Fr>,
+    pub b: Option<E::Fr>
+}
+
+impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(|| "c", || {
+            let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+            let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+            a.mul_assign(&b);
+            Ok(a)
+        })?;
+
+        cs.enforce(
+            || "a*b=c",
+            |lc| lc + a,
+            |lc| lc + b,
+            |lc| lc + c
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! Fiat-Shamir transcripts of the non-interactive
+//! arguments, which are hashed with BLAKE2b.
+
+use pairing::{
+    CurveAffine,
+    ExtensionField
+};
+
+use ff::{
+    PrimeField,
+    PrimeFieldRepr
+};
+
+use blake2_rfc::blake2b::Blake2b;
+
+/// Derives the challenges from the hash of
+/// all of the messages of the prover so far
+pub struct Transcript {
+    state: [u8; 64]
+}
+
+impl Transcript {
+    /// Starts a transcript, separated from the ones
+    /// of other arguments by the label
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Transcript {
+            state: [0; 64]
+        };
+
+        transcript.absorb(label);
+
+        transcript
+    }
+
+    pub fn absorb(&mut self, bytes: &[u8]) {
+        let mut h = Blake2b::new(64);
+        h.update(&self.state);
+        h.update(bytes);
+
+        self.state.copy_from_slice(h.finalize().as_bytes());
+    }
+
+    pub fn commit_point<G: CurveAffine>(&mut self, p: &G) {
+        self.absorb(p.into_uncompressed().as_ref());
+    }
+
+    pub fn commit_scalar<F: PrimeField>(&mut self, s: &F) {
+        let mut bytes = vec![];
+        s.into_repr().write_be(&mut bytes).expect("writing to a vector never fails");
+
+        self.absorb(&bytes);
+    }
+
+    /// Commits to an element of an extension field, such as the
+    /// target group of the pairing
+    pub fn commit_extension<F: ExtensionField>(&mut self, e: &F) {
+        let mut bytes = vec![];
+        e.write_be(&mut bytes).expect("writing to a vector never fails");
+
+        self.absorb(&bytes);
+    }
+
+    /// Returns a challenge depending on all of the messages, uniform
+    /// among the numbers of `F::CAPACITY` bits
+    pub fn challenge<F: PrimeField>(&mut self) -> F {
+        self.absorb(b"challenge");
+
+        let mut repr = F::Repr::default();
+        repr.read_le(&self.state[..]).expect("the state is longer than the representation");
+        repr.shr(repr.as_ref().len() as u32 * 64 - F::CAPACITY);
+
+        F::from_repr(repr).expect("the challenge is smaller than the modulus")
+    }
+}
@@ -549,6 +549,13 @@ fn prime_field_constants_and_sqrt(
     }
     inv = inv.wrapping_neg();
 
+    // The same constants for 32-bit limbs, since the Montgomery form
+    // is the same with R = 2^{limbs*64} = 2^{limbs*2*32}
+    let modulus_32: Vec<u32> = modulus.iter()
+        .flat_map(|&limb| vec![limb as u32, (limb >> 32) as u32])
+        .collect();
+    let inv_32 = inv as u32;
+
     (quote! {
         /// This is the modulus m of the prime field
         const MODULUS: #repr = #repr([#(#modulus,)*]);
@@ -569,6 +576,14 @@ fn prime_field_constants_and_sqrt(
         /// -(m^{-1} mod m) mod m
         const INV: u64 = #inv;
 
+        /// The modulus m in 32-bit limbs
+        #[cfg(any(test, target_arch = "wasm32"))]
+        const MODULUS_32: [u32; #limbs * 2] = [#(#modulus_32,)*];
+
+        /// -(m^{-1} mod m) mod 2^32
+        #[cfg(any(test, target_arch = "wasm32"))]
+        const INV_32: u32 = #inv_32;
+
         /// Multiplicative generator of `MODULUS` - 1 order, also quadratic
         /// nonresidue.
         const GENERATOR: #repr = #repr(#generator);
@@ -881,6 +896,7 @@ fn prime_field_impl(
                 }
             }
 
+            #[cfg(not(target_arch = "wasm32"))]
             fn into_repr(&self) -> #repr {
                 let mut r = *self;
                 r.mont_reduce(
@@ -890,6 +906,11 @@ fn prime_field_impl(
                 r.0
             }
 
+            #[cfg(target_arch = "wasm32")]
+            fn into_repr(&self) -> #repr {
+                self.into_repr_32()
+            }
+
             fn char() -> #repr {
                 MODULUS
             }
@@ -927,6 +948,7 @@ fn prime_field_impl(
             }
 
             #[inline]
+            #[cfg(not(target_arch = "wasm32"))]
             fn add_assign(&mut self, other: &#name) {
                 // This cannot exceed the backing capacity.
                 self.0.add_nocarry(&other.0);
@@ -935,6 +957,12 @@ fn prime_field_impl(
                 self.reduce();
             }
 
+            #[inline]
+            #[cfg(target_arch = "wasm32")]
+            fn add_assign(&mut self, other: &#name) {
+                self.add_assign_32(other);
+            }
+
             #[inline]
             fn double(&mut self) {
                 // This cannot exceed the backing capacity.
@@ -945,6 +973,7 @@ fn prime_field_impl(
             }
 
             #[inline]
+            #[cfg(not(target_arch = "wasm32"))]
             fn sub_assign(&mut self, other: &#name) {
                 // If `other` is larger than `self`, we'll need to add the modulus to self first.
                 if other.0 > self.0 {
@@ -954,6 +983,12 @@ fn prime_field_impl(
                 self.0.sub_noborrow(&other.0);
             }
 
+            #[inline]
+            #[cfg(target_arch = "wasm32")]
+            fn sub_assign(&mut self, other: &#name) {
+                self.sub_assign_32(other);
+            }
+
             #[inline]
             fn negate(&mut self) {
                 if !self.is_zero() {
@@ -1024,16 +1059,33 @@ fn prime_field_impl(
             }
 
             #[inline]
+            #[cfg(not(target_arch = "wasm32"))]
             fn mul_assign(&mut self, other: &#name)
             {
                 #multiply_impl
             }
 
             #[inline]
+            #[cfg(target_arch = "wasm32")]
+            fn mul_assign(&mut self, other: &#name)
+            {
+                self.mul_assign_32(other);
+            }
+
+            #[inline]
+            #[cfg(not(target_arch = "wasm32"))]
             fn square(&mut self)
             {
                 #squaring_impl
             }
+
+            #[inline]
+            #[cfg(target_arch = "wasm32")]
+            fn square(&mut self)
+            {
+                let tmp = *self;
+                self.mul_assign_32(&tmp);
+            }
         }
 
         impl std::default::Default for #name {
@@ -1059,7 +1111,163 @@ fn prime_field_impl(
                 }
             }
 
+            /// Splits the limbs of a representation into 32-bit limbs
+            #[inline(always)]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn to_limbs_32(repr: &#repr) -> [u32; #limbs * 2] {
+                let mut limbs = [0u32; #limbs * 2];
+                for i in 0..#limbs {
+                    limbs[2 * i] = repr.0[i] as u32;
+                    limbs[2 * i + 1] = (repr.0[i] >> 32) as u32;
+                }
+
+                limbs
+            }
+
+            /// Joins 32-bit limbs into the limbs of a representation
+            #[inline(always)]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn from_limbs_32(limbs: &[u32]) -> #repr {
+                let mut repr = #repr::default();
+                for i in 0..#limbs {
+                    repr.0[i] = u64::from(limbs[2 * i]) | (u64::from(limbs[2 * i + 1]) << 32);
+                }
+
+                repr
+            }
+
+            /// Subtracts the modulus from a value below 2m on 32-bit limbs
+            /// if the value is not in the field.
+            #[inline(always)]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn reduce_32(limbs: &mut [u32; #limbs * 2]) {
+                let mut tmp = [0u32; #limbs * 2];
+                let mut borrow = 0;
+                for i in 0..(#limbs * 2) {
+                    tmp[i] = ::ff::sbb_32(limbs[i], MODULUS_32[i], &mut borrow);
+                }
+
+                if borrow == 0 {
+                    *limbs = tmp;
+                }
+            }
+
+            /// Adds on 32-bit limbs.
+            #[inline]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn add_assign_32(&mut self, other: &#name)
+            {
+                let mut a = Self::to_limbs_32(&self.0);
+                let b = Self::to_limbs_32(&other.0);
+
+                // This cannot exceed the backing capacity.
+                let mut carry = 0;
+                for i in 0..(#limbs * 2) {
+                    a[i] = ::ff::adc_32(a[i], b[i], &mut carry);
+                }
+
+                Self::reduce_32(&mut a);
+                self.0 = Self::from_limbs_32(&a);
+            }
+
+            /// Subtracts on 32-bit limbs.
+            #[inline]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn sub_assign_32(&mut self, other: &#name)
+            {
+                let mut a = Self::to_limbs_32(&self.0);
+                let b = Self::to_limbs_32(&other.0);
+
+                let mut borrow = 0;
+                for i in 0..(#limbs * 2) {
+                    a[i] = ::ff::sbb_32(a[i], b[i], &mut borrow);
+                }
+
+                // If `other` was larger than `self`, the modulus wraps the difference back.
+                if borrow != 0 {
+                    let mut carry = 0;
+                    for i in 0..(#limbs * 2) {
+                        a[i] = ::ff::adc_32(a[i], MODULUS_32[i], &mut carry);
+                    }
+                }
+
+                self.0 = Self::from_limbs_32(&a);
+            }
+
+            /// Multiplies on 32-bit limbs, which is faster on targets that
+            /// lack the multiplication of 64-bit limbs into 128 bits, with
+            /// the Montgomery reduction interleaved with the multiplication
+            /// after every limb of `other`.
+            #[inline]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn mul_assign_32(&mut self, other: &#name)
+            {
+                let a = Self::to_limbs_32(&self.0);
+                let b = Self::to_limbs_32(&other.0);
+
+                // The result stays below 2m, which fits in the limbs,
+                // so the two extra limbs only hold carries
+                let mut t = [0u32; #limbs * 2 + 2];
+                for i in 0..(#limbs * 2) {
+                    let mut carry = 0;
+                    for j in 0..(#limbs * 2) {
+                        t[j] = ::ff::mac_with_carry_32(t[j], a[j], b[i], &mut carry);
+                    }
+                    t[#limbs * 2] = ::ff::adc_32(t[#limbs * 2], 0, &mut carry);
+                    t[#limbs * 2 + 1] = carry;
+
+                    let k = t[0].wrapping_mul(INV_32);
+                    let mut carry = 0;
+                    ::ff::mac_with_carry_32(t[0], k, MODULUS_32[0], &mut carry);
+                    for j in 1..(#limbs * 2) {
+                        t[j - 1] = ::ff::mac_with_carry_32(t[j], k, MODULUS_32[j], &mut carry);
+                    }
+                    t[#limbs * 2 - 1] = ::ff::adc_32(t[#limbs * 2], 0, &mut carry);
+                    t[#limbs * 2] = t[#limbs * 2 + 1] + carry;
+                }
+
+                let mut r = [0u32; #limbs * 2];
+                r.copy_from_slice(&t[0..(#limbs * 2)]);
+
+                Self::reduce_32(&mut r);
+                self.0 = Self::from_limbs_32(&r);
+            }
+
+            /// Divides by R on 32-bit limbs, with the Montgomery reduction
+            /// of a value which has only the lower half of the limbs.
+            #[inline]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn mont_reduce_32(&mut self)
+            {
+                let mut t = Self::to_limbs_32(&self.0);
+
+                // Every step divides t + k * m by 2^32, which stays below 2m
+                for _ in 0..(#limbs * 2) {
+                    let k = t[0].wrapping_mul(INV_32);
+                    let mut carry = 0;
+                    ::ff::mac_with_carry_32(t[0], k, MODULUS_32[0], &mut carry);
+                    for j in 1..(#limbs * 2) {
+                        t[j - 1] = ::ff::mac_with_carry_32(t[j], k, MODULUS_32[j], &mut carry);
+                    }
+                    t[#limbs * 2 - 1] = carry;
+                }
+
+                Self::reduce_32(&mut t);
+                self.0 = Self::from_limbs_32(&t);
+            }
+
+            /// Converts out of Montgomery form on 32-bit limbs.
+            #[inline]
+            #[cfg(any(test, target_arch = "wasm32"))]
+            fn into_repr_32(&self) -> #repr {
+                let mut r = *self;
+                r.mont_reduce_32();
+
+                r.0
+            }
+
             #[inline(always)]
+            #[cfg(not(target_arch = "wasm32"))]
             fn mont_reduce(
                 &mut self,
                 #mont_paramlist
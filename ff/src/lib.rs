@@ -393,4 +393,65 @@ mod arith_impl {
 
         tmp as u64
     }
+
+    /// Calculate a - b - borrow on 32-bit limbs, returning the result and
+    /// modifying the borrow value.
+    #[inline(always)]
+    pub fn sbb_32(a: u32, b: u32, borrow: &mut u32) -> u32 {
+        let tmp = (1u64 << 32) + u64::from(a) - u64::from(b) - u64::from(*borrow);
+
+        *borrow = if tmp >> 32 == 0 { 1 } else { 0 };
+
+        tmp as u32
+    }
+
+    /// Calculate a + b + carry on 32-bit limbs, returning the sum and
+    /// modifying the carry value.
+    #[inline(always)]
+    pub fn adc_32(a: u32, b: u32, carry: &mut u32) -> u32 {
+        let tmp = u64::from(a) + u64::from(b) + u64::from(*carry);
+
+        *carry = (tmp >> 32) as u32;
+
+        tmp as u32
+    }
+
+    /// Calculate a + (b * c) + carry on 32-bit limbs, returning the least
+    /// significant digit and setting carry to the most significant digit.
+    #[inline(always)]
+    pub fn mac_with_carry_32(a: u32, b: u32, c: u32, carry: &mut u32) -> u32 {
+        let tmp = u64::from(a) + u64::from(b) * u64::from(c) + u64::from(*carry);
+
+        *carry = (tmp >> 32) as u32;
+
+        tmp as u32
+    }
+}
+
+#[test]
+fn test_arith_32() {
+    let mut carry = 0;
+    assert_eq!(adc_32(0xffffffff, 0xffffffff, &mut carry), 0xfffffffe);
+    assert_eq!(carry, 1);
+    assert_eq!(adc_32(0xffffffff, 0, &mut carry), 0);
+    assert_eq!(carry, 1);
+
+    let mut borrow = 0;
+    assert_eq!(sbb_32(0, 1, &mut borrow), 0xffffffff);
+    assert_eq!(borrow, 1);
+    assert_eq!(sbb_32(1, 0, &mut borrow), 0);
+    assert_eq!(borrow, 0);
+
+    // The largest result still fits in two limbs
+    let mut carry = 0xffffffff;
+    assert_eq!(mac_with_carry_32(0xffffffff, 0xffffffff, 0xffffffff, &mut carry), 0xffffffff);
+    assert_eq!(carry, 0xffffffff);
+
+    for &(a, b, c) in [(3u32, 0x12345678u32, 0x9abcdef0u32), (0x80000000, 0x80000000, 2)].iter() {
+        let mut carry = 0;
+        let mut carry64 = 0;
+        let lo = mac_with_carry_32(a, b, c, &mut carry);
+        let expected = mac_with_carry(u64::from(a), u64::from(b), u64::from(c), &mut carry64);
+        assert_eq!(u64::from(lo) | (u64::from(carry) << 32), expected);
+    }
 }
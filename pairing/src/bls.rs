@@ -0,0 +1,453 @@
+//! BLS signatures, with the public keys in one group of an engine and the
+//! signatures in the other, which the messages are hashed to.
+//!
+//! Signatures of distinct messages can be aggregated into a single
+//! signature. Signatures of the same message can be aggregated too, and
+//! verified with the sum of the public keys, which is only secure if every
+//! public key has been checked with a proof of possession of its secret key.
+
+use ff::{Field, PrimeField, PrimeFieldRepr, ScalarEngine};
+use rand::{Rand, Rng};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+
+use {CurveAffine, CurveProjective, EncodedPoint, Engine, HashToCurve};
+
+/// The groups of the public keys and of the signatures.
+pub trait Variant: 'static {
+    type Engine: Engine;
+    type PublicKey: CurveAffine<Engine = Self::Engine, Scalar = <Self::Engine as ScalarEngine>::Fr>;
+    type Signature: CurveAffine<Engine = Self::Engine, Scalar = <Self::Engine as ScalarEngine>::Fr>;
+
+    /// The domain separation tag of the hashes of the messages.
+    const SIGNATURE_DST: &'static [u8];
+
+    /// The domain separation tag of the hashes of the proofs of possession.
+    const POP_DST: &'static [u8];
+
+    /// Hashes the message to the group of the signatures.
+    fn hash(msg: &[u8], dst: &[u8]) -> Self::Signature;
+
+    /// Returns true if the product of the pairings of the terms is one.
+    fn pairing_product_is_one(terms: &[(Self::PublicKey, Self::Signature)]) -> bool;
+}
+
+/// Public keys in G1 and signatures in G2, which minimizes the size of the
+/// public keys.
+#[derive(Debug)]
+pub struct MinPk<E: Engine>(PhantomData<E>);
+
+/// Signatures in G1 and public keys in G2, which minimizes the size of the
+/// signatures.
+#[derive(Debug)]
+pub struct MinSig<E: Engine>(PhantomData<E>);
+
+impl<E: Engine> Variant for MinPk<E> where E::G2: HashToCurve {
+    type Engine = E;
+    type PublicKey = E::G1Affine;
+    type Signature = E::G2Affine;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_G2_XMD:BLAKE2B_SVDW_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_G2_XMD:BLAKE2B_SVDW_RO_POP_";
+
+    fn hash(msg: &[u8], dst: &[u8]) -> E::G2Affine {
+        E::G2::hash_to_curve(msg, dst).into_affine()
+    }
+
+    fn pairing_product_is_one(terms: &[(E::G1Affine, E::G2Affine)]) -> bool {
+        let prepared: Vec<_> = terms.iter().map(|&(p, q)| (p.prepare(), q.prepare())).collect();
+        let refs: Vec<_> = prepared.iter().map(|&(ref p, ref q)| (p, q)).collect();
+
+        E::final_exponentiation(&E::miller_loop(refs.iter())) == Some(E::Fqk::one())
+    }
+}
+
+impl<E: Engine> Variant for MinSig<E> where E::G1: HashToCurve {
+    type Engine = E;
+    type PublicKey = E::G2Affine;
+    type Signature = E::G1Affine;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_G1_XMD:BLAKE2B_SVDW_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_G1_XMD:BLAKE2B_SVDW_RO_POP_";
+
+    fn hash(msg: &[u8], dst: &[u8]) -> E::G1Affine {
+        E::G1::hash_to_curve(msg, dst).into_affine()
+    }
+
+    fn pairing_product_is_one(terms: &[(E::G2Affine, E::G1Affine)]) -> bool {
+        let prepared: Vec<_> = terms.iter().map(|&(q, p)| (p.prepare(), q.prepare())).collect();
+        let refs: Vec<_> = prepared.iter().map(|&(ref p, ref q)| (p, q)).collect();
+
+        E::final_exponentiation(&E::miller_loop(refs.iter())) == Some(E::Fqk::one())
+    }
+}
+
+/// Reads a compressed point, which must be in the prime order subgroup
+/// and not the point at infinity.
+fn read_point<G: CurveAffine, R: Read>(mut reader: R) -> io::Result<G> {
+    let mut repr = G::Compressed::empty();
+    reader.read_exact(repr.as_mut())?;
+
+    let p = repr.into_affine().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    if p.is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "point at infinity"));
+    }
+
+    if !p.mul(G::Scalar::char()).is_zero() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "point is not in the prime order subgroup"));
+    }
+
+    Ok(p)
+}
+
+/// A secret key, which is a nonzero scalar.
+pub struct SecretKey<V: Variant> {
+    scalar: <V::Engine as ScalarEngine>::Fr,
+    _marker: PhantomData<V>
+}
+
+impl<V: Variant> fmt::Debug for SecretKey<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretKey(..)")
+    }
+}
+
+impl<V: Variant> Clone for SecretKey<V> {
+    fn clone(&self) -> Self {
+        SecretKey {
+            scalar: self.scalar,
+            _marker: PhantomData
+        }
+    }
+}
+
+impl<V: Variant> SecretKey<V> {
+    /// Generates a random secret key.
+    pub fn generate<R: Rng>(rng: &mut R) -> Self {
+        loop {
+            let scalar = <V::Engine as ScalarEngine>::Fr::rand(rng);
+
+            if !scalar.is_zero() {
+                return SecretKey {
+                    scalar: scalar,
+                    _marker: PhantomData
+                };
+            }
+        }
+    }
+
+    /// Returns the public key of the secret key.
+    pub fn public_key(&self) -> PublicKey<V> {
+        PublicKey(V::PublicKey::one().mul_secret(&self.scalar).into_affine())
+    }
+
+    /// Signs the message.
+    pub fn sign(&self, msg: &[u8]) -> Signature<V> {
+        Signature(V::hash(msg, V::SIGNATURE_DST).mul_secret(&self.scalar).into_affine())
+    }
+
+    /// Returns the proof of possession of the secret key, which is a
+    /// signature of the public key with its own domain separation tag.
+    pub fn prove_possession(&self) -> Signature<V> {
+        let public_key = self.public_key().0.into_compressed();
+
+        Signature(V::hash(public_key.as_ref(), V::POP_DST).mul_secret(&self.scalar).into_affine())
+    }
+
+    /// Writes the big-endian encoding of the scalar.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.scalar.into_repr().write_be(writer)
+    }
+
+    /// Reads the big-endian encoding of the scalar, which must be reduced
+    /// and nonzero.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let mut repr = <<V::Engine as ScalarEngine>::Fr as PrimeField>::Repr::default();
+        repr.read_be(reader)?;
+
+        let scalar = <V::Engine as ScalarEngine>::Fr::from_repr(repr)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if scalar.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "secret key is zero"));
+        }
+
+        Ok(SecretKey {
+            scalar: scalar,
+            _marker: PhantomData
+        })
+    }
+}
+
+/// A public key, which is the generator of its group times the secret key.
+pub struct PublicKey<V: Variant>(V::PublicKey);
+
+impl<V: Variant> fmt::Debug for PublicKey<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PublicKey({:?})", self.0)
+    }
+}
+
+impl<V: Variant> Clone for PublicKey<V> {
+    fn clone(&self) -> Self {
+        PublicKey(self.0)
+    }
+}
+
+impl<V: Variant> Copy for PublicKey<V> {}
+
+impl<V: Variant> PartialEq for PublicKey<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<V: Variant> Eq for PublicKey<V> {}
+
+impl<V: Variant> PublicKey<V> {
+    /// Returns the point of the public key.
+    pub fn as_affine(&self) -> &V::PublicKey {
+        &self.0
+    }
+
+    /// Returns the sum of the public keys, which verifies the aggregate of
+    /// their signatures of the same message. The public keys must have been
+    /// checked with proofs of possession, or the sum could be of a public
+    /// key whose secret key nobody knows.
+    pub fn aggregate(keys: &[PublicKey<V>]) -> PublicKey<V> {
+        let mut sum = <V::PublicKey as CurveAffine>::Projective::zero();
+        for key in keys {
+            sum.add_assign_mixed(&key.0);
+        }
+
+        PublicKey(sum.into_affine())
+    }
+
+    /// Verifies the signature of the message.
+    pub fn verify(&self, msg: &[u8], signature: &Signature<V>) -> bool {
+        self.verify_with_dst(msg, V::SIGNATURE_DST, signature)
+    }
+
+    /// Verifies the proof of possession of the secret key.
+    pub fn verify_possession(&self, proof: &Signature<V>) -> bool {
+        self.verify_with_dst(self.0.into_compressed().as_ref(), V::POP_DST, proof)
+    }
+
+    fn verify_with_dst(&self, msg: &[u8], dst: &[u8], signature: &Signature<V>) -> bool {
+        if self.0.is_zero() || signature.0.is_zero() {
+            return false;
+        }
+
+        // e(pk, H(m)) = e(g, sig)
+        let mut neg_g = V::PublicKey::one();
+        neg_g.negate();
+
+        V::pairing_product_is_one(&[(self.0, V::hash(msg, dst)), (neg_g, signature.0)])
+    }
+
+    /// Writes the compressed encoding of the point.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.0.into_compressed().as_ref())
+    }
+
+    /// Reads the compressed encoding of the point, which must be in the
+    /// prime order subgroup and not the point at infinity.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        read_point(reader).map(PublicKey)
+    }
+}
+
+/// A signature, which is the hash of the message times the secret key.
+pub struct Signature<V: Variant>(V::Signature);
+
+impl<V: Variant> fmt::Debug for Signature<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Signature({:?})", self.0)
+    }
+}
+
+impl<V: Variant> Clone for Signature<V> {
+    fn clone(&self) -> Self {
+        Signature(self.0)
+    }
+}
+
+impl<V: Variant> Copy for Signature<V> {}
+
+impl<V: Variant> PartialEq for Signature<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<V: Variant> Eq for Signature<V> {}
+
+impl<V: Variant> Signature<V> {
+    /// Returns the point of the signature.
+    pub fn as_affine(&self) -> &V::Signature {
+        &self.0
+    }
+
+    /// Returns the aggregate of the signatures, which is their sum.
+    pub fn aggregate(signatures: &[Signature<V>]) -> Signature<V> {
+        let mut sum = <V::Signature as CurveAffine>::Projective::zero();
+        for signature in signatures {
+            sum.add_assign_mixed(&signature.0);
+        }
+
+        Signature(sum.into_affine())
+    }
+
+    /// Verifies the aggregate of the signatures of the messages by the public
+    /// keys, which fails if the messages are not distinct, as the public keys
+    /// could then have been chosen to cancel each other out.
+    pub fn verify_aggregate(&self, keys: &[PublicKey<V>], msgs: &[&[u8]]) -> bool {
+        if keys.is_empty() || keys.len() != msgs.len() || self.0.is_zero() {
+            return false;
+        }
+
+        let mut sorted = msgs.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != msgs.len() {
+            return false;
+        }
+
+        // prod e(pk_i, H(m_i)) = e(g, sig)
+        let mut terms = Vec::with_capacity(keys.len() + 1);
+        for (key, msg) in keys.iter().zip(msgs.iter()) {
+            if key.0.is_zero() {
+                return false;
+            }
+
+            terms.push((key.0, V::hash(msg, V::SIGNATURE_DST)));
+        }
+
+        let mut neg_g = V::PublicKey::one();
+        neg_g.negate();
+        terms.push((neg_g, self.0));
+
+        V::pairing_product_is_one(&terms)
+    }
+
+    /// Verifies the aggregate of the signatures of the same message by the
+    /// public keys, which must have been checked with proofs of possession.
+    pub fn verify_same_message(&self, keys: &[PublicKey<V>], msg: &[u8]) -> bool {
+        !keys.is_empty() && PublicKey::aggregate(keys).verify(msg, self)
+    }
+
+    /// Writes the compressed encoding of the point.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.0.into_compressed().as_ref())
+    }
+
+    /// Reads the compressed encoding of the point, which must be in the
+    /// prime order subgroup and not the point at infinity.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        read_point(reader).map(Signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Bls12;
+    use bn256::Bn256;
+    use rand::{SeedableRng, XorShiftRng};
+
+    fn bls_tests<V: Variant>() {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let keys: Vec<SecretKey<V>> = (0..4).map(|_| SecretKey::generate(&mut rng)).collect();
+        let public_keys: Vec<_> = keys.iter().map(|key| key.public_key()).collect();
+        let msgs: Vec<&[u8]> = vec![b"", b"a", b"b", &[0; 300]];
+
+        for (key, public_key) in keys.iter().zip(public_keys.iter()) {
+            for msg in msgs.iter() {
+                let signature = key.sign(msg);
+                assert!(public_key.verify(msg, &signature));
+                assert!(!public_key.verify(b"c", &signature));
+                assert!(!public_keys[0].verify(msg, &key.sign(b"c")));
+            }
+
+            // The proofs of possession are not signatures of the public key
+            let proof = key.prove_possession();
+            assert!(public_key.verify_possession(&proof));
+            assert!(!public_keys[0].verify_possession(&keys[1].prove_possession()));
+
+            let mut encoded = vec![];
+            public_key.write(&mut encoded).unwrap();
+            assert!(!public_key.verify(&encoded, &proof));
+            assert!(!public_key.verify_possession(&key.sign(&encoded)));
+        }
+
+        // Aggregate signatures of distinct messages
+        let signatures: Vec<_> = keys.iter().zip(msgs.iter()).map(|(key, msg)| key.sign(msg)).collect();
+        let aggregate = Signature::aggregate(&signatures);
+        assert!(aggregate.verify_aggregate(&public_keys, &msgs));
+        assert!(!aggregate.verify_aggregate(&public_keys[1..], &msgs[1..]));
+        assert!(!aggregate.verify_aggregate(&public_keys, &[msgs[1], msgs[0], msgs[2], msgs[3]]));
+        assert!(!Signature::aggregate(&signatures[1..]).verify_aggregate(&public_keys, &msgs));
+
+        let repeated = Signature::aggregate(&[keys[0].sign(b"a"), keys[1].sign(b"a")]);
+        assert!(!repeated.verify_aggregate(&public_keys[0..2], &[b"a", b"a"]));
+
+        // Aggregate signatures of the same message
+        assert!(repeated.verify_same_message(&public_keys[0..2], b"a"));
+        assert!(!repeated.verify_same_message(&public_keys[0..2], b"b"));
+        assert!(!repeated.verify_same_message(&public_keys[1..3], b"a"));
+        assert!(!repeated.verify_same_message(&[], b"a"));
+
+        // The points are serialized with the compressed encodings
+        for public_key in public_keys.iter() {
+            let mut v = vec![];
+            public_key.write(&mut v).unwrap();
+            assert_eq!(v.len(), <V::PublicKey as CurveAffine>::Compressed::size());
+            assert_eq!(PublicKey::<V>::read(&v[..]).unwrap(), *public_key);
+        }
+
+        for signature in signatures.iter() {
+            let mut v = vec![];
+            signature.write(&mut v).unwrap();
+            assert_eq!(v.len(), <V::Signature as CurveAffine>::Compressed::size());
+            assert_eq!(Signature::<V>::read(&v[..]).unwrap(), *signature);
+        }
+
+        for key in keys.iter() {
+            let mut v = vec![];
+            key.write(&mut v).unwrap();
+            assert_eq!(SecretKey::<V>::read(&v[..]).unwrap().public_key(), key.public_key());
+        }
+
+        let mut v = vec![];
+        <V::Engine as ScalarEngine>::Fr::zero().into_repr().write_be(&mut v).unwrap();
+        assert!(SecretKey::<V>::read(&v[..]).is_err());
+
+        // The identity is neither a public key nor a signature
+        let zero = PublicKey::<V>(V::PublicKey::zero());
+        assert!(!zero.verify(b"a", &Signature(V::Signature::zero())));
+        assert!(!public_keys[0].verify(b"a", &Signature(V::Signature::zero())));
+    }
+
+    #[test]
+    fn bls12_min_pk() {
+        bls_tests::<MinPk<Bls12>>();
+    }
+
+    #[test]
+    fn bls12_min_sig() {
+        bls_tests::<MinSig<Bls12>>();
+    }
+
+    #[test]
+    fn bn256_min_pk() {
+        bls_tests::<MinPk<Bn256>>();
+    }
+
+    #[test]
+    fn bn256_min_sig() {
+        bls_tests::<MinSig<Bn256>>();
+    }
+}
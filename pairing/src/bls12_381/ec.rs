@@ -144,6 +144,17 @@ macro_rules! curve_impl {
             }
         }
 
+        impl $projective {
+            /// Returns `b` if `choice` is true and `a` otherwise, without branching.
+            fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+                $projective {
+                    x: $basefield::conditional_select(&a.x, &b.x, choice),
+                    y: $basefield::conditional_select(&a.y, &b.y, choice),
+                    z: $basefield::conditional_select(&a.z, &b.z, choice)
+                }
+            }
+        }
+
         impl CurveAffine for $affine {
             type Engine = Bls12;
             type Scalar = $scalarfield;
@@ -176,6 +187,10 @@ macro_rules! curve_impl {
                 self.mul_bits(bits)
             }
 
+            fn mul_secret(&self, scalar: &$scalarfield) -> $projective {
+                ::mul_secret_ladder(self, scalar, $projective::conditional_select)
+            }
+
             fn negate(&mut self) {
                 if !self.is_zero() {
                     self.y.negate();
@@ -194,6 +209,98 @@ macro_rules! curve_impl {
                 (*self).into()
             }
 
+            fn batch_add_assign(v: &mut [Self], other: &[Self]) {
+                assert_eq!(v.len(), other.len());
+
+                // The slopes are (y2 - y1) / (x2 - x1), or 3x^2 / 2y for
+                // doublings, with the denominators inverted all at once
+                // by Montgomery's trick
+                let mut denominators = Vec::with_capacity(v.len());
+                let mut prod = Vec::with_capacity(v.len());
+                let mut tmp = $basefield::one();
+                for (a, b) in v.iter().zip(other.iter()) {
+                    let d = if a.infinity || b.infinity {
+                        $basefield::one()
+                    } else if a.x == b.x {
+                        if a.y == b.y {
+                            // There are no points of order 2, so y is nonzero
+                            let mut d = a.y;
+                            d.double();
+                            d
+                        } else {
+                            $basefield::one()
+                        }
+                    } else {
+                        let mut d = b.x;
+                        d.sub_assign(&a.x);
+                        d
+                    };
+
+                    prod.push(tmp);
+                    tmp.mul_assign(&d);
+                    denominators.push(d);
+                }
+
+                tmp = tmp.inverse().unwrap(); // Guaranteed to be nonzero.
+
+                for ((a, b), (d, s)) in v.iter_mut()
+                                         .zip(other.iter())
+                                         .zip(denominators.into_iter().zip(prod.into_iter()))
+                                         .rev()
+                {
+                    // tmp := 1 / (d_0 ... d_i), so 1 / d_i = tmp * s
+                    let mut inverse = tmp;
+                    inverse.mul_assign(&s);
+                    tmp.mul_assign(&d);
+
+                    if b.infinity {
+                        continue;
+                    }
+
+                    if a.infinity {
+                        *a = *b;
+                        continue;
+                    }
+
+                    let lambda = if a.x == b.x {
+                        if a.y != b.y {
+                            *a = Self::zero();
+                            continue;
+                        }
+
+                        // 3x^2 / 2y
+                        let mut lambda = a.x;
+                        lambda.square();
+                        let mut tmp = lambda;
+                        tmp.double();
+                        lambda.add_assign(&tmp);
+                        lambda.mul_assign(&inverse);
+                        lambda
+                    } else {
+                        // (y2 - y1) / (x2 - x1)
+                        let mut lambda = b.y;
+                        lambda.sub_assign(&a.y);
+                        lambda.mul_assign(&inverse);
+                        lambda
+                    };
+
+                    // x3 = lambda^2 - x1 - x2
+                    let mut x3 = lambda;
+                    x3.square();
+                    x3.sub_assign(&a.x);
+                    x3.sub_assign(&b.x);
+
+                    // y3 = lambda * (x1 - x3) - y1
+                    let mut y3 = a.x;
+                    y3.sub_assign(&x3);
+                    y3.mul_assign(&lambda);
+                    y3.sub_assign(&a.y);
+
+                    a.x = x3;
+                    a.y = y3;
+                }
+            }
+
         }
 
         impl Rand for $projective {
@@ -621,12 +728,14 @@ macro_rules! curve_impl {
 }
 
 pub mod g1 {
+    use super::super::fq::SVDW_G1;
     use super::super::{Bls12, Fq, Fq12, FqRepr, Fr, FrRepr};
     use super::g2::G2Affine;
     use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+    use hash_to_curve::{hash_to_field, MapField};
     use rand::{Rand, Rng};
     use std::fmt;
-    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, HashToCurve};
 
     curve_impl!(
         "G1",
@@ -919,6 +1028,32 @@ pub mod g1 {
         }
     }
 
+    impl G1Affine {
+        /// Maps the element to a point of the curve with the Shallue-van de
+        /// Woestijne map, for which z = -3 satisfies the conditions. The point
+        /// is not guaranteed to be in the prime order subgroup.
+        fn map_to_curve(u: &Fq) -> G1Affine {
+            let (x, y) = SVDW_G1.map_to_curve(u);
+
+            G1Affine {
+                x: x,
+                y: y,
+                infinity: false
+            }
+        }
+    }
+
+    impl HashToCurve for G1 {
+        fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G1 {
+            let u = hash_to_field::<Fq>(msg, dst, 2);
+
+            let mut p = G1Affine::map_to_curve(&u[0]).into_projective();
+            p.add_assign_mixed(&G1Affine::map_to_curve(&u[1]));
+
+            p.into_affine().scale_by_cofactor()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct G1Prepared(pub(crate) G1Affine);
 
@@ -1264,15 +1399,64 @@ pub mod g1 {
         ::tests::curve::curve_tests::<G1>();
         ::tests::curve::random_transformation_tests_with_cofactor::<G1>();
     }
+
+    #[test]
+    fn g1_hash_to_curve() {
+        ::hash_to_curve::tests::map_to_curve_tests(&SVDW_G1);
+        ::tests::curve::hash_to_curve_tests::<G1>();
+    }
+
+    #[test]
+    fn g1_hash_to_curve_known_answers() {
+        let point = |x: &str, y: &str| G1Affine {
+            x: Fq::from_str(x).unwrap(),
+            y: Fq::from_str(y).unwrap(),
+            infinity: false,
+        };
+
+        // Computed with an independent implementation of the hash
+        let dst = b"BLS12381G1_XMD:BLAKE2b_SVDW_RO_TESTGEN";
+        assert_eq!(
+            G1::hash_to_curve(b"", dst).into_affine(),
+            point(
+                "2642279835820746741531238037105035487046063110273461658345576111803642083042390127433373556789627030308187608107130",
+                "2279858102560030628914432278089076803876503455109552064988121100749177710852825267199422861705378495059272636793062"
+            )
+        );
+        assert_eq!(
+            G1::hash_to_curve(b"abc", dst).into_affine(),
+            point(
+                "1128590694148829330088341942385776214453997556508027507074639497847995472912131335565179099063231237983312477385827",
+                "1534522375206411869409596192609546469561060801343191855477431531763886459235783244907837432798614232991537134295804"
+            )
+        );
+
+        assert_eq!(
+            G1Affine::map_to_curve(&Fq::zero()),
+            point(
+                "2001204777610833696708894912867952078278441409969503942666029068062015825245418932221343814564507832018947136279895",
+                "529116524740332847669714100785572711082909696439335196643846704187339954652678890733874953759233964593198896612660"
+            )
+        );
+        assert_eq!(
+            G1Affine::map_to_curve(&Fq::one()),
+            point(
+                "269525312557182531784698698608080631502142927326328939727057965900071998348885456999397603678153261318263930358673",
+                "3158558217244490072999688690581058868577013901778160136681348263671249909465546136169462108949165603340106547112611"
+            )
+        );
+    }
 }
 
 pub mod g2 {
+    use super::super::fq::SVDW_G2;
     use super::super::{Bls12, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
     use super::g1::G1Affine;
     use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+    use hash_to_curve::{hash_to_field, MapField};
     use rand::{Rand, Rng};
     use std::fmt;
-    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, HashToCurve};
 
     curve_impl!(
         "G2",
@@ -1609,6 +1793,32 @@ pub mod g2 {
         }
     }
 
+    impl G2Affine {
+        /// Maps the element to a point of the twist with the Shallue-van de
+        /// Woestijne map, for which z = -1 satisfies the conditions. The point
+        /// is not guaranteed to be in the prime order subgroup.
+        fn map_to_curve(u: &Fq2) -> G2Affine {
+            let (x, y) = SVDW_G2.map_to_curve(u);
+
+            G2Affine {
+                x: x,
+                y: y,
+                infinity: false
+            }
+        }
+    }
+
+    impl HashToCurve for G2 {
+        fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G2 {
+            let u = hash_to_field::<Fq2>(msg, dst, 2);
+
+            let mut p = G2Affine::map_to_curve(&u[0]).into_projective();
+            p.add_assign_mixed(&G2Affine::map_to_curve(&u[1]));
+
+            p.into_affine().scale_by_cofactor()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct G2Prepared {
         pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
@@ -2018,6 +2228,81 @@ pub mod g2 {
         ::tests::curve::curve_tests::<G2>();
         ::tests::curve::random_transformation_tests_with_cofactor::<G2>();
     }
+
+    #[test]
+    fn g2_hash_to_curve() {
+        ::hash_to_curve::tests::map_to_curve_tests(&SVDW_G2);
+        ::tests::curve::hash_to_curve_tests::<G2>();
+    }
+
+    #[test]
+    fn g2_hash_to_curve_known_answers() {
+        let fq2 = |c0: &str, c1: &str| Fq2 {
+            c0: Fq::from_str(c0).unwrap(),
+            c1: Fq::from_str(c1).unwrap(),
+        };
+        let point = |x: Fq2, y: Fq2| G2Affine {
+            x: x,
+            y: y,
+            infinity: false,
+        };
+
+        // Computed with an independent implementation of the hash
+        let dst = b"BLS12381G2_XMD:BLAKE2b_SVDW_RO_TESTGEN";
+        assert_eq!(
+            G2::hash_to_curve(b"", dst).into_affine(),
+            point(
+                fq2(
+                    "2834498720449840613707392637708948438731879568851960670274319191997100115774843291785016853616479051954479705917279",
+                    "3285204803680157333207756275667172828797752597488566866808482298791646026603777312484859668130597362716525691267703"
+                ),
+                fq2(
+                    "342560264901268350894504142619695015159823085881782495064576211950675634237052612512122178298818272087502526129068",
+                    "3455368166159932220737733922980661538426515137373835712304953085530725069726210074492469045903029518196462908131930"
+                )
+            )
+        );
+        assert_eq!(
+            G2::hash_to_curve(b"abc", dst).into_affine(),
+            point(
+                fq2(
+                    "1529511236786279870444978561983474640272845627660426210083950174194656407057435270448195600448322529054114284196370",
+                    "2579620397131911110972129869503243403559112203727516627766366609129290817972969298456201417202540232239220107111126"
+                ),
+                fq2(
+                    "1863944765438877140703815837134363526701559557545318480036338114457331265346330072838892893651217267707679423396234",
+                    "566665021306293089643684733125916709957753617517269670442078331595372187995321027389935304598703600644959421380537"
+                )
+            )
+        );
+
+        assert_eq!(
+            G2Affine::map_to_curve(&Fq2::zero()),
+            point(
+                fq2(
+                    "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559782",
+                    "1334136518407222464472596608578634718852294273313002628444019378708010550163612621480895876376338554679298090853257"
+                ),
+                fq2(
+                    "2668273036814444934940370836000231088621679625634078412459183017317367047517340925745697545767386974048773265060284",
+                    "2668273036814444946049082306209248265320995448501978671311597381252213949663731749727640632824055252739395507745191"
+                )
+            )
+        );
+        assert_eq!(
+            G2Affine::map_to_curve(&fq2("1", "1")),
+            point(
+                fq2(
+                    "1907030435135029757834869898191310318386863538489347617889515837962822952925281068076807678824290066704447364035573",
+                    "2778143103036216191229382197856423328451889297230661451480933105958766282370871580272844460808089937432432159865016"
+                ),
+                fq2(
+                    "855887947670576860315743671822933126930226780647624899591112481229519967583165568193380602522664985215908433522371",
+                    "2231722196373200811788064487769231999912457706696971076908014425076774973116847986222221250760866530721175528556298"
+                )
+            )
+        );
+    }
 }
 
 pub use self::g1::*;
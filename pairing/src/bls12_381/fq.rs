@@ -1,5 +1,9 @@
 use super::fq2::Fq2;
 use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+use hash_to_curve::{
+    MapField, Svdw, eq_limbs, prime_field_from_uniform_bytes, prime_field_inv0, prime_field_is_square,
+    prime_field_sgn0, prime_field_sqrt_unchecked, select_limbs
+};
 
 // B coefficient of BLS12-381 curve, 4.
 pub const B_COEFF: Fq = Fq(FqRepr([
@@ -443,11 +447,190 @@ pub const NEGATIVE_ONE: Fq = Fq(FqRepr([
     0x40ab3263eff0206,
 ]));
 
+// Constants of the Shallue-van de Woestijne maps to G1 with z = -3 and to
+// the twist of G2 with z = -1, c1 = g(z), c2 = -z / 2, c3 = sqrt(-g(z) * 3z^2)
+// and c4 = -4g(z) / 3z^2, where g(x) = x^3 + b
+pub(crate) const SVDW_G1: Svdw<Fq> = Svdw {
+    z: Fq(FqRepr([
+        0xcbe1fffffff6000a,
+        0x9827ffd8c7d7fff7,
+        0x17b8aedce8bcd83b,
+        0xc5fad9948998326e,
+        0xcd3da75be2de413d,
+        0xc201972bcfd0614,
+    ])),
+    b: B_COEFF,
+    c1: Fq(FqRepr([
+        0xed1cffffffb455a1,
+        0x3283fed73d7bffc1,
+        0x804ac4babeea4207,
+        0x15c7f6e3eeff9fb8,
+        0x9985b69dac1a42fe,
+        0xef2e2b0fc697ad0,
+    ])),
+    c2: Fq(FqRepr([
+        0xd40e00000004aaa6,
+        0x529800124d680003,
+        0x5b547b3282528a06,
+        0x8179debaaeb8f988,
+        0xe47cd40851dc8c38,
+        0x13f10530db01638f,
+    ])),
+    c3: Fq(FqRepr([
+        0xa79d7ec1bb728f69,
+        0xde71ffc7bead6157,
+        0xfaee511a2882c350,
+        0x92d5303a3823f741,
+        0x70a8555ff782f798,
+        0x181220a203579aec,
+    ])),
+    c4: Fq(FqRepr([
+        0xf33dda12f68fe05a,
+        0x124b8e6490134267,
+        0x75b3ebbc407665ce,
+        0x260fd93e25abd98a,
+        0xd4054c95e27eb430,
+        0x39067234fadfb1f,
+    ])),
+};
+
+pub(crate) const SVDW_G2: Svdw<Fq2> = Svdw {
+    z: Fq2 {
+        c0: Fq(FqRepr([
+            0x43f5fffffffcaaae,
+            0x32b7fff2ed47fffd,
+            0x7e83a49a2e99d69,
+            0xeca8f3318332bb7a,
+            0xef148d1ea0f4c069,
+            0x40ab3263eff0206,
+        ])),
+        c1: Fq(FqRepr([
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+        ])),
+    },
+    b: Fq2 {
+        c0: B_COEFF,
+        c1: B_COEFF,
+    },
+    c1: Fq2 {
+        c0: Fq(FqRepr([
+            0xee1d00000009aaa1,
+            0x86840025e97c0007,
+            0x4f7823c40df41de8,
+            0x9e7c71f069ece051,
+            0x7dde005a606d6b99,
+            0xde0f8777c82e085,
+        ])),
+        c1: Fq(FqRepr([
+            0xaa270000000cfff3,
+            0x53cc0032fc34000a,
+            0x478fe97a6b0a807f,
+            0xb1d37ebee6ba24d7,
+            0x8ec9733bbf78ab2f,
+            0x9d645513d83de7e,
+        ])),
+    },
+    c2: Fq2 {
+        c0: Fq(FqRepr([
+            0x1804000000015554,
+            0x855000053ab00001,
+            0x633cb57c253c276f,
+            0x6e22d1ec31ebb502,
+            0xd3916126f2d14ca2,
+            0x17fbb8571a006596,
+        ])),
+        c1: Fq(FqRepr([
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+            0x0,
+        ])),
+    },
+    c3: Fq2 {
+        c0: Fq(FqRepr([
+            0x3bd8d86de6303e44,
+            0x697376c820a968ae,
+            0x4ad2b457d282a50d,
+            0x3056d97ecd8e9378,
+            0xfab1c35c3fd6f0e7,
+            0xc592c60126f8172,
+        ])),
+        c1: Fq(FqRepr([
+            0x1dec6c36f3181f22,
+            0xb4b9bb641054b457,
+            0x25695a2be9415286,
+            0x982b6cbf66c749bc,
+            0x7d58e1ae1feb7873,
+            0x62c96300937c0b9,
+        ])),
+    },
+    c4: Fq2 {
+        c0: Fq(FqRepr([
+            0xfd7fffffff2aab8,
+            0xcadfffcbb51ffff5,
+            0x1fa0e9268ba675a4,
+            0xb2a3ccc60ccaede8,
+            0xbc52347a83d301a7,
+            0x102acc98fbfc081b,
+        ])),
+        c1: Fq(FqRepr([
+            0xbfcaaaaaaa98e3a0,
+            0xe7fffba46d55546,
+            0xd4d68c3364ddf231,
+            0x43851108110e928a,
+            0x506d9b4e0519578a,
+            0x158e66214ffab57a,
+        ])),
+    },
+};
+
 #[derive(PrimeField)]
 #[PrimeFieldModulus = "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787"]
 #[PrimeFieldGenerator = "2"]
 pub struct Fq(FqRepr);
 
+impl MapField for Fq {
+    const DEGREE: usize = 1;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        prime_field_from_uniform_bytes(bytes)
+    }
+
+    fn sgn0(&self) -> bool {
+        prime_field_sgn0(self)
+    }
+
+    fn inv0(&self) -> Self {
+        prime_field_inv0(self)
+    }
+
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mut result = *a;
+        select_limbs(&mut (result.0).0, &(a.0).0, &(b.0).0, choice);
+
+        result
+    }
+
+    fn ct_eq(&self, other: &Self) -> bool {
+        eq_limbs(&(self.0).0, &(other.0).0)
+    }
+
+    fn is_square(&self) -> bool {
+        prime_field_is_square(self)
+    }
+
+    fn sqrt_unchecked(&self) -> Self {
+        prime_field_sqrt_unchecked(self)
+    }
+}
+
 #[test]
 fn test_b_coeff() {
     assert_eq!(Fq::from_repr(FqRepr::from(4)).unwrap(), B_COEFF);
@@ -1948,6 +2131,52 @@ fn test_fq_squaring() {
     }
 }
 
+#[test]
+fn test_fq_limbs_32() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut neg_one = Fq::one();
+    neg_one.negate();
+
+    let mut elements = vec![Fq::zero(), Fq::one(), neg_one];
+    elements.extend((0..1000).map(|_| Fq::rand(&mut rng)));
+
+    // The arithmetic on 32-bit limbs agrees with the one on 64-bit limbs
+    for a in elements.iter() {
+        for b in elements.iter().take(100) {
+            let mut tmp1 = *a;
+            tmp1.add_assign(b);
+            let mut tmp2 = *a;
+            tmp2.add_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.sub_assign(b);
+            let mut tmp2 = *a;
+            tmp2.sub_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.mul_assign(b);
+            let mut tmp2 = *a;
+            tmp2.mul_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+        }
+
+        let mut tmp = *a;
+        tmp.square();
+        let mut tmp2 = *a;
+        tmp2.mul_assign_32(a);
+
+        assert_eq!(tmp, tmp2);
+
+        assert_eq!(a.into_repr(), a.into_repr_32());
+    }
+}
+
 #[test]
 fn test_fq_inverse() {
     assert!(Fq::zero().inverse().is_none());
@@ -1,8 +1,11 @@
 use super::fq::{FROBENIUS_COEFF_FQ2_C1, Fq, NEGATIVE_ONE};
-use ff::{Field, SqrtField};
+use ff::{Field, PrimeField, PrimeFieldRepr, SqrtField};
+use hash_to_curve::{MapField, UNIFORM_BYTES};
 use rand::{Rand, Rng};
 
 use std::cmp::Ordering;
+use std::io::{self, Write};
+use ExtensionField;
 
 /// An element of Fq2, represented by c0 + c1 * u.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -17,6 +20,15 @@ impl ::std::fmt::Display for Fq2 {
     }
 }
 
+impl ExtensionField for Fq2 {
+    fn write_be<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.c0.into_repr().write_be(&mut writer)?;
+        self.c1.into_repr().write_be(&mut writer)?;
+
+        Ok(())
+    }
+}
+
 /// `Fq2` elements are ordered lexicographically.
 impl Ord for Fq2 {
     #[inline(always)]
@@ -159,6 +171,96 @@ impl Field for Fq2 {
     }
 }
 
+impl MapField for Fq2 {
+    const DEGREE: usize = 2;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Fq2 {
+            c0: Fq::from_uniform_bytes(&bytes[0..UNIFORM_BYTES]),
+            c1: Fq::from_uniform_bytes(&bytes[UNIFORM_BYTES..])
+        }
+    }
+
+    fn sgn0(&self) -> bool {
+        self.c0.sgn0() | (self.c0.ct_eq(&Fq::zero()) & self.c1.sgn0())
+    }
+
+    fn inv0(&self) -> Self {
+        // The inverse is the conjugate divided by the norm
+        let norm_inv = self.norm().inv0();
+
+        let mut result = *self;
+        result.c0.mul_assign(&norm_inv);
+        result.c1.mul_assign(&norm_inv);
+        result.c1.negate();
+
+        result
+    }
+
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        Fq2 {
+            c0: Fq::conditional_select(&a.c0, &b.c0, choice),
+            c1: Fq::conditional_select(&a.c1, &b.c1, choice)
+        }
+    }
+
+    fn ct_eq(&self, other: &Self) -> bool {
+        self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
+    }
+
+    fn is_square(&self) -> bool {
+        self.norm().is_square()
+    }
+
+    fn sqrt_unchecked(&self) -> Self {
+        // Algorithm 9, https://eprint.iacr.org/2012/685.pdf, which computes
+        // the roots of both cases of alpha and selects one of them
+        let mut a1 = self.pow(Q_MINUS_3_OVER_4);
+        let mut alpha = a1;
+        alpha.square();
+        alpha.mul_assign(self);
+        a1.mul_assign(self);
+
+        let neg1 = Fq2 {
+            c0: NEGATIVE_ONE,
+            c1: Fq::zero(),
+        };
+
+        let mut i_a1 = a1;
+        i_a1.mul_assign(&Fq2 {
+            c0: Fq::zero(),
+            c1: Fq::one(),
+        });
+
+        let mut b = alpha;
+        b.add_assign(&Fq2::one());
+        let mut b = b.pow(Q_MINUS_1_OVER_2);
+        b.mul_assign(&a1);
+
+        Fq2::conditional_select(&b, &i_a1, alpha.ct_eq(&neg1))
+    }
+}
+
+// (q - 3) / 4
+const Q_MINUS_3_OVER_4: [u64; 6] = [
+    0xee7fbfffffffeaaa,
+    0x7aaffffac54ffff,
+    0xd9cc34a83dac3d89,
+    0xd91dd2e13ce144af,
+    0x92c6e9ed90d2eb35,
+    0x680447a8e5ff9a6,
+];
+
+// (q - 1) / 2
+const Q_MINUS_1_OVER_2: [u64; 6] = [
+    0xdcff7fffffffd555,
+    0xf55ffff58a9ffff,
+    0xb39869507b587b12,
+    0xb23ba5c279c2895f,
+    0x258dd3db21a5d66b,
+    0xd0088f51cbff34d,
+];
+
 impl SqrtField for Fq2 {
     fn legendre(&self) -> ::ff::LegendreSymbol {
         self.norm().legendre()
@@ -171,14 +273,7 @@ impl SqrtField for Fq2 {
             Some(Self::zero())
         } else {
             // a1 = self^((q - 3) / 4)
-            let mut a1 = self.pow([
-                0xee7fbfffffffeaaa,
-                0x7aaffffac54ffff,
-                0xd9cc34a83dac3d89,
-                0xd91dd2e13ce144af,
-                0x92c6e9ed90d2eb35,
-                0x680447a8e5ff9a6,
-            ]);
+            let mut a1 = self.pow(Q_MINUS_3_OVER_4);
             let mut alpha = a1;
             alpha.square();
             alpha.mul_assign(self);
@@ -204,14 +299,7 @@ impl SqrtField for Fq2 {
                 } else {
                     alpha.add_assign(&Fq2::one());
                     // alpha = alpha^((q - 1) / 2)
-                    alpha = alpha.pow([
-                        0xdcff7fffffffd555,
-                        0xf55ffff58a9ffff,
-                        0xb39869507b587b12,
-                        0xb23ba5c279c2895f,
-                        0x258dd3db21a5d66b,
-                        0xd0088f51cbff34d,
-                    ]);
+                    alpha = alpha.pow(Q_MINUS_1_OVER_2);
                     a1.mul_assign(&alpha);
                 }
 
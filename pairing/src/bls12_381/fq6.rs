@@ -2,6 +2,8 @@ use super::fq::{FROBENIUS_COEFF_FQ6_C1, FROBENIUS_COEFF_FQ6_C2};
 use super::fq2::Fq2;
 use ff::Field;
 use rand::{Rand, Rng};
+use std::io::{self, Write};
+use ExtensionField;
 
 /// An element of Fq6, represented by c0 + c1 * v + c2 * v^(2).
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -17,6 +19,16 @@ impl ::std::fmt::Display for Fq6 {
     }
 }
 
+impl ExtensionField for Fq6 {
+    fn write_be<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.c0.write_be(&mut writer)?;
+        self.c1.write_be(&mut writer)?;
+        self.c2.write_be(&mut writer)?;
+
+        Ok(())
+    }
+}
+
 impl Rand for Fq6 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
         Fq6 {
@@ -94,6 +94,50 @@ macro_rules! curve_impl {
                 res
             }
 
+            /// Splits the reduced scalar into k1 and k2 of half of the bits with
+            /// k1 * p1 + k2 * p2 = scalar * self, for p1 and p2 the element and
+            /// its endomorphism negated with the signs of the decomposition.
+            fn split(&self, scalar: FrRepr) -> [($affine, FrRepr); 2] {
+                let k = SCALAR_DECOMPOSITION.decompose(scalar);
+
+                let mut p1 = *self;
+                if k[0].1 {
+                    p1.negate();
+                }
+
+                let mut p2 = self.endomorphism();
+                if k[1].1 {
+                    p2.negate();
+                }
+
+                [(p1, k[0].0), (p2, k[1].0)]
+            }
+
+            /// Multiplies the element by the reduced scalar with the endomorphism
+            /// of the group, which halves the doublings.
+            fn mul_split(&self, by: FrRepr) -> $projective {
+                let terms = self.split(by);
+                let (p1, k1) = terms[0];
+                let (p2, k2) = terms[1];
+
+                let mut p12 = p1.into_projective();
+                p12.add_assign_mixed(&p2);
+
+                let mut res = $projective::zero();
+                for (b1, b2) in BitIterator::new(k1).zip(BitIterator::new(k2)) {
+                    res.double();
+
+                    match (b1, b2) {
+                        (true, true) => res.add_assign(&p12),
+                        (true, false) => res.add_assign_mixed(&p1),
+                        (false, true) => res.add_assign_mixed(&p2),
+                        (false, false) => {}
+                    }
+                }
+
+                res
+            }
+
             /// Attempts to construct an affine point given an x-coordinate. The
             /// point is not guaranteed to be in the prime order subgroup.
             ///
@@ -141,6 +185,17 @@ macro_rules! curve_impl {
 
         }
 
+        impl $projective {
+            /// Returns `b` if `choice` is true and `a` otherwise, without branching.
+            fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+                $projective {
+                    x: $basefield::conditional_select(&a.x, &b.x, choice),
+                    y: $basefield::conditional_select(&a.y, &b.y, choice),
+                    z: $basefield::conditional_select(&a.z, &b.z, choice)
+                }
+            }
+        }
+
         impl CurveAffine for $affine {
             type Engine = Bn256;
             type Scalar = $scalarfield;
@@ -169,8 +224,27 @@ macro_rules! curve_impl {
             }
 
             fn mul<S: Into<<Self::Scalar as PrimeField>::Repr>>(&self, by: S) -> $projective {
-                let bits = BitIterator::new(by.into());
-                self.mul_bits(bits)
+                let by = by.into();
+                if by < $scalarfield::char() {
+                    self.mul_split(by)
+                } else {
+                    self.mul_bits(BitIterator::new(by))
+                }
+            }
+
+            fn split_mul(
+                &self,
+                scalar: &<Self::Scalar as PrimeField>::Repr
+            ) -> Option<[(Self, <Self::Scalar as PrimeField>::Repr); 2]> {
+                if *scalar >= $scalarfield::char() {
+                    return None;
+                }
+
+                Some(self.split(*scalar))
+            }
+
+            fn mul_secret(&self, scalar: &$scalarfield) -> $projective {
+                ::mul_secret_ladder(self, scalar, $projective::conditional_select)
             }
 
             fn negate(&mut self) {
@@ -191,6 +265,98 @@ macro_rules! curve_impl {
                 (*self).into()
             }
 
+            fn batch_add_assign(v: &mut [Self], other: &[Self]) {
+                assert_eq!(v.len(), other.len());
+
+                // The slopes are (y2 - y1) / (x2 - x1), or 3x^2 / 2y for
+                // doublings, with the denominators inverted all at once
+                // by Montgomery's trick
+                let mut denominators = Vec::with_capacity(v.len());
+                let mut prod = Vec::with_capacity(v.len());
+                let mut tmp = $basefield::one();
+                for (a, b) in v.iter().zip(other.iter()) {
+                    let d = if a.infinity || b.infinity {
+                        $basefield::one()
+                    } else if a.x == b.x {
+                        if a.y == b.y {
+                            // There are no points of order 2, so y is nonzero
+                            let mut d = a.y;
+                            d.double();
+                            d
+                        } else {
+                            $basefield::one()
+                        }
+                    } else {
+                        let mut d = b.x;
+                        d.sub_assign(&a.x);
+                        d
+                    };
+
+                    prod.push(tmp);
+                    tmp.mul_assign(&d);
+                    denominators.push(d);
+                }
+
+                tmp = tmp.inverse().unwrap(); // Guaranteed to be nonzero.
+
+                for ((a, b), (d, s)) in v.iter_mut()
+                                         .zip(other.iter())
+                                         .zip(denominators.into_iter().zip(prod.into_iter()))
+                                         .rev()
+                {
+                    // tmp := 1 / (d_0 ... d_i), so 1 / d_i = tmp * s
+                    let mut inverse = tmp;
+                    inverse.mul_assign(&s);
+                    tmp.mul_assign(&d);
+
+                    if b.infinity {
+                        continue;
+                    }
+
+                    if a.infinity {
+                        *a = *b;
+                        continue;
+                    }
+
+                    let lambda = if a.x == b.x {
+                        if a.y != b.y {
+                            *a = Self::zero();
+                            continue;
+                        }
+
+                        // 3x^2 / 2y
+                        let mut lambda = a.x;
+                        lambda.square();
+                        let mut tmp = lambda;
+                        tmp.double();
+                        lambda.add_assign(&tmp);
+                        lambda.mul_assign(&inverse);
+                        lambda
+                    } else {
+                        // (y2 - y1) / (x2 - x1)
+                        let mut lambda = b.y;
+                        lambda.sub_assign(&a.y);
+                        lambda.mul_assign(&inverse);
+                        lambda
+                    };
+
+                    // x3 = lambda^2 - x1 - x2
+                    let mut x3 = lambda;
+                    x3.square();
+                    x3.sub_assign(&a.x);
+                    x3.sub_assign(&b.x);
+
+                    // y3 = lambda * (x1 - x3) - y1
+                    let mut y3 = a.x;
+                    y3.sub_assign(&x3);
+                    y3.mul_assign(&lambda);
+                    y3.sub_assign(&a.y);
+
+                    a.x = x3;
+                    a.y = y3;
+                }
+            }
+
         }
 
         // impl Rand for $projective {
@@ -536,11 +702,23 @@ macro_rules! curve_impl {
             }
 
             fn mul_assign<S: Into<<Self::Scalar as PrimeField>::Repr>>(&mut self, other: S) {
+                let other = other.into();
+
+                // Reduced scalars are split with the endomorphism of the group,
+                // which halves the doublings. The others, such as the order of
+                // the group for checking the membership of the subgroup and the
+                // cofactor of the twist, are multiplied bit by bit, which holds
+                // outside of the subgroup.
+                if other < $scalarfield::char() {
+                    *self = self.into_affine().mul_split(other);
+                    return;
+                }
+
                 let mut res = Self::zero();
 
                 let mut found_one = false;
 
-                for i in BitIterator::new(other.into())
+                for i in BitIterator::new(other)
                 {
                     if found_one {
                         res.double();
@@ -624,13 +802,100 @@ macro_rules! curve_impl {
     }
 }
 
+use super::{Fr, FrRepr};
+use ff::{Field, PrimeField};
+
+/// The decomposition of the scalars k = k1 + k2 * lambda modulo r, for the
+/// eigenvalue lambda of an endomorphism of a group, with a reduced basis
+/// (a1, b1), (a2, b2) of the lattice of the (x, y) with x + y * lambda = 0
+/// modulo r, whose coordinates are given modulo r, and the approximations
+/// g1 = round(2^256 * b2 / d) and g2 = round(-2^256 * b1 / d) for its
+/// determinant d, which are nonnegative.
+struct ScalarDecomposition {
+    a1: FrRepr,
+    b1: FrRepr,
+    a2: FrRepr,
+    b2: FrRepr,
+    g1: [u64; 3],
+    g2: [u64; 3],
+}
+
+impl ScalarDecomposition {
+    /// Returns k1 and k2 with k = k1 + k2 * lambda modulo r by their absolute
+    /// values, which are less than 2^128, and whether they are negative. The
+    /// scalar must be reduced.
+    fn decompose(&self, k: FrRepr) -> [(FrRepr, bool); 2] {
+        let fr = |repr| Fr::from_repr(repr).unwrap();
+
+        // c1 = k * g1 / 2^256 and c2 = k * g2 / 2^256 are the roundings of
+        // k * b2 / d and -k * b1 / d up to one, so that (k, 0) is close to
+        // the lattice vector c1 * (a1, b1) + c2 * (a2, b2), and the difference
+        // (k1, k2) is short
+        let c1 = fr(mul_shr_256(&k, &self.g1));
+        let c2 = fr(mul_shr_256(&k, &self.g2));
+
+        // k1 = k - c1 * a1 - c2 * a2
+        let mut k1 = fr(k);
+        let mut tmp = c1;
+        tmp.mul_assign(&fr(self.a1));
+        k1.sub_assign(&tmp);
+        let mut tmp = c2;
+        tmp.mul_assign(&fr(self.a2));
+        k1.sub_assign(&tmp);
+
+        // k2 = -c1 * b1 - c2 * b2
+        let mut k2 = c1;
+        k2.mul_assign(&fr(self.b1));
+        let mut tmp = c2;
+        tmp.mul_assign(&fr(self.b2));
+        k2.add_assign(&tmp);
+        k2.negate();
+
+        [signed_repr(k1), signed_repr(k2)]
+    }
+}
+
+/// Returns the floor of k * g / 2^256, which must be less than 2^128.
+fn mul_shr_256(k: &FrRepr, g: &[u64; 3]) -> FrRepr {
+    let mut product = [0u64; 7];
+    for (i, &a) in k.0.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &b) in g.iter().enumerate() {
+            let tmp = u128::from(a) * u128::from(b) + u128::from(product[i + j]) + carry;
+            product[i + j] = tmp as u64;
+            carry = tmp >> 64;
+        }
+        product[i + g.len()] = carry as u64;
+    }
+    debug_assert_eq!(product[6], 0);
+
+    FrRepr([product[4], product[5], 0, 0])
+}
+
+/// Returns the absolute value of the element, as the smaller of its
+/// representation and the one of its negation, and whether it is negative.
+fn signed_repr(f: Fr) -> (FrRepr, bool) {
+    let mut neg = f;
+    neg.negate();
+
+    let (f, neg) = (f.into_repr(), neg.into_repr());
+    if neg < f {
+        (neg, true)
+    } else {
+        (f, false)
+    }
+}
+
 pub mod g1 {
+    use super::super::fq::{BETA, SVDW_G1};
     use super::super::{Bn256, Fq, Fq12, FqRepr, Fr, FrRepr};
+    use super::ScalarDecomposition;
     use super::g2::G2Affine;
     use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+    use hash_to_curve::{hash_to_field, MapField};
     use rand::{Rand, Rng};
     use std::fmt;
-    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, HashToCurve};
 
     curve_impl!(
         "G1",
@@ -932,6 +1197,63 @@ pub mod g1 {
         }
     }
 
+    // The decomposition of the scalars for the endomorphism (x, y) -> (beta * x, y),
+    // whose eigenvalue is the cube root of unity
+    // lambda = 0xb3c4d79d41a917585bfc41088d8daaa78b17ea66b99c90dd
+    const SCALAR_DECOMPOSITION: ScalarDecomposition = ScalarDecomposition {
+        // 2u + 1
+        a1: FrRepr([0x89d3256894d213e3, 0x0, 0x0, 0x0]),
+        // -(6u^2 + 2u)
+        b1: FrRepr([
+            0xc1d039a872b0eed9,
+            0xb8e665ff8b011694,
+            0xb85045b68181585c,
+            0x30644e72e131a029,
+        ]),
+        // 6u^2 + 4u + 1
+        a2: FrRepr([0x0be4e1541221250b, 0x6f4d8248eeb859fd, 0x0, 0x0]),
+        // 2u + 1
+        b2: FrRepr([0x89d3256894d213e3, 0x0, 0x0, 0x0]),
+        g1: [0xd91d232ec7e0b3d7, 0x2, 0x0],
+        g2: [0x7a7bd9d4391eb18e, 0x4ccef014a773d2cf, 0x2],
+    };
+
+    impl G1Affine {
+        /// Returns (beta * x, y), which is the element multiplied by lambda.
+        fn endomorphism(&self) -> Self {
+            let mut res = *self;
+            res.x.mul_assign(&BETA);
+
+            res
+        }
+    }
+
+    impl G1Affine {
+        /// Maps the element to a point of the curve with the Shallue-van de
+        /// Woestijne map, for which z = 1 satisfies the conditions.
+        fn map_to_curve(u: &Fq) -> G1Affine {
+            let (x, y) = SVDW_G1.map_to_curve(u);
+
+            G1Affine {
+                x: x,
+                y: y,
+                infinity: false
+            }
+        }
+    }
+
+    impl HashToCurve for G1 {
+        fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G1 {
+            let u = hash_to_field::<Fq>(msg, dst, 2);
+
+            // The cofactor of G1 is one
+            let mut p = G1Affine::map_to_curve(&u[0]).into_projective();
+            p.add_assign_mixed(&G1Affine::map_to_curve(&u[1]));
+
+            p
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct G1Prepared(pub(crate) G1Affine);
 
@@ -1001,15 +1323,98 @@ pub mod g1 {
         ::tests::curve::curve_tests::<G1>();
         ::tests::curve::random_transformation_tests::<G1>();
     }
+
+    #[test]
+    fn g1_hash_to_curve() {
+        ::hash_to_curve::tests::map_to_curve_tests(&SVDW_G1);
+        ::tests::curve::hash_to_curve_tests::<G1>();
+    }
+
+    #[test]
+    fn g1_hash_to_curve_known_answers() {
+        let point = |x: &str, y: &str| G1Affine {
+            x: Fq::from_str(x).unwrap(),
+            y: Fq::from_str(y).unwrap(),
+            infinity: false,
+        };
+
+        // Computed with an independent implementation of the hash
+        let dst = b"BN256G1_XMD:BLAKE2b_SVDW_RO_TESTGEN";
+        assert_eq!(
+            G1::hash_to_curve(b"", dst).into_affine(),
+            point(
+                "7575676569381397161906991154655192199544925492923499184830993558830502716353",
+                "14647642974444871881889044528450628220074396230793072689610688417883472765661"
+            )
+        );
+        assert_eq!(
+            G1::hash_to_curve(b"abc", dst).into_affine(),
+            point(
+                "9621469975696397641776165165064808624522257512809701178306215362585941012908",
+                "12767532844303970748365353991950993127376272660019640581824553143523544116461"
+            )
+        );
+
+        assert_eq!(
+            G1Affine::map_to_curve(&Fq::zero()),
+            point(
+                "10944121435919637611123202872628637544348155578648911831344518947322613104291",
+                "4718603453640367770405249522358112449463417117041194427604452040985121683380"
+            )
+        );
+        assert_eq!(
+            G1Affine::map_to_curve(&Fq::one()),
+            point(
+                "19699418584655347698258596782613050042691797047307431679640710698076539066151",
+                "10343751156573783632778856105235937123682780444565366714146603135794096447717"
+            )
+        );
+    }
+
+    #[test]
+    fn g1_endomorphism() {
+        use rand::{SeedableRng, XorShiftRng};
+
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        // The cube root of unity lambda of the endomorphism
+        let lambda = FrRepr([0x8b17ea66b99c90dd, 0x5bfc41088d8daaa7, 0xb3c4d79d41a91758, 0x0]);
+
+        for _ in 0..10 {
+            let p = G1Affine::rand(&mut rng);
+
+            assert_eq!(p.endomorphism().into_projective(), p.mul_bits(BitIterator::new(lambda)));
+            assert_eq!(p.mul(lambda), p.endomorphism().into_projective());
+
+            let mut q = p.into_projective();
+            q.mul_assign(lambda);
+            assert_eq!(q, p.endomorphism().into_projective());
+
+            let s = Fr::rand(&mut rng).into_repr();
+            assert_eq!(p.mul(s), p.mul_bits(BitIterator::new(s)));
+        }
+
+        // The scalars which are not reduced are multiplied bit by bit
+        let p = G1Affine::rand(&mut rng);
+        assert!(p.mul(Fr::char()).is_zero());
+        assert!(G1Affine::zero().mul(lambda).is_zero());
+
+        assert!(G1Affine::zero().endomorphism().is_zero());
+    }
 }
 
 pub mod g2 {
-    use super::super::{Bn256, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr};
+    use super::super::fq::SVDW_G2;
+    use super::super::{
+        Bn256, Fq, Fq12, Fq2, FqRepr, Fr, FrRepr, FROBENIUS_COEFF_FQ6_C1, XI_TO_Q_MINUS_1_OVER_2,
+    };
+    use super::ScalarDecomposition;
     use super::g1::G1Affine;
     use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+    use hash_to_curve::{hash_to_field, MapField};
     use rand::{Rand, Rng};
     use std::fmt;
-    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError};
+    use {CurveAffine, CurveProjective, EncodedPoint, Engine, GroupDecodingError, HashToCurve};
 
     curve_impl!(
         "G2",
@@ -1330,6 +1735,87 @@ pub mod g2 {
         }
     }
 
+    // The decomposition of the scalars for the endomorphism
+    // psi(x, y) = (conj(x) * xi^((q - 1) / 3), conj(y) * xi^((q - 1) / 2)),
+    // which untwists the point, applies the Frobenius map and twists it
+    // back, and whose eigenvalue is lambda = q mod r = 6u^2
+    const SCALAR_DECOMPOSITION: ScalarDecomposition = ScalarDecomposition {
+        // -6u^2
+        a1: FrRepr([0xf83e9682e87cfd46, 0x6f4d8248eeb859fb, 0x0, 0x0]),
+        // -1
+        b1: FrRepr([
+            0x43e1f593f0000000,
+            0x2833e84879b97091,
+            0xb85045b68181585d,
+            0x30644e72e131a029,
+        ]),
+        // 6u + 1
+        a2: FrRepr([0x9d797039be763ba7, 0x1, 0x0, 0x0]),
+        // 6u^2 + 6u + 3
+        b2: FrRepr([0x95b806bca6f338ef, 0x6f4d8248eeb859fd, 0x0, 0x0]),
+        g1: [0x2cb62031c8e01942, 0x4ccef014a773d2d5, 0x2],
+        g2: [0x5, 0x0, 0x0],
+    };
+
+    impl G2Affine {
+        /// Returns psi of the element, which is the element multiplied by lambda.
+        fn endomorphism(&self) -> Self {
+            if self.is_zero() {
+                return *self;
+            }
+
+            let mut res = *self;
+            res.x.c1.negate();
+            res.x.mul_assign(&FROBENIUS_COEFF_FQ6_C1[1]);
+            res.y.c1.negate();
+            res.y.mul_assign(&XI_TO_Q_MINUS_1_OVER_2);
+
+            res
+        }
+    }
+
+    impl G2Affine {
+        /// Maps the element to a point of the twist with the Shallue-van de
+        /// Woestijne map, for which z = 1 satisfies the conditions. The point
+        /// is not guaranteed to be in the prime order subgroup.
+        fn map_to_curve(u: &Fq2) -> G2Affine {
+            let (x, y) = SVDW_G2.map_to_curve(u);
+
+            G2Affine {
+                x: x,
+                y: y,
+                infinity: false
+            }
+        }
+    }
+
+    impl G2 {
+        /// Multiplies the point by the cofactor 2q - r of the twist, which
+        /// maps it into the prime order subgroup.
+        fn clear_cofactor(&self) -> G2 {
+            let mut p = *self;
+            p.mul_assign(FrRepr([
+                0x345f2299c0f9fa8d,
+                0x06ceecda572a2489,
+                0xb85045b68181585e,
+                0x30644e72e131a029
+            ]));
+
+            p
+        }
+    }
+
+    impl HashToCurve for G2 {
+        fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G2 {
+            let u = hash_to_field::<Fq2>(msg, dst, 2);
+
+            let mut p = G2Affine::map_to_curve(&u[0]).into_projective();
+            p.add_assign_mixed(&G2Affine::map_to_curve(&u[1]));
+
+            p.clear_cofactor()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct G2Prepared {
         pub(crate) coeffs: Vec<(Fq2, Fq2, Fq2)>,
@@ -1592,6 +2078,127 @@ pub mod g2 {
         }
     }
 
+    #[test]
+    fn g2_endomorphism() {
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        // The eigenvalue lambda = q mod r = 6u^2 of the endomorphism
+        let lambda = FrRepr([0xf83e9682e87cfd46, 0x6f4d8248eeb859fb, 0x0, 0x0]);
+
+        for _ in 0..10 {
+            let p = G2Affine::rand(&mut rng);
+
+            assert_eq!(p.endomorphism().into_projective(), p.mul_bits(BitIterator::new(lambda)));
+            assert_eq!(p.mul(lambda), p.endomorphism().into_projective());
+
+            let mut q = p.into_projective();
+            q.mul_assign(lambda);
+            assert_eq!(q, p.endomorphism().into_projective());
+
+            let s = Fr::rand(&mut rng).into_repr();
+            assert_eq!(p.mul(s), p.mul_bits(BitIterator::new(s)));
+        }
+
+        // The scalars which are not reduced are multiplied bit by bit
+        let p = G2Affine::rand(&mut rng);
+        assert!(p.mul(Fr::char()).is_zero());
+        assert!(G2Affine::zero().mul(lambda).is_zero());
+
+        assert_eq!(G2Affine::zero().endomorphism(), G2Affine::zero());
+    }
+
+    #[test]
+    fn g2_hash_to_curve() {
+        ::hash_to_curve::tests::map_to_curve_tests(&SVDW_G2);
+        ::tests::curve::hash_to_curve_tests::<G2>();
+
+        // The points of the map are only in the subgroup after
+        // clearing the cofactor
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        for _ in 0..10 {
+            let p = G2Affine::map_to_curve(&Fq2::rand(&mut rng)).into_projective();
+
+            let mut q = p;
+            q.mul_assign(Fr::char());
+            assert!(!q.is_zero());
+
+            let mut q = p.clear_cofactor();
+            assert!(!q.is_zero());
+            q.mul_assign(Fr::char());
+            assert!(q.is_zero());
+        }
+    }
+
+    #[test]
+    fn g2_hash_to_curve_known_answers() {
+        let fq2 = |c0: &str, c1: &str| Fq2 {
+            c0: Fq::from_str(c0).unwrap(),
+            c1: Fq::from_str(c1).unwrap(),
+        };
+        let point = |x: Fq2, y: Fq2| G2Affine {
+            x: x,
+            y: y,
+            infinity: false,
+        };
+
+        // Computed with an independent implementation of the hash
+        let dst = b"BN256G2_XMD:BLAKE2b_SVDW_RO_TESTGEN";
+        assert_eq!(
+            G2::hash_to_curve(b"", dst).into_affine(),
+            point(
+                fq2(
+                    "21282945955597421733120799971335978263768689755891770297115929719190554674104",
+                    "4366121937147005043408571320898270416840167298268103113990100128545641227848"
+                ),
+                fq2(
+                    "12913589331948167126011757059600721493502741443740013181350974984077176464734",
+                    "7963603258429996845507171424876243560993855426438769662493657618448174263220"
+                )
+            )
+        );
+        assert_eq!(
+            G2::hash_to_curve(b"abc", dst).into_affine(),
+            point(
+                fq2(
+                    "6311273955869189194103357691761518886216167403488388942747922448648223887377",
+                    "21719188799237527770833856692101389456754785425970715127560572952968566754762"
+                ),
+                fq2(
+                    "17815277337080730281353934447740258613816148166062735226373937025533447070397",
+                    "13767936749622632891506526595854773933326617295758584495675393951576974852404"
+                )
+            )
+        );
+
+        assert_eq!(
+            G2Affine::map_to_curve(&Fq2::zero()),
+            point(
+                fq2(
+                    "10499238450719652342378357227399831140106360636427411350395554762472100376474",
+                    "6940174569119770192419592065569379906172001098655407502803841283667998553941"
+                ),
+                fq2(
+                    "19806355393382037816579263617074440827120892710708778621379218713139486732888",
+                    "10476946714790400356420157609988818757268013353500587580718617947726761912003"
+                )
+            )
+        );
+        assert_eq!(
+            G2Affine::map_to_curve(&fq2("1", "1")),
+            point(
+                fq2(
+                    "11752697248306744450038375715272013318117220733345044357773679707187527876673",
+                    "10709245134464165513885584417126673874593903244308031274113531694467602422987"
+                ),
+                fq2(
+                    "10879217903047981366071516817054261137514054011143249040595451550638762718857",
+                    "16892467931011678961362242534122046081811402677687342274591438271304163028632"
+                )
+            )
+        );
+    }
+
 }
 
 pub use self::g1::*;
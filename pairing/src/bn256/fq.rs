@@ -1,11 +1,50 @@
 use super::fq2::Fq2;
 use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr};
+use hash_to_curve::{
+    MapField, Svdw, eq_limbs, prime_field_from_uniform_bytes, prime_field_inv0, prime_field_is_square,
+    prime_field_sgn0, prime_field_sqrt_unchecked, select_limbs
+};
 
 #[derive(PrimeField)]
 #[PrimeFieldModulus = "21888242871839275222246405745257275088696311157297823662689037894645226208583"]
 #[PrimeFieldGenerator = "2"]
 pub struct Fq(FqRepr);
 
+impl MapField for Fq {
+    const DEGREE: usize = 1;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        prime_field_from_uniform_bytes(bytes)
+    }
+
+    fn sgn0(&self) -> bool {
+        prime_field_sgn0(self)
+    }
+
+    fn inv0(&self) -> Self {
+        prime_field_inv0(self)
+    }
+
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mut result = *a;
+        select_limbs(&mut (result.0).0, &(a.0).0, &(b.0).0, choice);
+
+        result
+    }
+
+    fn ct_eq(&self, other: &Self) -> bool {
+        eq_limbs(&(self.0).0, &(other.0).0)
+    }
+
+    fn is_square(&self) -> bool {
+        prime_field_is_square(self)
+    }
+
+    fn sqrt_unchecked(&self) -> Self {
+        prime_field_sqrt_unchecked(self)
+    }
+}
+
 // B coefficient of BN256 curve, B = 3
 // In Montgommery form with R = 2^256
 pub const B_COEFF: Fq = Fq(FqRepr([
@@ -31,6 +70,109 @@ pub const B_COEFF_FQ2: Fq2 = Fq2 {
 };
 
 
+// Constants of the Shallue-van de Woestijne maps to G1 and to the twist
+// of G2 with z = 1, c1 = g(z), c2 = -z / 2, c3 = sqrt(-g(z) * 3z^2) and
+// c4 = -4g(z) / 3z^2, where g(x) = x^3 + b
+// In Montgommery form with R = 2^256
+pub(crate) const SVDW_G1: Svdw<Fq> = Svdw {
+    z: Fq(FqRepr([
+        0xd35d438dc58f0d9d,
+        0x0a78eb28f5c70b3d,
+        0x666ea36f7879462c,
+        0x0e0a77c19a07df2f,
+    ])),
+    b: B_COEFF,
+    c1: Fq(FqRepr([
+        0x115482203dbf392d,
+        0x926242126eaa626a,
+        0xe16a48076063c052,
+        0x07c5909386eddc93,
+    ])),
+    c2: Fq(FqRepr([
+        0xb461a4448976f7d5,
+        0xc6843fb439555fa7,
+        0x28f0d12384840918,
+        0x112ceb58a394e07d,
+    ])),
+    c3: Fq(FqRepr([
+        0x7c8487078735ab72,
+        0x51da7e0048bfb8d4,
+        0x945cfd183cbd7bf4,
+        0x0b70b1ec48ae62c6,
+    ])),
+    c4: Fq(FqRepr([
+        0xa79a2bdca0800831,
+        0x19fd7617e49815a1,
+        0xbb8d0c885550c7b1,
+        0x05c4aeb6ec7e0f48,
+    ])),
+};
+
+pub(crate) const SVDW_G2: Svdw<Fq2> = Svdw {
+    z: Fq2 {
+        c0: Fq(FqRepr([
+            0xd35d438dc58f0d9d,
+            0x0a78eb28f5c70b3d,
+            0x666ea36f7879462c,
+            0x0e0a77c19a07df2f,
+        ])),
+        c1: Fq(FqRepr([0x0, 0x0, 0x0, 0x0])),
+    },
+    b: B_COEFF_FQ2,
+    c1: Fq2 {
+        c0: Fq(FqRepr([
+            0xd335f05a64ca12fe,
+            0x75029bbec388940d,
+            0xd4d64ba9406d402e,
+            0x02baef80fc5ae772,
+        ])),
+        c1: Fq(FqRepr([
+            0x38e7ecccd1dcff67,
+            0x65f0b37d93ce0d3e,
+            0xd749d0dd22ac00aa,
+            0x0141b9ce4a688d4d,
+        ])),
+    },
+    c2: Fq2 {
+        c0: Fq(FqRepr([
+            0xb461a4448976f7d5,
+            0xc6843fb439555fa7,
+            0x28f0d12384840918,
+            0x112ceb58a394e07d,
+        ])),
+        c1: Fq(FqRepr([0x0, 0x0, 0x0, 0x0])),
+    },
+    c3: Fq2 {
+        c0: Fq(FqRepr([
+            0xaaad0cab9a24277f,
+            0xf2209f5b7e5b757a,
+            0xc3a46b7e850013a7,
+            0x1f9e7f3768c5c9af,
+        ])),
+        c1: Fq(FqRepr([
+            0x412278c8de85d863,
+            0xfe3e4c7f559d375a,
+            0x5e44b9da0a96ad23,
+            0x297d818d387725c8,
+        ])),
+    },
+    c4: Fq2 {
+        c0: Fq(FqRepr([
+            0x63cdc796b49b3a32,
+            0x73a8220d40eb16f6,
+            0xb46d1eed55c49000,
+            0x1c9ef4f5f0528b82,
+        ])),
+        c1: Fq(FqRepr([
+            0x9aeb505b1600fe13,
+            0x64eb25e9f8b4638f,
+            0x43edd9e4fdf1577a,
+            0x2eb756b528a63917,
+        ])),
+    },
+};
+
+
 // The generators of G1/G2
 
 // Generator of G1
@@ -438,6 +580,15 @@ pub const NEGATIVE_ONE: Fq = Fq(FqRepr([
     0x2259d6b14729c0fa,
 ]));
 
+// A primitive cube root of unity, of the endomorphism (x, y) -> (beta * x, y)
+// of G1, which multiplies its points by the matching cube root of unity in Fr
+pub const BETA: Fq = Fq(FqRepr([
+    0x71930c11d782e155,
+    0xa6bb947cffbe3323,
+    0xaa303344d4741444,
+    0x2c3b3f0d26594943,
+]));
+
 #[cfg(test)]
 use rand::{Rand, SeedableRng, XorShiftRng};
 
@@ -570,6 +721,52 @@ fn test_fq_sqrt_2() {
     }
 }
 
+#[test]
+fn test_fq_limbs_32() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut neg_one = Fq::one();
+    neg_one.negate();
+
+    let mut elements = vec![Fq::zero(), Fq::one(), neg_one];
+    elements.extend((0..1000).map(|_| Fq::rand(&mut rng)));
+
+    // The arithmetic on 32-bit limbs agrees with the one on 64-bit limbs
+    for a in elements.iter() {
+        for b in elements.iter().take(100) {
+            let mut tmp1 = *a;
+            tmp1.add_assign(b);
+            let mut tmp2 = *a;
+            tmp2.add_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.sub_assign(b);
+            let mut tmp2 = *a;
+            tmp2.sub_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.mul_assign(b);
+            let mut tmp2 = *a;
+            tmp2.mul_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+        }
+
+        let mut tmp = *a;
+        tmp.square();
+        let mut tmp2 = *a;
+        tmp2.mul_assign_32(a);
+
+        assert_eq!(tmp, tmp2);
+
+        assert_eq!(a.into_repr(), a.into_repr_32());
+    }
+}
+
 #[test]
 fn fq_field_tests() {
     ::tests::field::random_field_tests::<Fq>();
@@ -3,6 +3,8 @@ use super::fq2::Fq2;
 use super::fq6::Fq6;
 use ff::Field;
 use rand::{Rand, Rng};
+use std::io::{self, Write};
+use ExtensionField;
 
 /// An element of Fq12, represented by c0 + c1 * w.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -17,6 +19,15 @@ impl ::std::fmt::Display for Fq12 {
     }
 }
 
+impl ExtensionField for Fq12 {
+    fn write_be<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.c0.write_be(&mut writer)?;
+        self.c1.write_be(&mut writer)?;
+
+        Ok(())
+    }
+}
+
 impl Rand for Fq12 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
         Fq12 {
@@ -50,4 +50,52 @@ fn test_roots_of_unity() {
 #[test]
 fn test_default() {
     assert_eq!(Fr::default(), Fr::zero());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_fr_limbs_32() {
+    use rand::{Rand, SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut neg_one = Fr::one();
+    neg_one.negate();
+
+    let mut elements = vec![Fr::zero(), Fr::one(), neg_one];
+    elements.extend((0..1000).map(|_| Fr::rand(&mut rng)));
+
+    // The arithmetic on 32-bit limbs agrees with the one on 64-bit limbs
+    for a in elements.iter() {
+        for b in elements.iter().take(100) {
+            let mut tmp1 = *a;
+            tmp1.add_assign(b);
+            let mut tmp2 = *a;
+            tmp2.add_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.sub_assign(b);
+            let mut tmp2 = *a;
+            tmp2.sub_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+
+            let mut tmp1 = *a;
+            tmp1.mul_assign(b);
+            let mut tmp2 = *a;
+            tmp2.mul_assign_32(b);
+
+            assert_eq!(tmp1, tmp2);
+        }
+
+        let mut tmp = *a;
+        tmp.square();
+        let mut tmp2 = *a;
+        tmp2.mul_assign_32(a);
+
+        assert_eq!(tmp, tmp2);
+
+        assert_eq!(a.into_repr(), a.into_repr_32());
+    }
+}
@@ -0,0 +1,417 @@
+//! Hashing to the curves, following the hash_to_curve construction of
+//! RFC 9380 with the Shallue-van de Woestijne map, which works for the
+//! curves y^2 = x^3 + b of this crate, and BLAKE2b as the hash function.
+
+use blake2_rfc::blake2b::Blake2b;
+use ff::{Field, PrimeField, PrimeFieldRepr};
+
+/// Groups with a deterministic hash from messages to their elements, whose
+/// discrete logarithms nobody knows. Hashes with different domain separation
+/// tags are independent, so that every protocol should use its own.
+pub trait HashToCurve {
+    /// Hashes the message to an element of the group, with the domain
+    /// separation tag `dst`.
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self;
+}
+
+/// A field of the coordinates of the points of the map, with the operations
+/// the map needs, which do the same operations for every element up to the
+/// arithmetic of the field.
+pub(crate) trait MapField: Field {
+    /// The number of elements of the prime field in an element.
+    const DEGREE: usize;
+
+    /// Returns the element of the bytes, which are `UNIFORM_BYTES` for
+    /// every element of the prime field and uniformly random.
+    fn from_uniform_bytes(bytes: &[u8]) -> Self;
+
+    /// Returns the sign of the element, in the sense of RFC 9380.
+    fn sgn0(&self) -> bool;
+
+    /// Returns the inverse of the element, or zero if it is zero, with
+    /// the same operations for every element.
+    fn inv0(&self) -> Self;
+
+    /// Returns `b` if `choice` is true and `a` otherwise, without branching.
+    fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self;
+
+    /// Returns true if the elements are equal, without branching.
+    fn ct_eq(&self, other: &Self) -> bool;
+
+    /// Returns true if the element is a square, including zero.
+    fn is_square(&self) -> bool;
+
+    /// Returns a square root of the element if it is a square, and an
+    /// unspecified element otherwise, with a fixed sequence of operations.
+    fn sqrt_unchecked(&self) -> Self;
+}
+
+/// The number of bytes for every element of a prime field, which leaves at
+/// least 128 bits more than the modulus, so that the bias of the reduction
+/// is negligible.
+pub(crate) const UNIFORM_BYTES: usize = 64;
+
+/// Returns the element of a prime field of the big-endian bytes, reduced
+/// modulo the characteristic.
+pub(crate) fn prime_field_from_uniform_bytes<F: PrimeField>(bytes: &[u8]) -> F {
+    let mut shift = F::Repr::from(1);
+    shift.shl(32);
+    let shift = F::from_repr(shift).unwrap();
+
+    // The limbs are of 32 bits, which are smaller than every modulus
+    let mut result = F::zero();
+    for chunk in bytes.chunks(4) {
+        let limb = chunk.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+
+        result.mul_assign(&shift);
+        result.add_assign(&F::from_repr(F::Repr::from(limb)).unwrap());
+    }
+
+    result
+}
+
+/// Returns the sign of an element of a prime field, which is its parity.
+pub(crate) fn prime_field_sgn0<F: PrimeField>(f: &F) -> bool {
+    f.into_repr().is_odd()
+}
+
+/// Returns the inverse of an element of a prime field with Fermat's little
+/// theorem, or zero if it is zero.
+pub(crate) fn prime_field_inv0<F: PrimeField>(f: &F) -> F {
+    let mut exp = F::char();
+    exp.sub_noborrow(&F::Repr::from(2));
+
+    f.pow(exp)
+}
+
+/// Returns true if an element of a prime field is a square, with Euler's
+/// criterion.
+pub(crate) fn prime_field_is_square<F: PrimeField + MapField>(f: &F) -> bool {
+    let mut exp = F::char();
+    exp.sub_noborrow(&F::Repr::from(1));
+    exp.div2();
+
+    let mut neg_one = F::one();
+    neg_one.negate();
+
+    !f.pow(exp).ct_eq(&neg_one)
+}
+
+/// Returns f^((p + 1) / 4), which is a square root of f if it is a square,
+/// for a prime field of characteristic p = 3 mod 4.
+pub(crate) fn prime_field_sqrt_unchecked<F: PrimeField>(f: &F) -> F {
+    let mut exp = F::char();
+    exp.add_nocarry(&F::Repr::from(1));
+    exp.shr(2);
+
+    f.pow(exp)
+}
+
+/// Sets the limbs to the ones of `b` if `choice` is true, and to the ones
+/// of `a` otherwise.
+pub(crate) fn select_limbs(result: &mut [u64], a: &[u64], b: &[u64], choice: bool) {
+    let mask = (choice as u64).wrapping_neg();
+
+    for (r, (a, b)) in result.iter_mut().zip(a.iter().zip(b.iter())) {
+        *r = a ^ (mask & (a ^ b));
+    }
+}
+
+/// Returns true if the limbs are equal.
+pub(crate) fn eq_limbs(a: &[u64], b: &[u64]) -> bool {
+    let diff = a.iter().zip(b.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+
+    // The top bit of diff | -diff is set unless diff is zero
+    ((diff | diff.wrapping_neg()) >> 63) == 0
+}
+
+/// Expands the message into `len` uniformly random bytes, with
+/// expand_message_xmd of RFC 9380 for BLAKE2b-512.
+fn expand_message(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    const HASH_BYTES: usize = 64;
+    const BLOCK_BYTES: usize = 128;
+
+    let ell = (len + HASH_BYTES - 1) / HASH_BYTES;
+    assert!(ell <= 255 && len <= 0xffff);
+
+    // Tags longer than 255 bytes are replaced by their hash
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        let mut h = Blake2b::new(HASH_BYTES);
+        h.update(b"H2C-OVERSIZE-DST-");
+        h.update(dst);
+        long_dst = h.finalize();
+
+        long_dst.as_bytes()
+    } else {
+        dst
+    };
+
+    let hash = |parts: &[&[u8]]| {
+        let mut h = Blake2b::new(HASH_BYTES);
+        for part in parts {
+            h.update(part);
+        }
+        h.update(dst);
+        h.update(&[dst.len() as u8]);
+
+        h.finalize()
+    };
+
+    let b_0 = hash(&[&[0; BLOCK_BYTES], msg, &[(len >> 8) as u8, len as u8, 0]]);
+    let mut b_i = hash(&[b_0.as_bytes(), &[1]]);
+
+    let mut result = b_i.as_bytes().to_vec();
+    for i in 2..(ell + 1) {
+        let mut tmp = [0; HASH_BYTES];
+        for (t, (a, b)) in tmp.iter_mut().zip(b_0.as_bytes().iter().zip(b_i.as_bytes().iter())) {
+            *t = a ^ b;
+        }
+
+        b_i = hash(&[&tmp, &[i as u8]]);
+        result.extend_from_slice(b_i.as_bytes());
+    }
+
+    result.truncate(len);
+
+    result
+}
+
+/// Hashes the message to `count` elements of the field.
+pub(crate) fn hash_to_field<F: MapField>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    let len = F::DEGREE * UNIFORM_BYTES;
+    let bytes = expand_message(msg, dst, count * len);
+
+    bytes.chunks(len).map(F::from_uniform_bytes).collect()
+}
+
+/// Returns x^3 + b
+fn curve_equation<F: Field>(x: &F, b: &F) -> F {
+    let mut result = *x;
+    result.square();
+    result.mul_assign(x);
+    result.add_assign(b);
+
+    result
+}
+
+/// The Shallue-van de Woestijne map to the curve y^2 = x^3 + b for the
+/// constant z, with the constants of the straight-line implementation of
+/// RFC 9380, which are computed once for every curve.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Svdw<F> {
+    pub z: F,
+    pub b: F,
+    /// g(z)
+    pub c1: F,
+    /// -z / 2
+    pub c2: F,
+    /// sqrt(-g(z) * 3z^2), with sgn0(c3) = 0
+    pub c3: F,
+    /// -4g(z) / 3z^2
+    pub c4: F
+}
+
+impl<F: MapField> Svdw<F> {
+    /// Computes the constants of the map. The constant z must satisfy the
+    /// conditions of the RFC.
+    #[cfg(test)]
+    pub fn new(z: &F, b: &F) -> Self {
+        let one = F::one();
+        let mut two = one;
+        two.double();
+        let mut three = two;
+        three.add_assign(&one);
+
+        let c1 = curve_equation(z, b);
+        let mut three_z2 = *z;
+        three_z2.square();
+        three_z2.mul_assign(&three);
+
+        let mut c2 = *z;
+        c2.mul_assign(&two.inv0());
+        c2.negate();
+
+        let mut c3 = c1;
+        c3.mul_assign(&three_z2);
+        c3.negate();
+        assert!(c3.is_square());
+        let mut c3 = c3.sqrt_unchecked();
+        if c3.sgn0() {
+            c3.negate();
+        }
+
+        let mut c4 = c1;
+        c4.double();
+        c4.double();
+        c4.negate();
+        c4.mul_assign(&three_z2.inv0());
+
+        Svdw {
+            z: *z,
+            b: *b,
+            c1: c1,
+            c2: c2,
+            c3: c3,
+            c4: c4
+        }
+    }
+
+    /// Maps the element to a point (x, y) of the curve, with the
+    /// straight-line implementation of RFC 9380, which has no exceptional
+    /// cases. It selects the coordinates without branching and takes the
+    /// square root with a fixed sequence of operations, so that it does
+    /// the same operations for every element, up to the arithmetic of
+    /// the field.
+    pub fn map_to_curve(&self, u: &F) -> (F, F) {
+        let one = F::one();
+
+        // tv2 = 1 + u^2 * c1, tv1 = 1 - u^2 * c1, tv3 = 1 / (tv1 * tv2)
+        let mut u2c1 = *u;
+        u2c1.square();
+        u2c1.mul_assign(&self.c1);
+        let mut tv1 = one;
+        tv1.sub_assign(&u2c1);
+        let mut tv2 = one;
+        tv2.add_assign(&u2c1);
+        let mut tv3 = tv1;
+        tv3.mul_assign(&tv2);
+        let tv3 = tv3.inv0();
+
+        // tv4 = u * tv1 * tv3 * c3
+        let mut tv4 = *u;
+        tv4.mul_assign(&tv1);
+        tv4.mul_assign(&tv3);
+        tv4.mul_assign(&self.c3);
+
+        let mut x1 = self.c2;
+        x1.sub_assign(&tv4);
+        let e1 = curve_equation(&x1, &self.b).is_square();
+
+        let mut x2 = self.c2;
+        x2.add_assign(&tv4);
+        let e2 = curve_equation(&x2, &self.b).is_square() & !e1;
+
+        // x3 = z + c4 * (tv2^2 * tv3)^2
+        let mut x3 = tv2;
+        x3.square();
+        x3.mul_assign(&tv3);
+        x3.square();
+        x3.mul_assign(&self.c4);
+        x3.add_assign(&self.z);
+
+        let x = F::conditional_select(&x3, &x2, e2);
+        let x = F::conditional_select(&x, &x1, e1);
+
+        // One of the candidates is on the curve
+        let y = curve_equation(&x, &self.b).sqrt_unchecked();
+        let mut neg_y = y;
+        neg_y.negate();
+        let y = F::conditional_select(&y, &neg_y, u.sgn0() ^ y.sgn0());
+
+        (x, y)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rand::{Rand, SeedableRng, XorShiftRng};
+
+    /// Checks the map and its constants.
+    pub(crate) fn map_to_curve_tests<F: MapField + Rand>(svdw: &Svdw<F>) {
+        assert_eq!(*svdw, Svdw::new(&svdw.z, &svdw.b));
+
+        let (z, b) = (&svdw.z, &svdw.b);
+        let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let mut elements = vec![F::zero(), F::one()];
+        for _ in 0..100 {
+            elements.push(F::rand(&mut rng));
+        }
+
+        // The exceptional cases are u^2 * g(z) = 1 or -1, where the
+        // inverse of the straight-line implementation is zero
+        let g_z_inv = curve_equation(z, b).inverse().unwrap();
+        let mut neg_g_z_inv = g_z_inv;
+        neg_g_z_inv.negate();
+        for t in [g_z_inv, neg_g_z_inv].iter() {
+            if t.is_square() {
+                let u = t.sqrt_unchecked();
+                let mut neg_u = u;
+                neg_u.negate();
+                elements.push(u);
+                elements.push(neg_u);
+            }
+        }
+        assert!(elements.len() > 102);
+
+        for u in elements {
+            let (x, y) = svdw.map_to_curve(&u);
+
+            let mut y2 = y;
+            y2.square();
+            assert_eq!(y2, curve_equation(&x, b));
+            assert_eq!(u.sgn0(), y.sgn0());
+        }
+
+        for _ in 0..10 {
+            let f = F::rand(&mut rng);
+            let mut f_inv = f.inv0();
+            f_inv.mul_assign(&f);
+            assert_eq!(f_inv, F::one());
+        }
+        assert!(F::zero().inv0().is_zero());
+
+        // The operations without branching agree with the usual ones
+        for _ in 0..100 {
+            let a = F::rand(&mut rng);
+            let b = F::rand(&mut rng);
+            assert_eq!(F::conditional_select(&a, &b, false), a);
+            assert_eq!(F::conditional_select(&a, &b, true), b);
+            assert!(a.ct_eq(&a));
+            assert!(!a.ct_eq(&b));
+
+            let mut square = a;
+            square.square();
+            assert!(square.is_square());
+            let mut root = square.sqrt_unchecked();
+            root.square();
+            assert_eq!(root, square);
+
+            // Exactly one of a and a * n is a square for a non-square n
+            let mut non_square = F::one();
+            while non_square.is_square() {
+                non_square = F::rand(&mut rng);
+            }
+            let mut a_n = a;
+            a_n.mul_assign(&non_square);
+            assert!(a.is_square() != a_n.is_square());
+        }
+        assert!(F::zero().is_square());
+        assert!(F::zero().sqrt_unchecked().is_zero());
+    }
+
+    #[test]
+    fn test_expand_message() {
+        for &len in [1, 32, 64, 65, 200].iter() {
+            let bytes = expand_message(b"abc", b"DST", len);
+            assert_eq!(bytes.len(), len);
+            assert_eq!(bytes, expand_message(b"abc", b"DST", len));
+            assert!(bytes != expand_message(b"abd", b"DST", len));
+            assert!(bytes != expand_message(b"abc", b"DSU", len));
+        }
+
+        // The length is hashed, so the outputs are not prefixes
+        assert!(expand_message(b"abc", b"DST", 64)[..32] != expand_message(b"abc", b"DST", 32)[..]);
+
+        let long_dst = [7; 300];
+        let mut long_dst_hash = Blake2b::new(64);
+        long_dst_hash.update(b"H2C-OVERSIZE-DST-");
+        long_dst_hash.update(&long_dst);
+        assert_eq!(
+            expand_message(b"abc", &long_dst, 100),
+            expand_message(b"abc", long_dst_hash.finalize().as_bytes(), 100)
+        );
+    }
+}
@@ -11,6 +11,7 @@
 // Force public structures to implement Debug
 #![deny(missing_debug_implementations)]
 
+extern crate blake2_rfc;
 extern crate byteorder;
 extern crate ff;
 extern crate rand;
@@ -23,15 +24,20 @@ extern crate serde_derive;
 #[cfg(test)]
 pub mod tests;
 
+pub mod bls;
 pub mod bls12_381;
 pub mod bn256;
 
 mod wnaf;
 pub use self::wnaf::Wnaf;
 
+mod hash_to_curve;
+pub use self::hash_to_curve::HashToCurve;
+
 use ff::{Field, PrimeField, PrimeFieldDecodingError, PrimeFieldRepr, ScalarEngine, SqrtField};
 use std::error::Error;
 use std::fmt;
+use std::io::{self, Write};
 
 /// An "engine" is a collection of types (fields, elliptic curve groups, etc.)
 /// with well-defined relationships. In particular, the G1/G2 curve groups are
@@ -84,7 +90,7 @@ pub trait Engine: ScalarEngine {
     type Fqe: SqrtField;
 
     /// The extension field that hosts the target group of the pairing.
-    type Fqk: Field;
+    type Fqk: ExtensionField;
 
     /// Perform a miller loop with some number of (G1, G2) pairs.
     fn miller_loop<'a, I>(i: I) -> Self::Fqk
@@ -111,6 +117,14 @@ pub trait Engine: ScalarEngine {
     }
 }
 
+/// An extension of a prime field, whose elements are encoded canonically
+/// by the big-endian representations of their coefficients over the
+/// prime field, starting from the constant one.
+pub trait ExtensionField: Field {
+    /// Writes the canonical encoding of this element.
+    fn write_be<W: Write>(&self, writer: W) -> io::Result<()>;
+}
+
 /// Projective representation of an elliptic curve point guaranteed to be
 /// in the correct prime order subgroup.
 pub trait CurveProjective:
@@ -213,6 +227,22 @@ pub trait CurveAffine:
     /// Performs scalar multiplication of this element with mixed addition.
     fn mul<S: Into<<Self::Scalar as PrimeField>::Repr>>(&self, other: S) -> Self::Projective;
 
+    /// Splits the multiplication of this element by the scalar into the sum
+    /// k1 * p1 + k2 * p2 of two multiplications by scalars of about half of
+    /// the bits, for curves with an efficiently computable endomorphism of
+    /// the group. Returns `None` if the curve has none, or if the scalar is
+    /// not reduced.
+    fn split_mul(
+        &self,
+        _scalar: &<Self::Scalar as PrimeField>::Repr,
+    ) -> Option<[(Self, <Self::Scalar as PrimeField>::Repr); 2]> {
+        None
+    }
+
+    /// Performs scalar multiplication of this element by a secret scalar,
+    /// with the same sequence of group operations for every scalar.
+    fn mul_secret(&self, scalar: &Self::Scalar) -> Self::Projective;
+
     /// Prepares this element for pairing purposes.
     fn prepare(&self) -> Self::Prepared;
 
@@ -222,6 +252,28 @@ pub trait CurveAffine:
     /// Converts this element into its affine representation.
     fn into_projective(&self) -> Self::Projective;
 
+    /// Adds the elements of `other` to the elements of `v` pairwise.
+    /// Curves can implement this with a single field inversion for
+    /// all of the additions.
+    fn batch_add_assign(v: &mut [Self], other: &[Self]) {
+        assert_eq!(v.len(), other.len());
+
+        let mut sums: Vec<_> = v.iter()
+            .zip(other.iter())
+            .map(|(a, b)| {
+                let mut sum = a.into_projective();
+                sum.add_assign_mixed(b);
+                sum
+            })
+            .collect();
+
+        Self::Projective::batch_normalization(&mut sums);
+
+        for (a, sum) in v.iter_mut().zip(sums.into_iter()) {
+            *a = sum.into_affine();
+        }
+    }
+
     /// Converts this element into its compressed encoding, so long as it's not
     /// the point at infinity.
     fn into_compressed(&self) -> Self::Compressed {
@@ -235,6 +287,42 @@ pub trait CurveAffine:
     }
 }
 
+/// Returns `scalar * p` with a double-and-always-add ladder, which selects
+/// the sum of every step with `select` instead of branching on the bits.
+/// The scalar k is replaced by k + r or k + 2r, whichever has the bit
+/// NUM_BITS set, so that every scalar has the same number of bits and the
+/// ladder never starts from the identity.
+pub(crate) fn mul_secret_ladder<G, F>(p: &G, scalar: &G::Scalar, select: F) -> G::Projective
+where
+    G: CurveAffine,
+    F: Fn(&G::Projective, &G::Projective, bool) -> G::Projective,
+{
+    let num_bits = G::Scalar::NUM_BITS;
+    let bit = |k: &<G::Scalar as PrimeField>::Repr, i: u32| {
+        (k.as_ref()[(i / 64) as usize] >> (i % 64)) & 1 == 1
+    };
+
+    // Both are below 2^(NUM_BITS + 1), as r > 2^(NUM_BITS - 1), unless
+    // k + r already has the bit set, in which case k + 2r may overflow
+    let mut k1 = scalar.into_repr();
+    k1.add_nocarry(&G::Scalar::char());
+    let mut k2 = k1;
+    k2.add_nocarry(&G::Scalar::char());
+
+    let mut k = k1;
+    hash_to_curve::select_limbs(k.as_mut(), k2.as_ref(), k1.as_ref(), bit(&k1, num_bits));
+
+    let mut acc = p.into_projective();
+    for i in (0..num_bits).rev() {
+        acc.double();
+        let mut sum = acc;
+        sum.add_assign_mixed(p);
+        acc = select(&acc, &sum, bit(&k, i));
+    }
+
+    acc
+}
+
 /// An encoded elliptic curve point, which should essentially wrap a `[u8; N]`.
 pub trait EncodedPoint:
     Sized + Send + Sync + AsRef<[u8]> + AsMut<[u8]> + Clone + Copy + 'static
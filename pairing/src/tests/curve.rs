@@ -1,7 +1,7 @@
-use ff::Field;
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr};
 use rand::{Rand, Rng, SeedableRng, XorShiftRng};
 
-use {CurveAffine, CurveProjective, EncodedPoint};
+use {CurveAffine, CurveProjective, EncodedPoint, HashToCurve};
 
 pub fn curve_tests<G: CurveProjective>() {
     let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
@@ -59,8 +59,10 @@ pub fn curve_tests<G: CurveProjective>() {
 
     random_addition_tests::<G>();
     random_multiplication_tests::<G>();
+    random_split_multiplication_tests::<G>();
     random_doubling_tests::<G>();
     random_negation_tests::<G>();
+    random_batch_addition_tests::<G>();
     random_wnaf_tests::<G>();
     random_encoding_tests::<G::Affine>();
 }
@@ -207,6 +209,36 @@ fn random_negation_tests<G: CurveProjective>() {
     }
 }
 
+fn random_batch_addition_tests<G: CurveProjective>() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut a = vec![];
+    let mut b = vec![];
+    for _ in 0..100 {
+        let r = G::rand(&mut rng).into_affine();
+        let s = G::rand(&mut rng).into_affine();
+        let mut rneg = r;
+        rneg.negate();
+
+        // Sums of distinct points, doublings, inverses and with zero
+        a.extend_from_slice(&[r, r, r, G::Affine::zero(), r, G::Affine::zero()]);
+        b.extend_from_slice(&[s, r, rneg, s, G::Affine::zero(), G::Affine::zero()]);
+    }
+
+    let expected: Vec<_> = a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let mut sum = a.into_projective();
+            sum.add_assign_mixed(b);
+            sum.into_affine()
+        })
+        .collect();
+
+    G::Affine::batch_add_assign(&mut a, &b);
+
+    assert_eq!(a, expected);
+}
+
 fn random_doubling_tests<G: CurveProjective>() {
     let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
@@ -261,8 +293,81 @@ fn random_multiplication_tests<G: CurveProjective>() {
         let mut tmp3 = a_affine.mul(s);
         tmp3.add_assign(&b_affine.mul(s));
 
+        // Multiplication by a secret scalar
+        let mut tmp4 = a_affine.mul_secret(&s);
+        tmp4.add_assign(&b_affine.mul_secret(&s));
+
         assert_eq!(tmp1, tmp2);
         assert_eq!(tmp1, tmp3);
+        assert_eq!(tmp1, tmp4);
+    }
+
+    // The secret scalars whose offset is k + 2r and the extremes
+    let p = G::rand(&mut rng).into_affine();
+    let mut neg_one = G::Scalar::one();
+    neg_one.negate();
+    for s in [G::Scalar::zero(), G::Scalar::one(), neg_one].iter() {
+        assert_eq!(p.mul_secret(s), p.mul(*s));
+    }
+}
+
+fn random_split_multiplication_tests<G: CurveProjective>() {
+    // Multiplies by doubling and adding for every bit of the scalar
+    fn naive_mul<G: CurveProjective>(p: &G, s: &<G::Scalar as PrimeField>::Repr) -> G {
+        let mut res = G::zero();
+        for bit in BitIterator::new(*s) {
+            res.double();
+            if bit {
+                res.add_assign(p);
+            }
+        }
+
+        res
+    }
+
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut neg_one = G::Scalar::one();
+    neg_one.negate();
+
+    let mut scalars = vec![
+        G::Scalar::zero().into_repr(),
+        G::Scalar::one().into_repr(),
+        neg_one.into_repr(),
+    ];
+    scalars.extend((0..100).map(|_| G::Scalar::rand(&mut rng).into_repr()));
+
+    for _ in 0..10 {
+        let p = G::rand(&mut rng);
+        let p_affine = p.into_affine();
+
+        for s in &scalars {
+            let expected = naive_mul(&p, s);
+
+            let mut tmp = p;
+            tmp.mul_assign(*s);
+            assert_eq!(tmp, expected);
+            assert_eq!(p_affine.mul(*s), expected);
+
+            // The multiplications with the endomorphism sum
+            // to the same point with half of the bits
+            if let Some(terms) = p_affine.split_mul(s) {
+                let mut sum = G::zero();
+                for &(q, k) in terms.iter() {
+                    assert!(k.num_bits() <= 128);
+                    sum.add_assign(&naive_mul(&q.into_projective(), &k));
+                }
+
+                assert_eq!(sum, expected);
+            }
+        }
+
+        // The scalars which are not reduced are not split
+        assert!(p_affine.split_mul(&G::Scalar::char()).is_none());
+
+        let mut tmp = p;
+        tmp.mul_assign(G::Scalar::char());
+        assert!(tmp.is_zero());
     }
 }
 
@@ -458,3 +563,26 @@ fn random_encoding_tests<G: CurveAffine>() {
         assert_eq!(de_compressed, r);
     }
 }
+
+pub fn hash_to_curve_tests<G: CurveProjective + HashToCurve>() {
+    let order = G::Scalar::char();
+
+    let mut points: Vec<G> = vec![];
+    for msg in [&b""[..], &b"abc"[..], &[0; 1000][..]].iter() {
+        for dst in [&b"DST-1"[..], &b"DST-2"[..], &[1; 300][..]].iter() {
+            let p = G::hash_to_curve(msg, dst);
+            assert!(p == G::hash_to_curve(msg, dst));
+
+            // The points are on the curve and in the subgroup
+            assert!(!p.is_zero());
+            assert!(p.into_affine().into_uncompressed().into_affine().unwrap() == p.into_affine());
+
+            let mut q = p;
+            q.mul_assign(order);
+            assert!(q.is_zero());
+
+            assert!(points.iter().all(|q| *q != p));
+            points.push(p);
+        }
+    }
+}
@@ -18,6 +18,6 @@ fn bench_baby_pedersen_hash(b: &mut test::Bencher) {
     let personalization = Personalization::MerkleTree(31);
 
     b.iter(|| {
-        pedersen_hash::<Bn256, _>(personalization, bits.clone(), &params)
+        pedersen_hash::<Bn256, _, _>(personalization, bits.clone(), &params)
     });
 }
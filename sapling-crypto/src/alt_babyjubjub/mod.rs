@@ -28,24 +28,19 @@
 //! the Montgomery curve forms a group isomorphism, allowing points
 //! to be freely converted between the two forms.
 
-// use ::jubjub::{
-//     Unknown,
-//     PrimeOrder,
-//     FixedGenerators,
-//     ToUniform,
-//     JubjubEngine,
-//     JubjubParams,
-// };
-
 use ff::{
     Field,
     PrimeField,
 };
 
-use group_hash::baby_group_hash;
+use group_hash::group_hash;
 
 use constants;
 
+use eddsa::CircomlibEddsaParams;
+use mimc7::Mimc7Params;
+use poseidon::PoseidonParams;
+
 use pairing::bn256::{
     Bn256,
     Fr
@@ -62,25 +57,16 @@ pub use ::jubjub::{
     montgomery
 };
 
-// /// This is an implementation of the twisted Edwards Jubjub curve.
-// pub mod edwards;
-
-// /// This is an implementation of the birationally equivalent
-// /// Montgomery curve.
-// pub mod montgomery;
-
 /// This is an implementation of the scalar field for Jubjub.
 pub mod fs;
 
-#[cfg(test)]
-pub mod tests;
-
 impl JubjubEngine for Bn256 {
     type Fs = self::fs::Fs;
     type Params = AltJubjubBn256;
 }
 
 pub struct AltJubjubBn256 {
+    edwards_a: Fr,
     edwards_d: Fr,
     montgomery_a: Fr,
     montgomery_2a: Fr,
@@ -95,6 +81,7 @@ pub struct AltJubjubBn256 {
 }
 
 impl JubjubParams<Bn256> for AltJubjubBn256 {
+    fn edwards_a(&self) -> &Fr { &self.edwards_a }
     fn edwards_d(&self) -> &Fr { &self.edwards_d }
     fn montgomery_a(&self) -> &Fr { &self.montgomery_a }
     fn montgomery_2a(&self) -> &Fr { &self.montgomery_2a }
@@ -133,7 +120,12 @@ impl AltJubjubBn256 {
         let mut montgomery_2a = montgomery_a;
         montgomery_2a.double();
 
+        let mut edwards_a = Fr::one();
+        edwards_a.negate();
+
         let mut tmp_params = AltJubjubBn256 {
+            // a = -1
+            edwards_a: edwards_a,
             // d = -(168696/168700)
             edwards_d: Fr::from_str("12181644023421730124874158521699555681764249180949974110617291017600649128846").unwrap(),
             // A = 168698
@@ -162,7 +154,7 @@ impl AltJubjubBn256 {
             tag.push(0u8);
 
             loop {
-                let gh = baby_group_hash(
+                let gh = group_hash(
                     &tag,
                     personalization,
                     params
@@ -348,11 +340,28 @@ impl AltJubjubBn256 {
     }
 }
 
+impl CircomlibEddsaParams<Bn256> {
+    /// Parameters of circomlib's EdDSA variants, which use Baby Jubjub in
+    /// the form `168700.x^2 + y^2 = 1 + 168696.x^2.y^2` and its Base8 point
+    /// as a generator.
+    pub fn new(params: &AltJubjubBn256) -> Self {
+        CircomlibEddsaParams::from_circomlib_generator(
+            // sqrt(-1/168700)
+            Fr::from_str("1911982854305225074381251344103329931637610209014896889891168275855466657090").unwrap(),
+            Fr::from_str("5299619240641551281634865583518297030282874472190772894086521144482721001553").unwrap(),
+            Fr::from_str("16950150798460657717958625567821834550301663161624707787222815936182638968203").unwrap(),
+            Mimc7Params::new(),
+            PoseidonParams::circomlib(),
+            params
+        ).expect("Base8 is in the prime order subgroup")
+    }
+}
+
 #[test]
 fn test_jubjub_altbn256() {
     let params = AltJubjubBn256::new();
 
-    tests::test_suite::<Bn256>(&params);
+    ::jubjub::tests::test_suite::<Bn256, _>(&params);
 
     // let test_repr = hex!("9d12b88b08dcbef8a11ee0712d94cb236ee2f4ca17317075bfafc82ce3139d31");
     // let p = edwards::Point::<Bn256, _>::read(&test_repr[..], &params).unwrap();
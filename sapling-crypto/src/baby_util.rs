@@ -1,11 +1 @@
-use blake2_rfc::blake2b::Blake2b;
-
-use babyjubjub::{JubjubEngine, ToUniform};
-
-pub fn hash_to_scalar<E: JubjubEngine>(persona: &[u8], a: &[u8], b: &[u8]) -> E::Fs {
-    let mut hasher = Blake2b::with_params(64, &[], &[], persona);
-    hasher.update(a);
-    hasher.update(b);
-    let ret = hasher.finalize();
-    E::Fs::to_uniform(ret.as_ref())
-}
+pub use util::hash_to_scalar;
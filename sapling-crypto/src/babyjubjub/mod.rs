@@ -19,17 +19,12 @@
 //! the Montgomery curve forms a group isomorphism, allowing points
 //! to be freely converted between the two forms.
 
-use pairing::{
-    Engine,
-};
-
 use ff::{
     Field,
     PrimeField,
-    SqrtField
 };
 
-use baby_group_hash::group_hash;
+use group_hash::group_hash;
 
 use constants;
 
@@ -38,112 +33,20 @@ use pairing::bn256::{
     Fr
 };
 
-/// This is an implementation of the twisted Edwards Jubjub curve.
-pub mod edwards;
-
-/// This is an implementation of the birationally equivalent
-/// Montgomery curve.
-pub mod montgomery;
-
-/// This is an implementation of the scalar field for Jubjub.
-pub mod fs;
-
-#[cfg(test)]
-pub mod tests;
-
-/// Point of unknown order.
-pub enum Unknown { }
-
-/// Point of prime order.
-pub enum PrimeOrder { }
-
-/// Fixed generators of the Jubjub curve of unknown
-/// exponent.
-#[derive(Copy, Clone)]
-pub enum FixedGenerators {
-    /// The prover will demonstrate knowledge of discrete log
-    /// with respect to this base when they are constructing
-    /// a proof, in order to authorize proof construction.
-    ProofGenerationKey = 0,
-
-    /// The note commitment is randomized over this generator.
-    NoteCommitmentRandomness = 1,
-
-    /// The node commitment is randomized again by the position
-    /// in order to supply the nullifier computation with a
-    /// unique input w.r.t. the note being spent, to prevent
-    /// Faerie gold attacks.
-    NullifierPosition = 2,
-
-    /// The value commitment is used to check balance between
-    /// inputs and outputs. The value is placed over this
-    /// generator.
-    ValueCommitmentValue = 3,
-    /// The value commitment is randomized over this generator,
-    /// for privacy.
-    ValueCommitmentRandomness = 4,
-
-    /// The spender proves discrete log with respect to this
-    /// base at spend time.
-    SpendingKeyGenerator = 5,
-
-    Max = 6
-}
-
-pub trait ToUniform {
-    fn to_uniform(digest: &[u8]) -> Self;
-}
-
-/// This is an extension to the pairing Engine trait which
-/// offers a scalar field for the embedded curve (Jubjub)
-/// and some pre-computed parameters.
-pub trait JubjubEngine: Engine {
-    /// The scalar field of the Jubjub curve
-    type Fs: PrimeField + SqrtField + ToUniform;
-    /// The parameters of Jubjub and the Sapling protocol
-    type Params: JubjubParams<Self>;
-}
+pub use ::jubjub::{
+    Unknown,
+    PrimeOrder,
+    FixedGenerators,
+    ToUniform,
+    JubjubEngine,
+    JubjubParams,
+    edwards,
+    montgomery
+};
 
-/// The pre-computed parameters for Jubjub, including curve
-/// constants and various limits and window tables.
-pub trait JubjubParams<E: JubjubEngine>: Sized {
-    /// The `a` constant of the twisted Edwards curve.
-    fn edwards_a(&self) -> &E::Fr;
-    /// The `d` constant of the twisted Edwards curve.
-    fn edwards_d(&self) -> &E::Fr;
-    /// The `A` constant of the birationally equivalent Montgomery curve.
-    fn montgomery_a(&self) -> &E::Fr;
-    /// The `A` constant, doubled.
-    fn montgomery_2a(&self) -> &E::Fr;
-    /// The scaling factor used for conversion from the Montgomery form.
-    fn scale(&self) -> &E::Fr;
-    /// Returns the generators (for each segment) used in all Pedersen commitments.
-    fn pedersen_hash_generators(&self) -> &[edwards::Point<E, PrimeOrder>];
-    /// Returns the exp table for Pedersen hashes.
-    fn pedersen_hash_exp_table(&self) -> &[Vec<Vec<edwards::Point<E, PrimeOrder>>>];
-    /// Returns the maximum number of chunks per segment of the Pedersen hash.
-    fn pedersen_hash_chunks_per_generator(&self) -> usize;
-    /// Returns the pre-computed window tables [-4, 3, 2, 1, 1, 2, 3, 4] of different
-    /// magnitudes of the Pedersen hash segment generators.
-    fn pedersen_circuit_generators(&self) -> &[Vec<Vec<(E::Fr, E::Fr)>>];
-
-    /// Returns the number of chunks needed to represent a full scalar during fixed-base
-    /// exponentiation.
-    fn fixed_base_chunks_per_generator(&self) -> usize;
-    /// Returns a fixed generator.
-    fn generator(&self, base: FixedGenerators) -> &edwards::Point<E, PrimeOrder>;
-    /// Returns a window table [0, 1, ..., 8] for different magnitudes of some
-    /// fixed generator.
-    fn circuit_generators(&self, FixedGenerators) -> &[Vec<(E::Fr, E::Fr)>];
-    /// Returns the window size for exponentiation of Pedersen hash generators
-    /// outside the circuit
-    fn pedersen_hash_exp_window_size() -> u32;
-}
-
-impl JubjubEngine for Bn256 {
-    type Fs = self::fs::Fs;
-    type Params = JubjubBn256;
-}
+/// Baby Jubjub is isomorphic to alternative Baby Jubjub,
+/// so they share the scalar field.
+pub use ::alt_babyjubjub::fs;
 
 pub struct JubjubBn256 {
     edwards_a: Fr,
@@ -172,7 +75,6 @@ impl JubjubParams<Bn256> for JubjubBn256 {
     fn pedersen_hash_exp_table(&self) -> &[Vec<Vec<edwards::Point<Bn256, PrimeOrder>>>] {
         &self.pedersen_hash_exp
     }
-    // HERE it's different from jubjub
     fn pedersen_hash_chunks_per_generator(&self) -> usize {
         62
     }
@@ -190,12 +92,18 @@ impl JubjubParams<Bn256> for JubjubBn256 {
     {
         &self.fixed_base_circuit_generators[base as usize][..]
     }
-    fn pedersen_hash_exp_window_size() -> u32 {
-        8
+    fn pedersen_hash_exp_window_size(&self) -> u32 {
+        JubjubBn256::pedersen_hash_exp_window_size()
     }
 }
 
 impl JubjubBn256 {
+    /// Returns the window size for exponentiation of Pedersen hash generators
+    /// outside the circuit
+    pub fn pedersen_hash_exp_window_size() -> u32 {
+        8
+    }
+
     pub fn new() -> Self {
         let montgomery_a = Fr::from_str("168698").unwrap();
         let mut montgomery_2a = montgomery_a;
@@ -221,10 +129,10 @@ impl JubjubBn256 {
             fixed_base_circuit_generators: vec![],
         };
 
-        fn find_group_hash<E: JubjubEngine>(
+        fn find_group_hash<E: JubjubEngine, P: JubjubParams<E>>(
             m: &[u8],
             personalization: &[u8; 8],
-            params: &E::Params
+            params: &P
         ) -> edwards::Point<E, PrimeOrder>
         {
             let mut tag = m.to_vec();
@@ -421,13 +329,13 @@ impl JubjubBn256 {
 #[test]
 fn test_params() {
     let params = JubjubBn256::new();
-    tests::test_jubjub_params::<Bn256>(&params);
+    ::jubjub::tests::test_jubjub_params::<Bn256, _>(&params);
 }
 
 #[test]
 fn test_jubjub_bn256() {
     let params = JubjubBn256::new();
-    tests::test_suite::<Bn256>(&params);
+    ::jubjub::tests::test_suite::<Bn256, _>(&params);
 
     // let test_repr = hex!("9d12b88b08dcbef8a11ee0712d94cb236ee2f4ca17317075bfafc82ce3139d31");
     // let p = edwards::Point::<Bn256, _>::read(&test_repr[..], &params).unwrap();
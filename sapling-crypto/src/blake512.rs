@@ -0,0 +1,135 @@
+//! BLAKE-512, the SHA-3 finalist (not BLAKE2b), as used by circomlib
+//! to derive EdDSA secret scalars and nonces from a private key seed.
+//! Only the unsalted variant is implemented.
+
+use byteorder::{BigEndian, ByteOrder};
+
+const BLAKE512_ROUNDS: usize = 16;
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+/// The first digits of pi.
+const C: [u64; 16] = [
+    0x243f6a8885a308d3, 0x13198a2e03707344, 0xa4093822299f31d0, 0x082efa98ec4e6c89,
+    0x452821e638d01377, 0xbe5466cf34e90c6c, 0xc0ac29b7c97c50dd, 0x3f84d5b5b5470917,
+    0x9216d5d98979fb1b, 0xd1310ba698dfb5ac, 0x2ffd72dbd01adfb7, 0xb8e1afed6a267e96,
+    0xba7c9045f12c7f99, 0x24a19947b3916cf7, 0x0801f2e2858efc16, 0x636920d871574e69,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+fn g(v: &mut [u64; 16], m: &[u64; 16], s: &[usize; 16], i: usize, (a, b, c, d): (usize, usize, usize, usize)) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i]] ^ C[s[2 * i + 1]]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(25);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[s[2 * i + 1]] ^ C[s[2 * i]]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(11);
+}
+
+/// Compresses a 128 byte block, `t` being the number of message bits
+/// hashed so far including this block (zero for a block of padding only).
+fn compress(h: &mut [u64; 8], block: &[u8], t: u128) {
+    let mut m = [0u64; 16];
+    BigEndian::read_u64_into(block, &mut m);
+
+    let t0 = t as u64;
+    let t1 = (t >> 64) as u64;
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(&h[..]);
+    v[8..12].copy_from_slice(&C[..4]);
+    v[12] = t0 ^ C[4];
+    v[13] = t0 ^ C[5];
+    v[14] = t1 ^ C[6];
+    v[15] = t1 ^ C[7];
+
+    for r in 0..BLAKE512_ROUNDS {
+        let s = &SIGMA[r % 10];
+
+        g(&mut v, &m, s, 0, (0, 4, 8, 12));
+        g(&mut v, &m, s, 1, (1, 5, 9, 13));
+        g(&mut v, &m, s, 2, (2, 6, 10, 14));
+        g(&mut v, &m, s, 3, (3, 7, 11, 15));
+        g(&mut v, &m, s, 4, (0, 5, 10, 15));
+        g(&mut v, &m, s, 5, (1, 6, 11, 12));
+        g(&mut v, &m, s, 6, (2, 7, 8, 13));
+        g(&mut v, &m, s, 7, (3, 4, 9, 14));
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+pub fn blake512(data: &[u8]) -> [u8; 64] {
+    let bit_len = (data.len() as u128) * 8;
+
+    // M || 1 || 0* || 1 || <bit length as 128 bits>, to a multiple of 1024 bits
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 128 != 112 {
+        padded.push(0);
+    }
+    *padded.last_mut().unwrap() |= 0x01;
+    let mut len_bytes = [0u8; 16];
+    BigEndian::write_u64(&mut len_bytes[..8], (bit_len >> 64) as u64);
+    BigEndian::write_u64(&mut len_bytes[8..], bit_len as u64);
+    padded.extend_from_slice(&len_bytes[..]);
+
+    let mut h = IV;
+    for (i, block) in padded.chunks(128).enumerate() {
+        let hashed_before = (i as u128) * 1024;
+        let t = if hashed_before < bit_len {
+            ::std::cmp::min(bit_len, hashed_before + 1024)
+        } else {
+            0
+        };
+
+        compress(&mut h, block, t);
+    }
+
+    let mut out = [0u8; 64];
+    BigEndian::write_u64_into(&h, &mut out);
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blake512_vectors() {
+        assert_eq!(
+            &blake512(&[])[..],
+            &hex!("a8cfbbd73726062df0c6864dda65defe58ef0cc52a5625090fa17601e1eecd1b628e94f396ae402a00acc9eab77b4d4c2e852aaaa25a636d80af3fc7913ef5b8")[..]
+        );
+
+        // The one and two block examples of the BLAKE specification
+        assert_eq!(
+            &blake512(&[0u8])[..],
+            &hex!("97961587f6d970faba6d2478045de6d1fabd09b61ae50932054d52bc29d31be4ff9102b9f69e2bbdb83be13d4b9c06091e5fa0b48bd081b634058be0ec49beb3")[..]
+        );
+        assert_eq!(
+            &blake512(&[0u8; 144])[..],
+            &hex!("313717d608e9cf758dcb1eb0f0c3cf9fc150b2d500fb33f51c52afc99d358a2f1374b8a38bba7974e7f6ef79cab16f22ce1e649d6e01ad9589c213045d545dde")[..]
+        );
+    }
+}
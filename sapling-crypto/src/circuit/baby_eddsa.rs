@@ -37,6 +37,12 @@ use super::ecc::EdwardsPoint;
 
 use super::blake2s::{blake2s};
 
+use super::mimc7::mimc7_multi_hash;
+
+use super::poseidon::poseidon_hash;
+
+use ::eddsa::{CircomlibEddsaParams, CircomlibHash};
+
 #[derive(Clone)]
 pub struct EddsaSignature<E: JubjubEngine> {
     pub r: EdwardsPoint<E>,
@@ -50,6 +56,23 @@ use constants::{MATTER_EDDSA_BLAKE2S_PERSONALIZATION};
 
 impl <E: JubjubEngine>EddsaSignature<E> {
 
+    /// Checks that the public key is not of small order.
+    /// Verification gadgets only check the order of R, so
+    /// this should be used whenever the public key is not
+    /// guaranteed to be in the prime order subgroup by other means.
+    pub fn assert_pk_not_small_order<CS>(
+        &self,
+        mut cs: CS,
+        params: &E::Params
+    ) -> Result<(), SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        self.pk.assert_not_small_order(
+            cs.namespace(|| "PK is in right order"),
+            params
+        )
+    }
+
     pub fn verify_eddsa_for_snark<CS>(
         &self,
         mut cs: CS,
@@ -59,10 +82,10 @@ impl <E: JubjubEngine>EddsaSignature<E> {
     ) -> Result<(), SynthesisError>
         where CS: ConstraintSystem<E>
     {
-        // TODO check that s < Fs::Char
-        let scalar_bits = field_into_boolean_vec_le(
-            cs.namespace(|| "Get S bits"),
-            self.s.get_value()
+        // s must be strictly less than the order of the subgroup,
+        // otherwise s + order gives another valid signature
+        let scalar_bits = self.s.into_bits_le_strict_for_field::<E::Fs, _>(
+            cs.namespace(|| "Get S bits")
         )?;
 
         let sb = generator.mul(
@@ -76,7 +99,7 @@ impl <E: JubjubEngine>EddsaSignature<E> {
         // by some other means for out particular case
         self.r.assert_not_small_order(
             cs.namespace(|| "R is in right order"),
-            &params
+            params
         )?;
 
         let mut hash_bits: Vec<Boolean> = vec![];
@@ -145,8 +168,6 @@ impl <E: JubjubEngine>EddsaSignature<E> {
     ) -> Result<(), SynthesisError>
         where CS: ConstraintSystem<E>
     {
-        // TODO check that s < Fs::Char
-
         // message is always padded to 256 bits in this gadget, but still checked on synthesis
         assert!(message.len() <= max_message_len * 8);
 
@@ -155,7 +176,9 @@ impl <E: JubjubEngine>EddsaSignature<E> {
         //     || Ok(*self.s.get_value().get()?)
         // )?;
 
-        let scalar_bits = self.s.into_bits_le(
+        // s must be strictly less than the order of the subgroup,
+        // otherwise s + order gives another valid signature
+        let scalar_bits = self.s.into_bits_le_strict_for_field::<E::Fs, _>(
             cs.namespace(|| "Get S bits")
         )?;
 
@@ -179,7 +202,7 @@ impl <E: JubjubEngine>EddsaSignature<E> {
         // by some other means for out particular case
         self.r.assert_not_small_order(
             cs.namespace(|| "R is in right order"),
-            &params
+            params
         )?;
 
         let mut h: Vec<Boolean> = vec![];
@@ -223,18 +246,183 @@ impl <E: JubjubEngine>EddsaSignature<E> {
 
         return Ok(());
     }
-} 
+
+    /// Verifies a signature of circomlib's EdDSA-MiMC over a
+    /// single field element. `generator` is expected to be
+    /// circomlib's Base8 point.
+    pub fn verify_mimc7_signature<CS>(
+        &self,
+        cs: CS,
+        params: &E::Params,
+        message: &AllocatedNum<E>,
+        generator: EdwardsPoint<E>,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Result<(), SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        self.verify_circomlib_signature(cs, params, message, generator, circomlib_params, CircomlibHash::Mimc7)
+    }
+
+    /// Verifies a signature of circomlib's EdDSA-Poseidon over a
+    /// single field element. `generator` is expected to be
+    /// circomlib's Base8 point.
+    pub fn verify_poseidon_signature<CS>(
+        &self,
+        cs: CS,
+        params: &E::Params,
+        message: &AllocatedNum<E>,
+        generator: EdwardsPoint<E>,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Result<(), SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        self.verify_circomlib_signature(cs, params, message, generator, circomlib_params, CircomlibHash::Poseidon)
+    }
+
+    fn verify_circomlib_signature<CS>(
+        &self,
+        mut cs: CS,
+        params: &E::Params,
+        message: &AllocatedNum<E>,
+        generator: EdwardsPoint<E>,
+        circomlib_params: &CircomlibEddsaParams<E>,
+        hash: CircomlibHash
+    ) -> Result<(), SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        // s must be strictly less than the order of the subgroup,
+        // otherwise s + order gives another valid signature
+        let scalar_bits = self.s.into_bits_le_strict_for_field::<E::Fs, _>(
+            cs.namespace(|| "Get S bits")
+        )?;
+
+        let sb = generator.mul(
+            cs.namespace(|| "S*B computation"),
+            &scalar_bits,
+            params
+        )?;
+
+        self.r.assert_not_small_order(
+            cs.namespace(|| "R is in right order"),
+            params
+        )?;
+
+        // h = Hash(R_X || R_Y || PK_X || PK_Y || message) with
+        // x coordinates as seen by circomlib
+        let r_x = scale_x(cs.namespace(|| "Scale R_X"), self.r.get_x(), circomlib_params.x_scaling())?;
+        let pk_x = scale_x(cs.namespace(|| "Scale PK_X"), self.pk.get_x(), circomlib_params.x_scaling())?;
+
+        let hash_inputs = [r_x, self.r.get_y().clone(), pk_x, self.pk.get_y().clone(), message.clone()];
+
+        let h = match hash {
+            CircomlibHash::Mimc7 => {
+                let key = AllocatedNum::alloc(cs.namespace(|| "Allocate zero key"), || Ok(E::Fr::zero()))?;
+
+                cs.enforce(
+                    || "key is zero",
+                    |lc| lc + key.get_variable(),
+                    |lc| lc + CS::one(),
+                    |lc| lc
+                );
+
+                mimc7_multi_hash(
+                    cs.namespace(|| "Calculate EdDSA hash"),
+                    &hash_inputs,
+                    &key,
+                    circomlib_params.mimc7_params()
+                )?
+            },
+            CircomlibHash::Poseidon => {
+                poseidon_hash(
+                    cs.namespace(|| "Calculate EdDSA hash"),
+                    &hash_inputs,
+                    circomlib_params.poseidon_params()
+                )?
+            }
+        };
+
+        let h_bits = h.into_bits_le_strict(cs.namespace(|| "Get hash bits"))?;
+
+        // 8*PK is in the prime order subgroup, so multiplying
+        // it by h is the same as by h reduced modulo its order
+        let pk_mul_cofactor = self.pk.double(cs.namespace(|| "Calculate 2*PK"), params)?;
+        let pk_mul_cofactor = pk_mul_cofactor.double(cs.namespace(|| "Calculate 4*PK"), params)?;
+        let pk_mul_cofactor = pk_mul_cofactor.double(cs.namespace(|| "Calculate 8*PK"), params)?;
+
+        let pk_mul_hash = pk_mul_cofactor.mul(
+            cs.namespace(|| "Calculate h*8*PK"),
+            &h_bits,
+            params
+        )?;
+
+        let rhs = pk_mul_hash.add(
+            cs.namespace(|| "Make signature RHS"),
+            &self.r,
+            params
+        )?;
+
+        let rhs_x = rhs.get_x();
+        let rhs_y = rhs.get_y();
+
+        let sb_x = sb.get_x();
+        let sb_y = sb.get_y();
+
+        let one = CS::one();
+        cs.enforce(
+            || "check x coordinate of signature",
+            |lc| lc + rhs_x.get_variable(),
+            |lc| lc + one,
+            |lc| lc + sb_x.get_variable()
+        );
+
+        cs.enforce(
+            || "check y coordinate of signature",
+            |lc| lc + rhs_y.get_variable(),
+            |lc| lc + one,
+            |lc| lc + sb_y.get_variable()
+        );
+
+        Ok(())
+    }
+}
+
+/// Multiplies x coordinate by a constant to move it
+/// to circomlib's curve representation
+fn scale_x<E, CS>(
+    mut cs: CS,
+    x: &AllocatedNum<E>,
+    scaling: &E::Fr
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: JubjubEngine,
+          CS: ConstraintSystem<E>
+{
+    let scaled = AllocatedNum::alloc(cs.namespace(|| "scaled x"), || {
+        let mut tmp = *x.get_value().get()?;
+        tmp.mul_assign(scaling);
+
+        Ok(tmp)
+    })?;
+
+    cs.enforce(
+        || "scaling constraint",
+        |lc| lc + (*scaling, x.get_variable()),
+        |lc| lc + CS::one(),
+        |lc| lc + scaled.get_variable()
+    );
+
+    Ok(scaled)
+}
 
 
 #[cfg(test)]
 mod test {
-    use ::eddsa::{PrivateKey, PublicKey};
+    use ::eddsa::{PrivateKey, PublicKey, CircomlibEddsaParams, CircomlibHash};
     use rand::{SeedableRng, Rng, XorShiftRng};
     use super::*;
     use ::circuit::test::*;
     use ::circuit::boolean::{Boolean, AllocatedBit};
     use pairing::bn256::{Bn256, Fr};
-    use ff::{PrimeField, PrimeFieldRepr};
+    use ff::{Field, PrimeField, PrimeFieldRepr};
     use ::alt_babyjubjub::AltJubjubBn256;
     
     #[test]
@@ -357,6 +545,220 @@ mod test {
         print!("EdDSA variant raw message signature takes constraints: {}\n", cs.num_constraints());
     }
 
-}
+    fn message_into_booleans<CS: ConstraintSystem<Bn256>>(mut cs: CS, msg: &[u8]) -> Vec<Boolean> {
+        let mut input_bools = vec![];
+
+        for (i, b) in msg.iter().enumerate() {
+            for j in 0..8 {
+                input_bools.push(Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("input {}", i * 8 + j)), Some(b & (1 << j) != 0)).unwrap()
+                ));
+            }
+        }
 
+        input_bools
+    }
+
+    /// Returns S as an element of Fr, optionally adding
+    /// the subgroup order to it to malleate the signature
+    fn s_into_fr(s: Fs, add_order: bool) -> Fr {
+        let mut sigs_bytes = [0u8; 32];
+        s.into_repr().write_le(& mut sigs_bytes[..]).expect("get LE bytes of signature S");
+        let mut sigs_repr = <Fr as PrimeField>::Repr::from(0);
+        sigs_repr.read_le(&sigs_bytes[..]).expect("interpret S as field element representation");
+
+        if add_order {
+            let mut order_bytes = [0u8; 32];
+            Fs::char().write_le(& mut order_bytes[..]).expect("get LE bytes of subgroup order");
+            let mut order_repr = <Fr as PrimeField>::Repr::from(0);
+            order_repr.read_le(&order_bytes[..]).expect("interpret order as field element representation");
 
+            sigs_repr.add_nocarry(&order_repr);
+        }
+
+        Fr::from_repr(sigs_repr).unwrap()
+    }
+
+    #[test]
+    fn test_malleated_for_snark_signatures() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+        let sk = PrivateKey::<Bn256>(rng.gen());
+        let vk = PublicKey::from_private(&sk, p_g, params);
+
+        let msg1 = b"Foo bar pad to16"; // 16 bytes
+
+        let sig1 = sk.sign_for_snark(msg1, &mut rng, p_g, params);
+        assert!(vk.verify_for_snark(msg1, &sig1, p_g, params));
+
+        for &malleate in &[false, true] {
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let input_bools = message_into_booleans(cs.namespace(|| "message"), msg1);
+
+            let s = AllocatedNum::alloc(cs.namespace(|| "allocate s"), || {
+                    Ok(s_into_fr(sig1.s, malleate))
+                }
+            ).unwrap();
+
+            let public_generator = params.generator(FixedGenerators::SpendingKeyGenerator).clone();
+
+            let generator = EdwardsPoint::witness(cs.namespace(|| "allocate public generator"), Some(public_generator), params).unwrap();
+
+            let r = EdwardsPoint::witness(cs.namespace(|| "allocate r"), Some(sig1.r.clone()), params).unwrap();
+
+            let pk = EdwardsPoint::witness(cs.namespace(|| "allocate pk"), Some(vk.0.clone()), params).unwrap();
+
+            let signature = EddsaSignature{r, s, pk};
+            signature.verify_eddsa_for_snark(cs.namespace(|| "verify signature"), params, &input_bools, generator).expect("succesfully generated verifying gadget");
+
+            assert_eq!(cs.is_satisfied(), !malleate);
+        }
+    }
+
+    #[test]
+    fn test_malleated_raw_message_signatures() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+        let sk = PrivateKey::<Bn256>(rng.gen());
+        let vk = PublicKey::from_private(&sk, p_g, params);
+
+        let msg1 = b"Foo bar pad to16"; // 16 bytes
+
+        let sig1 = sk.sign_raw_message(msg1, &mut rng, p_g, params, 16);
+        assert!(vk.verify_for_raw_message(msg1, &sig1, p_g, params, 16));
+
+        for &malleate in &[false, true] {
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let input_bools = message_into_booleans(cs.namespace(|| "message"), msg1);
+
+            let s = AllocatedNum::alloc(cs.namespace(|| "allocate s"), || {
+                    Ok(s_into_fr(sig1.s, malleate))
+                }
+            ).unwrap();
+
+            let public_generator = params.generator(FixedGenerators::SpendingKeyGenerator).clone();
+
+            let generator = EdwardsPoint::witness(cs.namespace(|| "allocate public generator"), Some(public_generator), params).unwrap();
+
+            let r = EdwardsPoint::witness(cs.namespace(|| "allocate r"), Some(sig1.r.clone()), params).unwrap();
+
+            let pk = EdwardsPoint::witness(cs.namespace(|| "allocate pk"), Some(vk.0.clone()), params).unwrap();
+
+            let signature = EddsaSignature{r, s, pk};
+            signature.verify_raw_message_signature(cs.namespace(|| "verify signature"), params, &input_bools, generator, 16).expect("succesfully generated verifying gadget");
+
+            assert_eq!(cs.is_satisfied(), !malleate);
+        }
+    }
+
+    #[test]
+    fn test_pk_small_order() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+        let sk = PrivateKey::<Bn256>(rng.gen());
+        let vk = PublicKey::from_private(&sk, p_g, params);
+
+        let msg1 = b"Foo bar pad to16"; // 16 bytes
+        let sig1 = sk.sign_for_snark(msg1, &mut rng, p_g, params);
+
+        // (0, -1) is a point of order two
+        let mut minus_one = Fr::one();
+        minus_one.negate();
+        let small_order_point = edwards::Point::<Bn256, _>::get_for_y(minus_one, false, params).unwrap();
+
+        {
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let s = AllocatedNum::alloc(cs.namespace(|| "allocate s"), || Ok(s_into_fr(sig1.s, false))).unwrap();
+            let r = EdwardsPoint::witness(cs.namespace(|| "allocate r"), Some(sig1.r.clone()), params).unwrap();
+            let pk = EdwardsPoint::witness(cs.namespace(|| "allocate pk"), Some(vk.0.clone()), params).unwrap();
+
+            let signature = EddsaSignature{r, s, pk};
+            signature.assert_pk_not_small_order(cs.namespace(|| "check pk"), params).unwrap();
+
+            assert!(cs.is_satisfied());
+        }
+
+        {
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let s = AllocatedNum::alloc(cs.namespace(|| "allocate s"), || Ok(s_into_fr(sig1.s, false))).unwrap();
+            let r = EdwardsPoint::witness(cs.namespace(|| "allocate r"), Some(sig1.r.clone()), params).unwrap();
+            let pk = EdwardsPoint::witness(cs.namespace(|| "allocate pk"), Some(small_order_point), params).unwrap();
+
+            let signature = EddsaSignature{r, s, pk};
+
+            // the inverse of zero can not be witnessed
+            assert!(signature.assert_pk_not_small_order(cs.namespace(|| "check pk"), params).is_err());
+        }
+    }
+
+
+    fn circomlib_signature_circuit(
+        hash: CircomlibHash,
+        msg: Fr,
+        s: Fr
+    ) -> TestConstraintSystem<Bn256> {
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+
+        // Signature of circomlib's test vector
+        let pk = circomlib_params.from_circomlib_xy(
+            Fr::from_str("13277427435165878497778222415993513565335242147425444199013288855685581939618").unwrap(),
+            Fr::from_str("13622229784656158136036771217484571176836296686641868549125388198837476602820").unwrap(),
+            params
+        ).unwrap();
+        let r = circomlib_params.from_circomlib_xy(
+            Fr::from_str("11384336176656855268977457483345535180380036354188103142384839473266348197733").unwrap(),
+            Fr::from_str("15383486972088797283337779941324724402501462225528836549661220478783371668959").unwrap(),
+            params
+        ).unwrap();
+
+        let message = AllocatedNum::alloc(cs.namespace(|| "allocate message"), || Ok(msg)).unwrap();
+        let s = AllocatedNum::alloc(cs.namespace(|| "allocate s"), || Ok(s)).unwrap();
+        let generator = EdwardsPoint::witness(cs.namespace(|| "allocate generator"), Some(circomlib_params.generator().clone()), params).unwrap();
+        let r = EdwardsPoint::witness(cs.namespace(|| "allocate r"), Some(r), params).unwrap();
+        let pk = EdwardsPoint::witness(cs.namespace(|| "allocate pk"), Some(pk), params).unwrap();
+
+        let signature = EddsaSignature{r, s, pk};
+        match hash {
+            CircomlibHash::Mimc7 => signature.verify_mimc7_signature(
+                cs.namespace(|| "verify signature"), params, &message, generator, &circomlib_params
+            ).unwrap(),
+            CircomlibHash::Poseidon => signature.verify_poseidon_signature(
+                cs.namespace(|| "verify signature"), params, &message, generator, &circomlib_params
+            ).unwrap()
+        }
+
+        cs
+    }
+
+    #[test]
+    fn test_circomlib_signatures() {
+        let msg = Fr::from_str("42649378395939397566720").unwrap();
+        let s_mimc7 = Fr::from_str("2523202440825208709475937830811065542425109372212752003460238913256192595070").unwrap();
+        let s_poseidon = Fr::from_str("248298168863866362217836334079793350221620631973732197668910946177382043688").unwrap();
+
+        let cs = circomlib_signature_circuit(CircomlibHash::Mimc7, msg, s_mimc7);
+        assert!(cs.is_satisfied());
+        print!("EdDSA-MiMC verification takes constraints: {}\n", cs.num_constraints());
+
+        let cs = circomlib_signature_circuit(CircomlibHash::Poseidon, msg, s_poseidon);
+        assert!(cs.is_satisfied());
+        print!("EdDSA-Poseidon verification takes constraints: {}\n", cs.num_constraints());
+
+        let cs = circomlib_signature_circuit(CircomlibHash::Mimc7, msg, s_poseidon);
+        assert!(!cs.is_satisfied());
+
+        let mut wrong_msg = msg;
+        wrong_msg.add_assign(&Fr::one());
+        let cs = circomlib_signature_circuit(CircomlibHash::Poseidon, wrong_msg, s_poseidon);
+        assert!(!cs.is_satisfied());
+    }
+}
@@ -1,119 +1,14 @@
-use super::*;
-use super::baby_ecc::{
-    MontgomeryPoint,
-    EdwardsPoint
-};
-use super::boolean::Boolean;
-use ::babyjubjub::*;
-use bellman::{
-    ConstraintSystem
-};
-use super::lookup::*;
-pub use baby_pedersen_hash::Personalization;
-
-impl Personalization {
-    fn get_constant_bools(&self) -> Vec<Boolean> {
-        self.get_bits()
-        .into_iter()
-        .map(|e| Boolean::constant(e))
-        .collect()
-    }
-}
-
-pub fn pedersen_hash<E: JubjubEngine, CS>(
-    mut cs: CS,
-    personalization: Personalization,
-    bits: &[Boolean],
-    params: &E::Params
-) -> Result<EdwardsPoint<E>, SynthesisError>
-    where CS: ConstraintSystem<E>
-{
-    let personalization = personalization.get_constant_bools();
-    assert_eq!(personalization.len(), 6);
-
-    let mut edwards_result = None;
-    let mut bits = personalization.iter().chain(bits.iter());
-    let mut segment_generators = params.pedersen_circuit_generators().iter();
-    let boolean_false = Boolean::constant(false);
-
-    let mut segment_i = 0;
-    loop {
-        let mut segment_result = None;
-        let mut segment_windows = &segment_generators.next()
-                                                     .expect("enough segments")[..];
-
-        let mut window_i = 0;
-        while let Some(a) = bits.next() {
-            let b = bits.next().unwrap_or(&boolean_false);
-            let c = bits.next().unwrap_or(&boolean_false);
-
-            let tmp = lookup3_xy_with_conditional_negation(
-                cs.namespace(|| format!("segment {}, window {}", segment_i, window_i)),
-                &[a.clone(), b.clone(), c.clone()],
-                &segment_windows[0]
-            )?;
-
-            let tmp = MontgomeryPoint::interpret_unchecked(tmp.0, tmp.1);
-
-            match segment_result {
-                None => {
-                    segment_result = Some(tmp);
-                },
-                Some(ref mut segment_result) => {
-                    *segment_result = tmp.add(
-                        cs.namespace(|| format!("addition of segment {}, window {}", segment_i, window_i)),
-                        segment_result,
-                        params
-                    )?;
-                }
-            }
-
-            segment_windows = &segment_windows[1..];
-
-            if segment_windows.len() == 0 {
-                break;
-            }
-
-            window_i += 1;
-        }
-
-        match segment_result {
-            Some(segment_result) => {
-                // Convert this segment into twisted Edwards form.
-                let segment_result = segment_result.into_edwards(
-                    cs.namespace(|| format!("conversion of segment {} into edwards", segment_i)),
-                    params
-                )?;
-
-                match edwards_result {
-                    Some(ref mut edwards_result) => {
-                        *edwards_result = segment_result.add(
-                            cs.namespace(|| format!("addition of segment {} to accumulator", segment_i)),
-                            edwards_result,
-                            params
-                        )?;
-                    },
-                    None => {
-                        edwards_result = Some(segment_result);
-                    }
-                }
-            },
-            None => {
-                // We didn't process any new bits.
-                break;
-            }
-        }
-
-        segment_i += 1;
-    }
+//! Pedersen hash gadget for Baby Jubjub is the same as for the
+//! other curves, given `babyjubjub::JubjubBn256` as parameters.
 
-    Ok(edwards_result.unwrap())
-}
+pub use super::pedersen_hash::*;
 
 #[cfg(test)]
 mod test {
     use rand::{SeedableRng, Rng, XorShiftRng};
     use super::*;
+    use bellman::ConstraintSystem;
+    use ::babyjubjub::JubjubBn256;
     use ::circuit::test::*;
     use ::circuit::boolean::{Boolean, AllocatedBit};
     use pairing::bn256::{Bn256, Fr};
@@ -170,7 +65,7 @@ mod test {
 
                 assert!(cs.is_satisfied());
 
-                let expected = ::baby_pedersen_hash::pedersen_hash::<Bn256, _>(
+                let expected = ::baby_pedersen_hash::pedersen_hash::<Bn256, _, _>(
                     Personalization::MerkleTree(1),
                     input.clone().into_iter(),
                     params
@@ -180,7 +75,7 @@ mod test {
                 assert_eq!(res.get_y().get_value().unwrap(), expected.1);
 
                 // Test against the output of a different personalization
-                let unexpected = ::baby_pedersen_hash::pedersen_hash::<Bn256, _>(
+                let unexpected = ::baby_pedersen_hash::pedersen_hash::<Bn256, _, _>(
                     Personalization::MerkleTree(0),
                     input.into_iter(),
                     params
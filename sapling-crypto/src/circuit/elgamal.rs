@@ -0,0 +1,265 @@
+use bellman::{
+    SynthesisError,
+    ConstraintSystem
+};
+
+use jubjub::{
+    JubjubEngine,
+    FixedGenerators
+};
+
+use super::ecc::{
+    EdwardsPoint,
+    fixed_base_multiplication
+};
+
+use super::boolean::Boolean;
+
+#[derive(Clone)]
+pub struct Ciphertext<E: JubjubEngine> {
+    /// r * G
+    pub c1: EdwardsPoint<E>,
+    /// M + r * PK
+    pub c2: EdwardsPoint<E>
+}
+
+impl<E: JubjubEngine> Ciphertext<E> {
+    /// Encrypts `message` to `pk`, given the bits of the
+    /// randomness in little-endian order. The public key
+    /// is not checked to be in the prime order subgroup.
+    pub fn encrypt<CS>(
+        mut cs: CS,
+        message: &EdwardsPoint<E>,
+        pk: &EdwardsPoint<E>,
+        randomness: &[Boolean],
+        generator: FixedGenerators,
+        params: &E::Params
+    ) -> Result<Self, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        let c1 = fixed_base_multiplication(
+            cs.namespace(|| "c1 computation"),
+            generator,
+            randomness,
+            params
+        )?;
+
+        let r_pk = pk.mul_windowed(
+            cs.namespace(|| "r*PK computation"),
+            randomness,
+            params
+        )?;
+
+        let c2 = r_pk.add(
+            cs.namespace(|| "c2 computation"),
+            message,
+            params
+        )?;
+
+        Ok(Ciphertext {
+            c1: c1,
+            c2: c2
+        })
+    }
+
+    /// Decrypts the message given the bits of the private
+    /// key in little-endian order.
+    pub fn decrypt<CS>(
+        &self,
+        mut cs: CS,
+        sk: &[Boolean],
+        params: &E::Params
+    ) -> Result<EdwardsPoint<E>, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        let shared = ecdh(
+            cs.namespace(|| "sk*c1 computation"),
+            sk,
+            &self.c1,
+            params
+        )?;
+
+        let shared = shared.negate(cs.namespace(|| "negation"))?;
+
+        self.c2.add(
+            cs.namespace(|| "message computation"),
+            &shared,
+            params
+        )
+    }
+
+    pub fn inputize<CS>(
+        &self,
+        mut cs: CS
+    ) -> Result<(), SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        self.c1.inputize(cs.namespace(|| "c1"))?;
+        self.c2.inputize(cs.namespace(|| "c2"))?;
+
+        Ok(())
+    }
+}
+
+/// Encodes a number given as little-endian bits
+/// as the multiple of the generator.
+pub fn encode_message<E, CS>(
+    mut cs: CS,
+    message: &[Boolean],
+    generator: FixedGenerators,
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where E: JubjubEngine,
+          CS: ConstraintSystem<E>
+{
+    fixed_base_multiplication(
+        cs.namespace(|| "message encoding"),
+        generator,
+        message,
+        params
+    )
+}
+
+/// Derives the shared point `sk * PK` of Diffie-Hellman key
+/// agreement, given the bits of the private key in little-endian
+/// order. The public key is not checked to be in the prime order
+/// subgroup.
+pub fn ecdh<E, CS>(
+    mut cs: CS,
+    sk: &[Boolean],
+    pk: &EdwardsPoint<E>,
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where E: JubjubEngine,
+          CS: ConstraintSystem<E>
+{
+    pk.mul_windowed(
+        cs.namespace(|| "sk*PK computation"),
+        sk,
+        params
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use bellman::ConstraintSystem;
+    use rand::{XorShiftRng, SeedableRng, Rng};
+    use pairing::bn256::{Bn256, Fr};
+    use ff::{BitIterator, Field, PrimeField};
+    use ::circuit::test::*;
+    use ::circuit::boolean::{Boolean, AllocatedBit};
+    use ::alt_babyjubjub::{AltJubjubBn256, fs::Fs, edwards};
+    use ::jubjub::FixedGenerators;
+    use ::eddsa::{PrivateKey, PublicKey};
+    use ::elgamal;
+    use super::*;
+
+    fn scalar_into_booleans<CS: ConstraintSystem<Bn256>>(mut cs: CS, s: Fs) -> Vec<Boolean> {
+        let mut s_bits = BitIterator::new(s.into_repr()).collect::<Vec<_>>();
+        s_bits.reverse();
+        s_bits.truncate(Fs::NUM_BITS as usize);
+
+        s_bits.into_iter()
+              .enumerate()
+              .map(|(i, b)| AllocatedBit::alloc(cs.namespace(|| format!("bit {}", i)), Some(b)).unwrap())
+              .map(|v| Boolean::from(v))
+              .collect()
+    }
+
+    #[test]
+    fn test_encryption() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        for _ in 0..5 {
+            let sk = PrivateKey::<Bn256>(rng.gen());
+            let pk = PublicKey::from_private(&sk, p_g, params);
+            let vote: bool = rng.gen();
+            let r: Fs = rng.gen();
+
+            let expected = elgamal::Ciphertext::encrypt_with_randomness(
+                &elgamal::encode_message(vote as u64, p_g, params),
+                &pk,
+                r,
+                p_g,
+                params
+            );
+
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let pk_var = EdwardsPoint::witness(cs.namespace(|| "pk"), Some(pk.0.clone()), params).unwrap();
+            let vote_var = Boolean::from(AllocatedBit::alloc(cs.namespace(|| "vote"), Some(vote)).unwrap());
+            let r_bits = scalar_into_booleans(cs.namespace(|| "r"), r);
+            let sk_bits = scalar_into_booleans(cs.namespace(|| "sk"), sk.0);
+
+            let message = encode_message(cs.namespace(|| "encode"), &[vote_var], p_g, params).unwrap();
+            let c = Ciphertext::encrypt(cs.namespace(|| "encrypt"), &message, &pk_var, &r_bits, p_g, params).unwrap();
+            let decrypted = c.decrypt(cs.namespace(|| "decrypt"), &sk_bits, params).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            let (c1_x, c1_y) = expected.c1.into_xy();
+            let (c2_x, c2_y) = expected.c2.into_xy();
+            assert_eq!(c.c1.get_x().get_value().unwrap(), c1_x);
+            assert_eq!(c.c1.get_y().get_value().unwrap(), c1_y);
+            assert_eq!(c.c2.get_x().get_value().unwrap(), c2_x);
+            assert_eq!(c.c2.get_y().get_value().unwrap(), c2_y);
+
+            assert_eq!(decrypted.get_x().get_value(), message.get_x().get_value());
+            assert_eq!(decrypted.get_y().get_value(), message.get_y().get_value());
+
+            // A different vote changes the ciphertext
+            cs.set("vote/boolean", if vote { Fr::zero() } else { Fr::one() });
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_ecdh() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        for _ in 0..5 {
+            let sk_a = PrivateKey::<Bn256>(rng.gen());
+            let sk_b = PrivateKey::<Bn256>(rng.gen());
+            let pk_b = PublicKey::from_private(&sk_b, p_g, params);
+
+            let expected = elgamal::ecdh(&sk_a, &pk_b, params);
+
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let pk_var = EdwardsPoint::witness(cs.namespace(|| "pk"), Some(pk_b.0.clone()), params).unwrap();
+            let sk_bits = scalar_into_booleans(cs.namespace(|| "sk"), sk_a.0);
+
+            let shared = ecdh(cs.namespace(|| "ecdh"), &sk_bits, &pk_var, params).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            let (x, y) = expected.into_xy();
+            assert_eq!(shared.get_x().get_value().unwrap(), x);
+            assert_eq!(shared.get_y().get_value().unwrap(), y);
+        }
+    }
+
+    #[test]
+    fn test_negation() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &AltJubjubBn256::new();
+
+        let p = edwards::Point::<Bn256, _>::rand(rng, params);
+        let (x, y) = p.negate().into_xy();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let p_var = EdwardsPoint::witness(cs.namespace(|| "p"), Some(p), params).unwrap();
+        let neg = p_var.negate(cs.namespace(|| "negate")).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_eq!(neg.get_x().get_value().unwrap(), x);
+        assert_eq!(neg.get_y().get_value().unwrap(), y);
+
+        cs.set("negate/-x/num", rng.gen());
+        assert_eq!(cs.which_is_unsatisfied(), Some("negate/negation"));
+    }
+}
@@ -7,7 +7,8 @@ use super::num::{
 };
 use super::boolean::Boolean;
 use bellman::{
-    ConstraintSystem
+    ConstraintSystem,
+    LinearCombination
 };
 
 // Synthesize the constants for each base pattern.
@@ -121,6 +122,152 @@ pub fn lookup3_xy<E: Engine, CS>(
     Ok((res_x, res_y))
 }
 
+/// Performs a 3-bit window table lookup like `lookup3_xy`,
+/// for a table whose coordinates are linear combinations
+/// of variables instead of constants. `bits` is in
+/// little-endian order.
+pub fn lookup3_xy_variable<E: Engine, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    coords: &[(Num<E>, Num<E>)]
+) -> Result<(AllocatedNum<E>, AllocatedNum<E>), SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    assert_eq!(bits.len(), 3);
+    assert_eq!(coords.len(), 8);
+
+    let precomp = Boolean::and(cs.namespace(|| "precomp"), &bits[1], &bits[2])?;
+
+    let xs = coords.iter().map(|c| &c.0).collect::<Vec<_>>();
+    let ys = coords.iter().map(|c| &c.1).collect::<Vec<_>>();
+
+    let res_x = lookup3_variable(cs.namespace(|| "x"), bits, &precomp, &xs)?;
+    let res_y = lookup3_variable(cs.namespace(|| "y"), bits, &precomp, &ys)?;
+
+    Ok((res_x, res_y))
+}
+
+/// Looks up one coordinate of `lookup3_xy_variable`, with
+/// the same constraint as `lookup3_xy`, whose products of
+/// the bits with the coefficients are allocated since the
+/// coefficients are not constant.
+fn lookup3_variable<E: Engine, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    precomp: &Boolean,
+    values: &[&Num<E>]
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    let one = CS::one();
+
+    // The coefficients of the lookup polynomial are the
+    // alternating sums of the values of the subsets of
+    // their index, as computed by `synth` for constants
+    let coeffs = (0..8usize).map(|i| {
+        let mut lc = LinearCombination::<E>::zero();
+        let mut value = Some(E::Fr::zero());
+
+        for j in (0..8).filter(|j| j & i == *j) {
+            let negative = (i ^ j).count_ones() % 2 == 1;
+
+            let mut coeff = E::Fr::one();
+            if negative {
+                coeff.negate();
+            }
+            lc = lc + &values[j].lc(coeff);
+
+            value = match (value, values[j].get_value()) {
+                (Some(mut acc), Some(v)) => {
+                    if negative {
+                        acc.sub_assign(&v);
+                    } else {
+                        acc.add_assign(&v);
+                    }
+                    Some(acc)
+                },
+                _ => None
+            };
+        }
+
+        (lc, value)
+    }).collect::<Vec<_>>();
+
+    // Allocates the product of the boolean and the coefficient
+    let mut product = |name: &str, bit: &Boolean, i: usize| -> Result<AllocatedNum<E>, SynthesisError> {
+        let &(ref lc, value) = &coeffs[i];
+
+        let res = AllocatedNum::alloc(
+            cs.namespace(|| format!("{} product", name)),
+            || {
+                if *bit.get_value().get()? {
+                    Ok(*value.get()?)
+                } else {
+                    Ok(E::Fr::zero())
+                }
+            }
+        )?;
+
+        cs.enforce(
+            || format!("{} product constraint", name),
+            |_| lc.clone(),
+            |lc| lc + &bit.lc::<E>(one, E::Fr::one()),
+            |lc| lc + res.get_variable()
+        );
+
+        Ok(res)
+    };
+
+    let p_011 = product("011", &bits[1], 0b011)?;
+    let p_101 = product("101", &bits[2], 0b101)?;
+    let p_111 = product("111", precomp, 0b111)?;
+    let p_010 = product("010", &bits[1], 0b010)?;
+    let p_100 = product("100", &bits[2], 0b100)?;
+    let p_110 = product("110", precomp, 0b110)?;
+
+    // Calculate the index into `values`
+    let i =
+    match (bits[0].get_value(), bits[1].get_value(), bits[2].get_value()) {
+        (Some(a_value), Some(b_value), Some(c_value)) => {
+            let mut tmp = 0;
+            if a_value {
+                tmp += 1;
+            }
+            if b_value {
+                tmp += 2;
+            }
+            if c_value {
+                tmp += 4;
+            }
+            Some(tmp)
+        },
+        _ => None
+    };
+
+    let res = AllocatedNum::alloc(
+        cs.namespace(|| "lookup"),
+        || {
+            Ok(*values[*i.get()?].get_value().get()?)
+        }
+    )?;
+
+    cs.enforce(
+        || "lookup constraint",
+        |lc| lc + &coeffs[0b001].0
+                + p_011.get_variable()
+                + p_101.get_variable()
+                + p_111.get_variable(),
+        |lc| lc + &bits[0].lc::<E>(one, E::Fr::one()),
+        |lc| lc + res.get_variable()
+                - &coeffs[0b000].0
+                - p_010.get_variable()
+                - p_100.get_variable()
+                - p_110.get_variable(),
+    );
+
+    Ok(res)
+}
+
 /// Performs a 3-bit window table lookup, where
 /// one of the bits is a sign bit.
 pub fn lookup3_xy_with_conditional_negation<E: Engine, CS>(
@@ -241,6 +388,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_lookup3_xy_variable() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0655]);
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Bls12>::new();
+
+            let bits_val: Vec<bool> = (0..3).map(|_| rng.gen()).collect();
+            let bits = bits_val.iter().enumerate().map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("bit {}", i)), Some(*b)).unwrap()
+                )
+            }).collect::<Vec<_>>();
+
+            let points: Vec<(Fr, Fr)> = (0..8).map(|_| (rng.gen(), rng.gen())).collect();
+            let coords = points.iter().enumerate().map(|(i, &(x, y))| {
+                let x = AllocatedNum::alloc(cs.namespace(|| format!("x {}", i)), || Ok(x)).unwrap();
+                let y = AllocatedNum::alloc(cs.namespace(|| format!("y {}", i)), || Ok(y)).unwrap();
+
+                (Num::from(x), Num::from(y))
+            }).collect::<Vec<_>>();
+
+            let num_constraints = cs.num_constraints();
+            let res = lookup3_xy_variable(cs.namespace(|| "lookup"), &bits, &coords).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints() - num_constraints, 15);
+
+            let mut index = 0;
+            if bits_val[0] { index += 1 }
+            if bits_val[1] { index += 2 }
+            if bits_val[2] { index += 4 }
+
+            assert_eq!(res.0.get_value().unwrap(), points[index].0);
+            assert_eq!(res.1.get_value().unwrap(), points[index].1);
+
+            cs.set("lookup/x/lookup/num", rng.gen());
+            assert!(!cs.is_satisfied());
+        }
+    }
+
     #[test]
     fn test_lookup3_xy_with_conditional_negation() {
         let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
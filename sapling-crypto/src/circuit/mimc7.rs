@@ -0,0 +1,187 @@
+use pairing::Engine;
+use ff::Field;
+
+use bellman::{
+    SynthesisError,
+    ConstraintSystem,
+    LinearCombination
+};
+
+use super::Assignment;
+use super::num::AllocatedNum;
+
+use ::mimc7::Mimc7Params;
+
+/// Computes MiMC7 of `x` keyed by `k`, costing
+/// four constraints per round
+pub fn mimc7_hash<E, CS>(
+    mut cs: CS,
+    x: &AllocatedNum<E>,
+    k: &AllocatedNum<E>,
+    params: &Mimc7Params<E>
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let mut r_value = x.get_value();
+    let mut r_lc = LinearCombination::<E>::zero() + x.get_variable();
+
+    for (i, c) in params.round_constants().iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("round {}", i));
+
+        // t = r + k + c_i
+        let t_value = match (r_value, k.get_value()) {
+            (Some(mut r), Some(k)) => {
+                r.add_assign(&k);
+                r.add_assign(c);
+
+                Some(r)
+            },
+            _ => None
+        };
+        let t_lc = r_lc + k.get_variable() + (*c, CS::one());
+
+        let t2 = AllocatedNum::alloc(cs.namespace(|| "t^2"), || {
+            let mut tmp = *t_value.get()?;
+            tmp.square();
+
+            Ok(tmp)
+        })?;
+
+        cs.enforce(
+            || "t^2 constraint",
+            |lc| lc + &t_lc,
+            |lc| lc + &t_lc,
+            |lc| lc + t2.get_variable()
+        );
+
+        let t4 = t2.square(cs.namespace(|| "t^4"))?;
+        let t6 = t4.mul(cs.namespace(|| "t^6"), &t2)?;
+
+        let t7 = AllocatedNum::alloc(cs.namespace(|| "t^7"), || {
+            let mut tmp = *t6.get_value().get()?;
+            tmp.mul_assign(t_value.get()?);
+
+            Ok(tmp)
+        })?;
+
+        cs.enforce(
+            || "t^7 constraint",
+            |lc| lc + t6.get_variable(),
+            |lc| lc + &t_lc,
+            |lc| lc + t7.get_variable()
+        );
+
+        r_value = t7.get_value();
+        r_lc = LinearCombination::<E>::zero() + t7.get_variable();
+    }
+
+    let result = AllocatedNum::alloc(cs.namespace(|| "result"), || {
+        let mut tmp = *r_value.get()?;
+        tmp.add_assign(k.get_value().get()?);
+
+        Ok(tmp)
+    })?;
+
+    cs.enforce(
+        || "result constraint",
+        |lc| lc + &r_lc + k.get_variable(),
+        |lc| lc + CS::one(),
+        |lc| lc + result.get_variable()
+    );
+
+    Ok(result)
+}
+
+/// Hashes several numbers in Miyaguchi-Preneel mode,
+/// starting from the given key
+pub fn mimc7_multi_hash<E, CS>(
+    mut cs: CS,
+    inputs: &[AllocatedNum<E>],
+    key: &AllocatedNum<E>,
+    params: &Mimc7Params<E>
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let mut r = key.clone();
+
+    for (i, x) in inputs.iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("input {}", i));
+
+        let h = mimc7_hash(cs.namespace(|| "hash"), x, &r, params)?;
+
+        // r = r + x + H(x, r)
+        let new_r = AllocatedNum::alloc(cs.namespace(|| "accumulate"), || {
+            let mut tmp = *r.get_value().get()?;
+            tmp.add_assign(x.get_value().get()?);
+            tmp.add_assign(h.get_value().get()?);
+
+            Ok(tmp)
+        })?;
+
+        cs.enforce(
+            || "accumulation constraint",
+            |lc| lc + r.get_variable() + x.get_variable() + h.get_variable(),
+            |lc| lc + CS::one(),
+            |lc| lc + new_r.get_variable()
+        );
+
+        r = new_r;
+    }
+
+    Ok(r)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, Rng, XorShiftRng};
+    use bellman::ConstraintSystem;
+    use pairing::bn256::{Bn256, Fr};
+    use ff::Field;
+    use ::circuit::test::*;
+    use ::circuit::num::AllocatedNum;
+    use ::mimc7::{self, Mimc7Params};
+
+    #[test]
+    fn test_mimc7_hash() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = Mimc7Params::<Bn256>::new();
+
+        for _ in 0..5 {
+            let x_value: Fr = rng.gen();
+            let k_value: Fr = rng.gen();
+
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let x = AllocatedNum::alloc(cs.namespace(|| "x"), || Ok(x_value)).unwrap();
+            let k = AllocatedNum::alloc(cs.namespace(|| "k"), || Ok(k_value)).unwrap();
+
+            let h = super::mimc7_hash(cs.namespace(|| "mimc7"), &x, &k, &params).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(h.get_value().unwrap(), mimc7::mimc7_hash(&x_value, &k_value, &params));
+            assert_eq!(cs.num_constraints(), 4 * mimc7::MIMC7_ROUNDS + 1);
+        }
+    }
+
+    #[test]
+    fn test_mimc7_multi_hash() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = Mimc7Params::<Bn256>::new();
+
+        let values: Vec<Fr> = (0..5).map(|_| rng.gen()).collect();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+
+        let inputs: Vec<_> = values.iter().enumerate().map(|(i, v)| {
+            AllocatedNum::alloc(cs.namespace(|| format!("input {}", i)), || Ok(*v)).unwrap()
+        }).collect();
+        let key = AllocatedNum::alloc(cs.namespace(|| "key"), || Ok(Fr::zero())).unwrap();
+
+        let h = super::mimc7_multi_hash(cs.namespace(|| "mimc7"), &inputs, &key, &params).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_eq!(h.get_value().unwrap(), mimc7::mimc7_multi_hash(&values, &Fr::zero(), &params));
+    }
+}
@@ -15,6 +15,11 @@ pub mod multipack;
 pub mod sha256;
 pub mod baby_eddsa;
 pub mod float_point;
+pub mod mimc7;
+pub mod poseidon;
+pub mod elgamal;
+pub mod sparse_merkle_tree;
+pub mod profiler;
 // pub mod shark_mimc;
 
 pub mod sapling;
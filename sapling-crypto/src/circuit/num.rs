@@ -93,9 +93,26 @@ impl<E: Engine> AllocatedNum<E> {
     /// congruency is not allowed.)
     pub fn into_bits_le_strict<CS>(
         &self,
-        mut cs: CS
+        cs: CS
     ) -> Result<Vec<Boolean>, SynthesisError>
         where CS: ConstraintSystem<E>
+    {
+        self.into_bits_le_strict_for_field::<E::Fr, CS>(cs)
+    }
+
+    /// Deconstructs this allocated number into its
+    /// boolean representation in little-endian bit
+    /// order, requiring that the number is strictly
+    /// less than the characteristic of the field `F`.
+    /// This is useful for numbers that represent
+    /// elements of a smaller field, e.g. scalars of
+    /// an embedded curve.
+    pub fn into_bits_le_strict_for_field<F, CS>(
+        &self,
+        mut cs: CS
+    ) -> Result<Vec<Boolean>, SynthesisError>
+        where F: PrimeField,
+              CS: ConstraintSystem<E>
     {
         pub fn kary_and<E, CS>(
             mut cs: CS,
@@ -125,28 +142,37 @@ impl<E: Engine> AllocatedNum<E> {
             Ok(cur.expect("v.len() > 0"))
         }
 
+        // Packing of the bits must not wrap around in our field
+        assert!(F::NUM_BITS <= E::Fr::NUM_BITS);
+
         // We want to ensure that the bit representation of a is
-        // less than or equal to r - 1.
-        let mut a = self.value.map(|e| BitIterator::new(e.into_repr()));
-        let mut b = E::Fr::char();
+        // less than or equal to char - 1.
+        let a = self.value.map(|e| e.into_repr());
+        let mut b = F::char();
         b.sub_noborrow(&1.into());
 
+        let b_len = b.as_ref().len() * 64;
+
         let mut result = vec![];
 
-        // Runs of ones in r
+        // Runs of ones in char - 1
         let mut last_run = None;
         let mut current_run = vec![];
 
         let mut found_one = false;
         let mut i = 0;
-        for b in BitIterator::new(b) {
-            let a_bit = a.as_mut().map(|e| e.next().unwrap());
+        for (position, b) in BitIterator::new(b).enumerate() {
+            let position = b_len - 1 - position;
+            let a_bit = a.as_ref().map(|e| {
+                let limbs = e.as_ref();
 
-            // Skip over unset bits at the beginning
+                position / 64 < limbs.len() && (limbs[position / 64] >> (position % 64)) & 1 == 1
+            });
+
+            // Skip over unset bits at the beginning. If a has any
+            // of them set, the unpacking constraint will not hold.
             found_one |= b;
             if !found_one {
-                // a_bit should also be false
-                a_bit.map(|e| assert!(!e));
                 continue;
             }
 
@@ -486,6 +512,154 @@ impl<E: Engine> AllocatedNum<E> {
         Ok(())
     }
 
+    /// Returns a boolean that is true if and only if
+    /// the allocated number is zero.
+    pub fn is_zero<CS>(
+        &self,
+        cs: CS
+    ) -> Result<Boolean, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        is_zero_lc(
+            cs,
+            self.value,
+            LinearCombination::<E>::zero() + self.variable
+        )
+    }
+
+    /// Returns a boolean that is true if and only if
+    /// two allocated numbers are equal.
+    pub fn is_equal<CS>(
+        cs: CS,
+        a: &Self,
+        b: &Self
+    ) -> Result<Boolean, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        let value = match (a.value, b.value) {
+            (Some(a), Some(b)) => {
+                let mut tmp = a;
+                tmp.sub_assign(&b);
+
+                Some(tmp)
+            },
+            _ => None
+        };
+
+        is_zero_lc(
+            cs,
+            value,
+            LinearCombination::<E>::zero() + a.variable - b.variable
+        )
+    }
+
+    /// Returns a boolean that is true if and only if a < b.
+    /// Both numbers MUST already be constrained to be at most
+    /// `number_of_bits` long (e.g. by `limit_number_of_bits`),
+    /// otherwise the result is meaningless.
+    pub fn less_than<CS>(
+        mut cs: CS,
+        a: &Self,
+        b: &Self,
+        number_of_bits: usize
+    ) -> Result<Boolean, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        // a - b + 2^n lies in [1, 2^(n+1)) and should not wrap around
+        assert!(number_of_bits < E::Fr::CAPACITY as usize);
+
+        let mut shift = E::Fr::one();
+        for _ in 0..number_of_bits {
+            shift.double();
+        }
+
+        let shifted_difference = match (a.value, b.value) {
+            (Some(a), Some(b)) => {
+                let mut tmp = a;
+                tmp.sub_assign(&b);
+                tmp.add_assign(&shift);
+
+                Some(tmp.into_repr())
+            },
+            _ => None
+        };
+
+        let mut packed_lc = LinearCombination::zero();
+        let mut coeff = E::Fr::one();
+        let mut top_bit = None;
+
+        for i in 0..(number_of_bits + 1) {
+            let bit = AllocatedBit::alloc(
+                cs.namespace(|| format!("bit {}", i)),
+                shifted_difference.map(|e| (e.as_ref()[i / 64] >> (i % 64)) & 1 == 1)
+            )?;
+
+            packed_lc = packed_lc + (coeff, bit.get_variable());
+            coeff.double();
+
+            top_bit = Some(bit);
+        }
+
+        cs.enforce(
+            || "unpacking constraint",
+            |lc| lc + a.variable - b.variable + (shift, CS::one()),
+            |lc| lc + CS::one(),
+            |_| packed_lc
+        );
+
+        // the top bit is set if and only if a >= b
+        let top_bit = Boolean::from(top_bit.expect("at least one bit is allocated"));
+
+        Ok(top_bit.not())
+    }
+
+    /// Returns a boolean that is true if and only if a <= b.
+    /// Has the same requirements on the inputs as `less_than`.
+    pub fn less_than_or_equal<CS>(
+        cs: CS,
+        a: &Self,
+        b: &Self,
+        number_of_bits: usize
+    ) -> Result<Boolean, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        let greater = Self::less_than(cs, b, a, number_of_bits)?;
+
+        Ok(greater.not())
+    }
+
+    /// Returns a boolean that is true if and only if lo <= x < hi.
+    /// Has the same requirements on the inputs as `less_than`.
+    pub fn is_in_range<CS>(
+        mut cs: CS,
+        x: &Self,
+        lo: &Self,
+        hi: &Self,
+        number_of_bits: usize
+    ) -> Result<Boolean, SynthesisError>
+        where CS: ConstraintSystem<E>
+    {
+        let below_lo = Self::less_than(
+            cs.namespace(|| "compare with lower bound"),
+            x,
+            lo,
+            number_of_bits
+        )?;
+
+        let below_hi = Self::less_than(
+            cs.namespace(|| "compare with upper bound"),
+            x,
+            hi,
+            number_of_bits
+        )?;
+
+        Boolean::and(
+            cs.namespace(|| "combine bounds"),
+            &below_lo.not(),
+            &below_hi
+        )
+    }
+
     pub fn get_value(&self) -> Option<E::Fr> {
         self.value
     }
@@ -495,11 +669,62 @@ impl<E: Engine> AllocatedNum<E> {
     }
 }
 
+/// Returns a boolean that is true if and only if the
+/// linear combination with the given value is zero.
+fn is_zero_lc<E, CS>(
+    mut cs: CS,
+    value: Option<E::Fr>,
+    lc: LinearCombination<E>
+) -> Result<Boolean, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let flag = AllocatedBit::alloc(
+        cs.namespace(|| "is zero flag"),
+        value.map(|e| e.is_zero())
+    )?;
+
+    let inv = cs.alloc(|| "ephemeral inverse", || {
+        let tmp = *value.get()?;
+
+        Ok(tmp.inverse().unwrap_or(E::Fr::zero()))
+    })?;
+
+    // Constrain lc * inv = 1 - flag, so the flag
+    // must be set if lc is zero.
+    cs.enforce(
+        || "inverse constraint",
+        |zero| zero + &lc,
+        |zero| zero + inv,
+        |zero| zero + CS::one() - flag.get_variable()
+    );
+
+    // Constrain lc * flag = 0, so the flag
+    // must be unset if lc is not zero.
+    cs.enforce(
+        || "zero flag constraint",
+        |zero| zero + &lc,
+        |zero| zero + flag.get_variable(),
+        |zero| zero
+    );
+
+    Ok(Boolean::from(flag))
+}
+
 pub struct Num<E: Engine> {
     value: Option<E::Fr>,
     lc: LinearCombination<E>
 }
 
+impl<E: Engine> Clone for Num<E> {
+    fn clone(&self) -> Self {
+        Num {
+            value: self.value,
+            lc: self.lc.clone()
+        }
+    }
+}
+
 impl<E: Engine> From<AllocatedNum<E>> for Num<E> {
     fn from(num: AllocatedNum<E>) -> Num<E> {
         Num {
@@ -525,6 +750,20 @@ impl<E: Engine> Num<E> {
         LinearCombination::zero() + (coeff, &self.lc)
     }
 
+    /// Multiplies this number by a constant, which
+    /// needs no constraint.
+    pub fn scale(self, coeff: E::Fr) -> Self {
+        let newval = self.value.map(|mut curval| {
+            curval.mul_assign(&coeff);
+            curval
+        });
+
+        Num {
+            value: newval,
+            lc: self.lc(coeff)
+        }
+    }
+
     pub fn add_bool_with_coeff(
         self,
         one: Variable,
@@ -693,6 +932,96 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_num_is_zero() {
+        for &v in &[0u64, 1, 5] {
+            let mut cs = TestConstraintSystem::<Bls12>::new();
+
+            let n = AllocatedNum::alloc(&mut cs, || Ok(Fr::from_str(&v.to_string()).unwrap())).unwrap();
+            let is_zero = n.is_zero(&mut cs).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(is_zero.get_value().unwrap(), v == 0);
+
+            // the flag can not be flipped
+            let flag = cs.get("is zero flag/boolean");
+            let mut flipped = Fr::one();
+            flipped.sub_assign(&flag);
+            cs.set("is zero flag/boolean", flipped);
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_num_is_equal() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        for i in 0..10 {
+            let mut cs = TestConstraintSystem::<Bls12>::new();
+
+            let a_value: Fr = rng.gen();
+            let b_value = if i % 2 == 0 { a_value } else { rng.gen() };
+
+            let a = AllocatedNum::alloc(cs.namespace(|| "a"), || Ok(a_value)).unwrap();
+            let b = AllocatedNum::alloc(cs.namespace(|| "b"), || Ok(b_value)).unwrap();
+            let is_equal = AllocatedNum::is_equal(&mut cs, &a, &b).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(is_equal.get_value().unwrap(), i % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn test_num_comparisons_exhaustive() {
+        let number_of_bits = 3;
+
+        for a_value in 0..(1u64 << number_of_bits) {
+            for b_value in 0..(1u64 << number_of_bits) {
+                let mut cs = TestConstraintSystem::<Bls12>::new();
+
+                let a = AllocatedNum::alloc(cs.namespace(|| "a"), || Ok(Fr::from_str(&a_value.to_string()).unwrap())).unwrap();
+                let b = AllocatedNum::alloc(cs.namespace(|| "b"), || Ok(Fr::from_str(&b_value.to_string()).unwrap())).unwrap();
+
+                let lt = AllocatedNum::less_than(cs.namespace(|| "lt"), &a, &b, number_of_bits).unwrap();
+                let le = AllocatedNum::less_than_or_equal(cs.namespace(|| "le"), &a, &b, number_of_bits).unwrap();
+
+                assert!(cs.is_satisfied());
+                assert_eq!(lt.get_value().unwrap(), a_value < b_value);
+                assert_eq!(le.get_value().unwrap(), a_value <= b_value);
+
+                // the comparison result can not be flipped
+                let top_bit = format!("lt/bit {}/boolean", number_of_bits);
+                let cur = cs.get(&top_bit);
+                let mut flipped = Fr::one();
+                flipped.sub_assign(&cur);
+                cs.set(&top_bit, flipped);
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_num_is_in_range_exhaustive() {
+        let number_of_bits = 3;
+
+        for x_value in 0..(1u64 << number_of_bits) {
+            for lo_value in 0..(1u64 << number_of_bits) {
+                for hi_value in 0..(1u64 << number_of_bits) {
+                    let mut cs = TestConstraintSystem::<Bls12>::new();
+
+                    let x = AllocatedNum::alloc(cs.namespace(|| "x"), || Ok(Fr::from_str(&x_value.to_string()).unwrap())).unwrap();
+                    let lo = AllocatedNum::alloc(cs.namespace(|| "lo"), || Ok(Fr::from_str(&lo_value.to_string()).unwrap())).unwrap();
+                    let hi = AllocatedNum::alloc(cs.namespace(|| "hi"), || Ok(Fr::from_str(&hi_value.to_string()).unwrap())).unwrap();
+
+                    let in_range = AllocatedNum::is_in_range(&mut cs, &x, &lo, &hi, number_of_bits).unwrap();
+
+                    assert!(cs.is_satisfied());
+                    assert_eq!(in_range.get_value().unwrap(), lo_value <= x_value && x_value < hi_value);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_into_bits_strict() {
         let mut negone = Fr::one();
@@ -20,11 +20,11 @@ impl Personalization {
     }
 }
 
-pub fn pedersen_hash<E: JubjubEngine, CS>(
+pub fn pedersen_hash<E: JubjubEngine, CS, P: JubjubParams<E>>(
     mut cs: CS,
     personalization: Personalization,
     bits: &[Boolean],
-    params: &E::Params
+    params: &P
 ) -> Result<EdwardsPoint<E>, SynthesisError>
     where CS: ConstraintSystem<E>
 {
@@ -170,7 +170,7 @@ mod test {
 
                 assert!(cs.is_satisfied());
 
-                let expected = ::pedersen_hash::pedersen_hash::<Bls12, _>(
+                let expected = ::pedersen_hash::pedersen_hash::<Bls12, _, _>(
                     Personalization::MerkleTree(1),
                     input.clone().into_iter(),
                     params
@@ -180,7 +180,7 @@ mod test {
                 assert_eq!(res.get_y().get_value().unwrap(), expected.1);
 
                 // Test against the output of a different personalization
-                let unexpected = ::pedersen_hash::pedersen_hash::<Bls12, _>(
+                let unexpected = ::pedersen_hash::pedersen_hash::<Bls12, _, _>(
                     Personalization::MerkleTree(0),
                     input.into_iter(),
                     params
@@ -254,7 +254,7 @@ mod baby_test {
 
                 assert!(cs.is_satisfied());
 
-                let expected = ::pedersen_hash::pedersen_hash::<Bn256, _>(
+                let expected = ::pedersen_hash::pedersen_hash::<Bn256, _, _>(
                     Personalization::MerkleTree(0),
                     input.clone().into_iter(),
                     params
@@ -264,7 +264,7 @@ mod baby_test {
                 assert_eq!(res.get_y().get_value().unwrap(), expected.1);
 
                 // Test against the output of a different personalization
-                let unexpected = ::pedersen_hash::pedersen_hash::<Bn256, _>(
+                let unexpected = ::pedersen_hash::pedersen_hash::<Bn256, _, _>(
                     Personalization::MerkleTree(1),
                     input.into_iter(),
                     params
@@ -0,0 +1,179 @@
+use pairing::Engine;
+use ff::Field;
+
+use bellman::{
+    SynthesisError,
+    ConstraintSystem,
+    LinearCombination
+};
+
+use super::Assignment;
+use super::num::AllocatedNum;
+
+use ::poseidon::PoseidonParams;
+
+/// Raises the linear combination with the given value
+/// to the fifth power, costing three constraints
+fn pow5<E, CS>(
+    mut cs: CS,
+    value: Option<E::Fr>,
+    lc: &LinearCombination<E>
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let x2 = AllocatedNum::alloc(cs.namespace(|| "x^2"), || {
+        let mut tmp = *value.get()?;
+        tmp.square();
+
+        Ok(tmp)
+    })?;
+
+    cs.enforce(
+        || "x^2 constraint",
+        |zero| zero + lc,
+        |zero| zero + lc,
+        |zero| zero + x2.get_variable()
+    );
+
+    let x4 = x2.square(cs.namespace(|| "x^4"))?;
+
+    let x5 = AllocatedNum::alloc(cs.namespace(|| "x^5"), || {
+        let mut tmp = *x4.get_value().get()?;
+        tmp.mul_assign(value.get()?);
+
+        Ok(tmp)
+    })?;
+
+    cs.enforce(
+        || "x^5 constraint",
+        |zero| zero + x4.get_variable(),
+        |zero| zero + lc,
+        |zero| zero + x5.get_variable()
+    );
+
+    Ok(x5)
+}
+
+/// Hashes up to `t` numbers. Missing inputs are set to zero,
+/// and the first element of the final state is the output.
+pub fn poseidon_hash<E, CS>(
+    mut cs: CS,
+    inputs: &[AllocatedNum<E>],
+    params: &PoseidonParams<E>
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    assert!(inputs.len() > 0);
+    assert!(inputs.len() <= params.t());
+
+    // State elements are kept as (value, linear combination) pairs
+    let mut state: Vec<(Option<E::Fr>, LinearCombination<E>)> = inputs.iter().map(|x| {
+        (x.get_value(), LinearCombination::zero() + x.get_variable())
+    }).collect();
+    state.resize(params.t(), (Some(E::Fr::zero()), LinearCombination::zero()));
+
+    let mut result = None;
+
+    for (round, c) in params.round_constants().iter().enumerate() {
+        let mut cs = cs.namespace(|| format!("round {}", round));
+
+        // Add round constant
+        let state_with_constant: Vec<_> = state.into_iter().map(|(value, lc)| {
+            let value = value.map(|mut v| {
+                v.add_assign(c);
+
+                v
+            });
+
+            (value, lc + (*c, CS::one()))
+        }).collect();
+
+        // Apply S-boxes
+        let mut after_sbox = Vec::with_capacity(params.t());
+        for (i, (value, lc)) in state_with_constant.into_iter().enumerate() {
+            if i == 0 || params.is_full_round(round) {
+                let x5 = pow5(cs.namespace(|| format!("s-box {}", i)), value, &lc)?;
+
+                after_sbox.push((x5.get_value(), LinearCombination::zero() + x5.get_variable()));
+            } else {
+                after_sbox.push((value, lc));
+            }
+        }
+
+        // Mix, allocating the new state to keep linear
+        // combinations short in partial rounds
+        state = Vec::with_capacity(params.t());
+        for (i, row) in params.mds_matrix().iter().enumerate() {
+            let mut lc = LinearCombination::zero();
+            let mut value = Some(E::Fr::zero());
+
+            for (m, &(ref s_value, ref s_lc)) in row.iter().zip(after_sbox.iter()) {
+                lc = lc + (*m, s_lc);
+                value = match (value, *s_value) {
+                    (Some(mut acc), Some(mut s)) => {
+                        s.mul_assign(m);
+                        acc.add_assign(&s);
+
+                        Some(acc)
+                    },
+                    _ => None
+                };
+            }
+
+            let mixed = AllocatedNum::alloc(cs.namespace(|| format!("mix {}", i)), || {
+                Ok(*value.get()?)
+            })?;
+
+            cs.enforce(
+                || format!("mix {} constraint", i),
+                |zero| zero + &lc,
+                |zero| zero + CS::one(),
+                |zero| zero + mixed.get_variable()
+            );
+
+            state.push((mixed.get_value(), LinearCombination::zero() + mixed.get_variable()));
+
+            if i == 0 {
+                result = Some(mixed);
+            }
+        }
+    }
+
+    Ok(result.expect("there is at least one round"))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, Rng, XorShiftRng};
+    use bellman::ConstraintSystem;
+    use pairing::bn256::{Bn256, Fr};
+    use ::circuit::test::*;
+    use ::circuit::num::AllocatedNum;
+    use ::poseidon::{self, PoseidonParams};
+
+    #[test]
+    fn test_poseidon_hash() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = PoseidonParams::<Bn256>::circomlib();
+
+        for num_inputs in 1..(params.t() + 1) {
+            let values: Vec<Fr> = (0..num_inputs).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let inputs: Vec<_> = values.iter().enumerate().map(|(i, v)| {
+                AllocatedNum::alloc(cs.namespace(|| format!("input {}", i)), || Ok(*v)).unwrap()
+            }).collect();
+
+            let h = super::poseidon_hash(cs.namespace(|| "poseidon"), &inputs, &params).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(h.get_value().unwrap(), poseidon::poseidon_hash(&values, &params));
+
+            cs.set("poseidon/round 10/mix 1/num", rng.gen());
+            assert!(!cs.is_satisfied());
+        }
+    }
+}
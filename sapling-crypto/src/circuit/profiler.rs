@@ -0,0 +1,312 @@
+//! Constraint system that counts constraints and allocated variables
+//! per namespace, without computing any assignments. Counts of every
+//! namespace include the ones of its children, like in a flame graph.
+
+use pairing::Engine;
+
+use bellman::{
+    LinearCombination,
+    SynthesisError,
+    ConstraintSystem,
+    Variable,
+    Index
+};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde_json;
+
+#[derive(Serialize, Clone, Default)]
+pub struct ProfileNode {
+    pub name: String,
+    pub constraints: usize,
+    pub aux: usize,
+    pub inputs: usize,
+    /// Children in order of their first appearance
+    pub children: Vec<ProfileNode>,
+    #[serde(skip)]
+    child_indices: HashMap<String, usize>
+}
+
+impl ProfileNode {
+    fn new(name: String) -> Self {
+        ProfileNode {
+            name: name,
+            ..Default::default()
+        }
+    }
+
+    fn child_index(&mut self, name: String) -> usize {
+        if let Some(index) = self.child_indices.get(&name) {
+            return *index;
+        }
+
+        let index = self.children.len();
+        self.child_indices.insert(name.clone(), index);
+        self.children.push(ProfileNode::new(name));
+
+        index
+    }
+
+    pub fn child(&self, name: &str) -> Option<&ProfileNode> {
+        self.child_indices.get(name).map(|i| &self.children[*i])
+    }
+
+    /// Returns the node at the path relative to this one,
+    /// with namespaces separated by `/`
+    pub fn get(&self, path: &str) -> Option<&ProfileNode> {
+        path.split('/').fold(Some(self), |node, name| {
+            node.and_then(|n| n.child(name))
+        })
+    }
+
+    /// Constraints enforced directly in this namespace
+    pub fn own_constraints(&self) -> usize {
+        self.constraints - self.children.iter().map(|c| c.constraints).sum::<usize>()
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter, depth: usize, max_depth: usize, total: usize) -> fmt::Result {
+        let share = if total == 0 {
+            0.0
+        } else {
+            100.0 * self.constraints as f64 / total as f64
+        };
+
+        writeln!(
+            f,
+            "{:indent$}{}: {} constraints ({:.1}%), {} aux, {} inputs",
+            "",
+            self.name,
+            self.constraints,
+            share,
+            self.aux,
+            self.inputs,
+            indent = depth * 2
+        )?;
+
+        if depth < max_depth {
+            for child in self.children.iter() {
+                child.fmt_tree(f, depth + 1, max_depth, total)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ProfileNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_depth = f.precision().unwrap_or(usize::max_value());
+
+        self.fmt_tree(f, 0, max_depth, self.constraints)
+    }
+}
+
+pub struct ProfilingConstraintSystem<E: Engine> {
+    root: ProfileNode,
+    /// Indices of the children on the way
+    /// to the current namespace
+    current_namespace: Vec<usize>,
+    num_inputs: usize,
+    num_aux: usize,
+    _marker: PhantomData<E>
+}
+
+impl<E: Engine> ProfilingConstraintSystem<E> {
+    pub fn new() -> Self {
+        ProfilingConstraintSystem {
+            root: ProfileNode::new("root".to_string()),
+            current_namespace: vec![],
+            // The constant one input is counted
+            num_inputs: 1,
+            num_aux: 0,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn root(&self) -> &ProfileNode {
+        &self.root
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.root.constraints
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn num_aux(&self) -> usize {
+        self.num_aux
+    }
+
+    /// Prints the tree of namespaces up to the given depth
+    pub fn print(&self, max_depth: usize) {
+        print!("{:.*}", max_depth, self.root);
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.root).expect("profile is serializable")
+    }
+
+    /// Applies `f` to all of the nodes on the path to the current namespace
+    fn update<F: Fn(&mut ProfileNode)>(&mut self, f: F) {
+        let mut node = &mut self.root;
+        f(node);
+
+        for i in self.current_namespace.iter() {
+            node = &mut {node}.children[*i];
+            f(node);
+        }
+    }
+
+    fn current_node(&mut self) -> &mut ProfileNode {
+        let mut node = &mut self.root;
+
+        for i in self.current_namespace.iter() {
+            node = &mut {node}.children[*i];
+        }
+
+        node
+    }
+}
+
+impl<E: Engine> ConstraintSystem<E> for ProfilingConstraintSystem<E> {
+    type Root = Self;
+
+    fn alloc<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        let index = self.num_aux;
+        self.num_aux += 1;
+        self.update(|n| n.aux += 1);
+
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    fn alloc_input<F, A, AR>(
+        &mut self,
+        _: A,
+        _: F
+    ) -> Result<Variable, SynthesisError>
+        where F: FnOnce() -> Result<E::Fr, SynthesisError>, A: FnOnce() -> AR, AR: Into<String>
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+        self.update(|n| n.inputs += 1);
+
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(
+        &mut self,
+        _: A,
+        _: LA,
+        _: LB,
+        _: LC
+    )
+        where A: FnOnce() -> AR, AR: Into<String>,
+              LA: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LB: FnOnce(LinearCombination<E>) -> LinearCombination<E>,
+              LC: FnOnce(LinearCombination<E>) -> LinearCombination<E>
+    {
+        self.update(|n| n.constraints += 1);
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where NR: Into<String>, N: FnOnce() -> NR
+    {
+        let index = self.current_node().child_index(name_fn().into());
+        self.current_namespace.push(index);
+    }
+
+    fn pop_namespace(&mut self)
+    {
+        assert!(self.current_namespace.pop().is_some());
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root
+    {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bellman::{ConstraintSystem, SynthesisError};
+    use pairing::bn256::{Bn256, Fr};
+    use ff::Field;
+    use ::circuit::test::*;
+    use ::circuit::num::AllocatedNum;
+    use ::circuit::boolean::{Boolean, AllocatedBit};
+    use ::circuit::sparse_merkle_tree::compute_root;
+    use ::poseidon::PoseidonParams;
+    use super::*;
+
+    fn synthesize<CS: ConstraintSystem<Bn256>>(mut cs: CS, value: Option<Fr>, params: &PoseidonParams<Bn256>) {
+        let leaf = AllocatedNum::alloc(cs.namespace(|| "leaf"), || {
+            value.ok_or(SynthesisError::AssignmentMissing)
+        }).unwrap();
+
+        let key = (0..4).map(|i| {
+            Boolean::from(AllocatedBit::alloc(cs.namespace(|| format!("key bit {}", i)), value.map(|_| true)).unwrap())
+        }).collect::<Vec<_>>();
+
+        let siblings = (0..4).map(|i| {
+            AllocatedNum::alloc(cs.namespace(|| format!("sibling {}", i)), || {
+                value.ok_or(SynthesisError::AssignmentMissing)
+            }).unwrap()
+        }).collect::<Vec<_>>();
+
+        let root = compute_root(cs.namespace(|| "merkle tree"), &key, &leaf, &siblings, params).unwrap();
+        root.inputize(cs.namespace(|| "root")).unwrap();
+    }
+
+    #[test]
+    fn test_profile_matches_test_cs() {
+        let params = PoseidonParams::<Bn256>::new(3, 8, 57);
+
+        let mut test_cs = TestConstraintSystem::<Bn256>::new();
+        synthesize(&mut test_cs, Some(Fr::one()), &params);
+
+        // No assignments are needed for profiling
+        let mut cs = ProfilingConstraintSystem::<Bn256>::new();
+        synthesize(&mut cs, None, &params);
+
+        assert_eq!(cs.num_constraints(), test_cs.num_constraints());
+        assert_eq!(cs.num_inputs(), test_cs.num_inputs());
+
+        let root = cs.root();
+        let tree = root.get("merkle tree").unwrap();
+        assert_eq!(tree.children.len(), 4);
+        assert_eq!(tree.own_constraints(), 0);
+
+        let level = tree.get("height 2").unwrap();
+        let hash = level.get("node hash").unwrap();
+        let reversal = level.get("conditional reversal of preimage").unwrap();
+        assert_eq!(reversal.constraints, 2);
+        assert_eq!(reversal.aux, 2);
+        assert_eq!(level.constraints, hash.constraints + reversal.constraints);
+        assert_eq!(tree.constraints, 4 * level.constraints);
+
+        // Key bits are boolean constrained
+        assert_eq!(root.get("key bit 0").unwrap().constraints, 1);
+        assert_eq!(root.get("root").unwrap().inputs, 1);
+        assert!(root.get("merkle tree/height 4").is_none());
+
+        let printed = format!("{:.1}", root);
+        assert!(printed.contains("  merkle tree: "));
+        assert!(!printed.contains("height 2"));
+
+        let json = cs.to_json();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["constraints"], cs.num_constraints());
+        assert_eq!(parsed["children"][0]["name"], "leaf");
+    }
+}
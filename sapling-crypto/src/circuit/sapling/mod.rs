@@ -682,7 +682,7 @@ fn test_input_circuit_with_bls12_381() {
                 lhs.reverse();
                 rhs.reverse();
 
-                cur = ::pedersen_hash::pedersen_hash::<Bls12, _>(
+                cur = ::pedersen_hash::pedersen_hash::<Bls12, _, _>(
                     ::pedersen_hash::Personalization::MerkleTree(i),
                     lhs.into_iter()
                        .take(Fr::NUM_BITS as usize)
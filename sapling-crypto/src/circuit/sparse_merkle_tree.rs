@@ -0,0 +1,297 @@
+use pairing::Engine;
+use ff::Field;
+
+use bellman::{
+    SynthesisError,
+    ConstraintSystem
+};
+
+use super::num::AllocatedNum;
+use super::boolean::Boolean;
+use super::poseidon::poseidon_hash;
+
+use ::poseidon::PoseidonParams;
+
+/// Computes the root from a leaf and its authentication path.
+/// `key` are the bits of the leaf's key in little-endian order,
+/// telling if the current node is the right child at each height.
+pub fn compute_root<E, CS>(
+    mut cs: CS,
+    key: &[Boolean],
+    leaf: &AllocatedNum<E>,
+    siblings: &[AllocatedNum<E>],
+    params: &PoseidonParams<E>
+) -> Result<AllocatedNum<E>, SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    assert_eq!(key.len(), siblings.len());
+
+    let mut cur = leaf.clone();
+
+    for (height, (bit, sibling)) in key.iter().zip(siblings.iter()).enumerate() {
+        let mut cs = cs.namespace(|| format!("height {}", height));
+
+        // Swap the two if the current node is on the right
+        let (left, right) = AllocatedNum::conditionally_reverse(
+            cs.namespace(|| "conditional reversal of preimage"),
+            &cur,
+            sibling,
+            bit
+        )?;
+
+        cur = poseidon_hash(
+            cs.namespace(|| "node hash"),
+            &[left, right],
+            params
+        )?;
+    }
+
+    Ok(cur)
+}
+
+fn enforce_equal<E, CS>(
+    mut cs: CS,
+    a: &AllocatedNum<E>,
+    b: &AllocatedNum<E>
+)
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    cs.enforce(
+        || "equality",
+        |lc| lc + a.get_variable(),
+        |lc| lc + CS::one(),
+        |lc| lc + b.get_variable()
+    );
+}
+
+/// Enforces that `value` is stored at `key` in the tree with the given root
+pub fn verify_membership<E, CS>(
+    mut cs: CS,
+    root: &AllocatedNum<E>,
+    key: &[Boolean],
+    value: &AllocatedNum<E>,
+    siblings: &[AllocatedNum<E>],
+    params: &PoseidonParams<E>
+) -> Result<(), SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let computed = compute_root(
+        cs.namespace(|| "root computation"),
+        key,
+        value,
+        siblings,
+        params
+    )?;
+
+    enforce_equal(cs.namespace(|| "root check"), &computed, root);
+
+    Ok(())
+}
+
+/// Enforces that nothing is stored at `key` in the tree with the given root
+pub fn verify_non_membership<E, CS>(
+    mut cs: CS,
+    root: &AllocatedNum<E>,
+    key: &[Boolean],
+    siblings: &[AllocatedNum<E>],
+    params: &PoseidonParams<E>
+) -> Result<(), SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    let empty = AllocatedNum::alloc(cs.namespace(|| "empty leaf"), || {
+        Ok(E::Fr::zero())
+    })?;
+
+    cs.enforce(
+        || "empty leaf is zero",
+        |lc| lc + empty.get_variable(),
+        |lc| lc + CS::one(),
+        |lc| lc
+    );
+
+    verify_membership(
+        cs.namespace(|| "membership of empty leaf"),
+        root,
+        key,
+        &empty,
+        siblings,
+        params
+    )
+}
+
+/// Enforces that replacing `old_value` at `key` by `new_value`
+/// changes the root from `old_root` to `new_root`
+pub fn verify_update<E, CS>(
+    mut cs: CS,
+    old_root: &AllocatedNum<E>,
+    new_root: &AllocatedNum<E>,
+    key: &[Boolean],
+    old_value: &AllocatedNum<E>,
+    new_value: &AllocatedNum<E>,
+    siblings: &[AllocatedNum<E>],
+    params: &PoseidonParams<E>
+) -> Result<(), SynthesisError>
+    where E: Engine,
+          CS: ConstraintSystem<E>
+{
+    verify_membership(
+        cs.namespace(|| "old value membership"),
+        old_root,
+        key,
+        old_value,
+        siblings,
+        params
+    )?;
+
+    verify_membership(
+        cs.namespace(|| "new value membership"),
+        new_root,
+        key,
+        new_value,
+        siblings,
+        params
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{SeedableRng, Rng, XorShiftRng};
+    use bellman::ConstraintSystem;
+    use pairing::bn256::{Bn256, Fr};
+    use ff::Field;
+    use ::circuit::test::*;
+    use ::circuit::num::AllocatedNum;
+    use ::circuit::boolean::{Boolean, AllocatedBit};
+    use ::poseidon::PoseidonParams;
+    use ::sparse_merkle_tree::SparseMerkleTree;
+    use super::*;
+
+    const DEPTH: usize = 16;
+
+    fn alloc_path<CS: ConstraintSystem<Bn256>>(
+        mut cs: CS,
+        key: u64,
+        siblings: &[Fr]
+    ) -> (Vec<Boolean>, Vec<AllocatedNum<Bn256>>)
+    {
+        let key_bits = (0..siblings.len()).map(|i| {
+            Boolean::from(AllocatedBit::alloc(
+                cs.namespace(|| format!("key bit {}", i)),
+                Some((key >> i) & 1 == 1)
+            ).unwrap())
+        }).collect();
+
+        let siblings = siblings.iter().enumerate().map(|(i, s)| {
+            AllocatedNum::alloc(cs.namespace(|| format!("sibling {}", i)), || Ok(*s)).unwrap()
+        }).collect();
+
+        (key_bits, siblings)
+    }
+
+    fn alloc_num<CS: ConstraintSystem<Bn256>>(cs: CS, value: Fr) -> AllocatedNum<Bn256> {
+        AllocatedNum::alloc(cs, || Ok(value)).unwrap()
+    }
+
+    fn random_tree<R: Rng>(rng: &mut R, params: &PoseidonParams<Bn256>) -> SparseMerkleTree<Bn256> {
+        let mut tree = SparseMerkleTree::new(DEPTH, params);
+
+        for _ in 0..10 {
+            let key = rng.gen::<u64>() % (1 << DEPTH);
+            tree.update(key, rng.gen(), params);
+        }
+
+        tree
+    }
+
+    #[test]
+    fn test_membership() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = PoseidonParams::<Bn256>::new(3, 8, 57);
+        let mut tree = random_tree(rng, &params);
+
+        let key = rng.gen::<u64>() % (1 << DEPTH);
+        let value: Fr = rng.gen();
+        tree.update(key, value, &params);
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+
+        let root = alloc_num(cs.namespace(|| "root"), tree.root());
+        let value = alloc_num(cs.namespace(|| "value"), value);
+        let (key_bits, siblings) = alloc_path(cs.namespace(|| "path"), key, &tree.proof(key));
+
+        verify_membership(cs.namespace(|| "membership"), &root, &key_bits, &value, &siblings, &params).unwrap();
+
+        assert!(cs.is_satisfied());
+
+        cs.set("root/num", rng.gen());
+        assert_eq!(cs.which_is_unsatisfied(), Some("membership/root check/equality"));
+    }
+
+    #[test]
+    fn test_non_membership() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = PoseidonParams::<Bn256>::new(3, 8, 57);
+        let tree = random_tree(rng, &params);
+
+        let key = (0..).find(|k| tree.get(*k) == Fr::zero()).unwrap();
+        let present = (0..(1 << DEPTH)).find(|k| tree.get(*k) != Fr::zero()).unwrap();
+
+        for &(key, expected) in &[(key, true), (present, false)] {
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let root = alloc_num(cs.namespace(|| "root"), tree.root());
+            let (key_bits, siblings) = alloc_path(cs.namespace(|| "path"), key, &tree.proof(key));
+
+            verify_non_membership(cs.namespace(|| "non-membership"), &root, &key_bits, &siblings, &params).unwrap();
+
+            assert_eq!(cs.is_satisfied(), expected);
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = PoseidonParams::<Bn256>::new(3, 8, 57);
+        let mut tree = random_tree(rng, &params);
+
+        for _ in 0..3 {
+            let key = rng.gen::<u64>() % (1 << DEPTH);
+            let old_root = tree.root();
+            let old_value = tree.get(key);
+            let new_value: Fr = rng.gen();
+            let siblings = tree.update(key, new_value, &params);
+
+            let mut cs = TestConstraintSystem::<Bn256>::new();
+
+            let old_root = alloc_num(cs.namespace(|| "old root"), old_root);
+            let new_root = alloc_num(cs.namespace(|| "new root"), tree.root());
+            let old_value = alloc_num(cs.namespace(|| "old value"), old_value);
+            let new_value = alloc_num(cs.namespace(|| "new value"), new_value);
+            let (key_bits, siblings) = alloc_path(cs.namespace(|| "path"), key, &siblings);
+
+            verify_update(
+                cs.namespace(|| "update"),
+                &old_root,
+                &new_root,
+                &key_bits,
+                &old_value,
+                &new_value,
+                &siblings,
+                &params
+            ).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            // The root must match the native one
+            let root = compute_root(cs.namespace(|| "root"), &key_bits, &new_value, &siblings, &params).unwrap();
+            assert_eq!(root.get_value().unwrap(), tree.root());
+
+            cs.set("path/key bit 3/boolean", if (key >> 3) & 1 == 1 { Fr::zero() } else { Fr::one() });
+            assert!(!cs.is_satisfied());
+        }
+    }
+}
@@ -0,0 +1,668 @@
+//! Differential tests of the gadgets against their native
+//! counterparts. Inputs are generated randomly, both sides are
+//! evaluated, and the gadget is required to be satisfied and
+//! to compute the same output.
+//!
+//! Redjubjub signatures are only verified natively, so for
+//! them only the rerandomization of the key, which the spend
+//! circuit computes, is compared.
+
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use rand::{XorShiftRng, SeedableRng, Rng, Rand};
+use pairing::Engine;
+use pairing::bls12_381::Bls12;
+use pairing::bn256::{Bn256, Fr};
+use ff::{BitIterator, Field, PrimeField, PrimeFieldRepr};
+use bellman::ConstraintSystem;
+use blake2_rfc::blake2s::Blake2s;
+use crypto::sha2::Sha256;
+use crypto::digest::Digest;
+
+use ::circuit::test::TestConstraintSystem;
+use ::circuit::boolean::{Boolean, AllocatedBit};
+use ::circuit::num::AllocatedNum;
+use ::circuit::ecc::{self, EdwardsPoint, MontgomeryPoint};
+use ::circuit::multieq::MultiEq;
+use ::circuit::uint32::UInt32;
+use ::circuit::{
+    blake2s,
+    elgamal,
+    float_point,
+    lookup,
+    mimc7,
+    multipack,
+    pedersen_hash,
+    poseidon,
+    sha256,
+    sparse_merkle_tree
+};
+use ::jubjub::{
+    edwards,
+    montgomery,
+    JubjubEngine,
+    JubjubParams,
+    JubjubBls12,
+    FixedGenerators
+};
+use ::alt_babyjubjub::AltJubjubBn256;
+use ::babyjubjub::JubjubBn256;
+use ::alt_babyjubjub::fs::Fs;
+use ::eddsa::{PrivateKey, PublicKey, CircomlibEddsaParams, CircomlibHash};
+use ::redjubjub;
+use ::mimc7::Mimc7Params;
+use ::poseidon::PoseidonParams;
+
+/// Gadgets are expensive to synthesize in the test
+/// constraint system, so few cases are run for each
+const CASES: u32 = 16;
+
+fn seed() -> impl Strategy<Value = [u32; 4]> {
+    prop::array::uniform4(1u32..)
+}
+
+fn alloc_bits<E: Engine, CS: ConstraintSystem<E>>(mut cs: CS, bits: &[bool]) -> Vec<Boolean> {
+    bits.iter().enumerate().map(|(i, b)| {
+        Boolean::from(AllocatedBit::alloc(cs.namespace(|| format!("bit {}", i)), Some(*b)).unwrap())
+    }).collect()
+}
+
+fn alloc_num<E: Engine, CS: ConstraintSystem<E>>(cs: CS, value: E::Fr) -> AllocatedNum<E> {
+    AllocatedNum::alloc(cs, || Ok(value)).unwrap()
+}
+
+fn bits_value(bits: &[Boolean]) -> Vec<bool> {
+    bits.iter().map(|b| b.get_value().unwrap()).collect()
+}
+
+/// Little-endian bits of a scalar
+fn scalar_bits<F: PrimeField>(s: F) -> Vec<bool> {
+    let mut bits = BitIterator::new(s.into_repr()).collect::<Vec<_>>();
+    bits.reverse();
+    bits.truncate(F::NUM_BITS as usize);
+
+    bits
+}
+
+fn u32_value(n: &UInt32) -> u32 {
+    n.into_bits().iter().rev().fold(0, |acc, b| (acc << 1) | b.get_value().unwrap() as u32)
+}
+
+fn assert_point_eq<E: JubjubEngine, Order>(
+    p: &EdwardsPoint<E>,
+    expected: &edwards::Point<E, Order>
+) -> Result<(), TestCaseError>
+{
+    let (x, y) = expected.into_xy();
+    prop_assert_eq!(p.get_x().get_value().unwrap(), x);
+    prop_assert_eq!(p.get_y().get_value().unwrap(), y);
+
+    Ok(())
+}
+
+/// Longest input the pedersen hash accepts with the parameters,
+/// besides the personalization bits
+fn pedersen_max_input<E: JubjubEngine, P: JubjubParams<E>>(params: &P) -> usize {
+    let segments = ::std::cmp::min(
+        params.pedersen_circuit_generators().len(),
+        params.pedersen_hash_exp_table().len()
+    );
+    let windows = params.pedersen_circuit_generators().iter()
+                        .map(|s| s.len())
+                        .chain(Some(params.pedersen_hash_chunks_per_generator()))
+                        .min()
+                        .unwrap();
+
+    // The gadget looks up another segment once the bits of the
+    // last one are consumed, so one segment is left unused
+    (segments - 1) * windows * 3 - 6
+}
+
+fn check_pedersen_hash<E: JubjubEngine, P: JubjubParams<E>>(
+    input: &[bool],
+    params: &P
+) -> Result<(), TestCaseError>
+{
+    let input = &input[..::std::cmp::min(input.len(), pedersen_max_input(params))];
+    let personalization = pedersen_hash::Personalization::MerkleTree(input.len() % 63);
+
+    let mut cs = TestConstraintSystem::<E>::new();
+    let input_bits = alloc_bits(cs.namespace(|| "input"), input);
+    let res = pedersen_hash::pedersen_hash(cs.namespace(|| "pedersen hash"), personalization, &input_bits, params).unwrap();
+
+    let expected = ::pedersen_hash::pedersen_hash::<E, _, _>(personalization, input.iter().cloned(), params);
+
+    prop_assert!(cs.is_satisfied());
+    assert_point_eq(&res, &expected)
+}
+
+fn check_edwards_ops<E: JubjubEngine>(
+    seed: [u32; 4],
+    params: &E::Params
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    // `mul_windowed` takes points of the prime order subgroup
+    let p = edwards::Point::<E, _>::rand(rng, params).mul_by_cofactor(params);
+    let q = edwards::Point::<E, _>::rand(rng, params).mul_by_cofactor(params);
+    let s = E::Fs::rand(rng);
+
+    let mut cs = TestConstraintSystem::<E>::new();
+    let p_var = EdwardsPoint::witness(cs.namespace(|| "p"), Some(p.clone()), params).unwrap();
+    let q_var = EdwardsPoint::witness(cs.namespace(|| "q"), Some(q.clone()), params).unwrap();
+    let s_bits = alloc_bits(cs.namespace(|| "s"), &scalar_bits(s));
+
+    let sum = p_var.add(cs.namespace(|| "add"), &q_var, params).unwrap();
+    let double = p_var.double(cs.namespace(|| "double"), params).unwrap();
+    let product = p_var.mul(cs.namespace(|| "mul"), &s_bits, params).unwrap();
+    let windowed = p_var.mul_windowed(cs.namespace(|| "mul windowed"), &s_bits, params).unwrap();
+    let negation = p_var.negate(cs.namespace(|| "negate")).unwrap();
+
+    prop_assert!(cs.is_satisfied());
+    assert_point_eq(&sum, &p.add(&q, params))?;
+    assert_point_eq(&double, &p.double(params))?;
+    assert_point_eq(&product, &p.mul(s, params))?;
+    assert_point_eq(&windowed, &p.mul(s, params))?;
+    assert_point_eq(&negation, &p.negate())
+}
+
+fn check_fixed_base_multiplication<E: JubjubEngine>(
+    seed: [u32; 4],
+    params: &E::Params
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    let s = E::Fs::rand(rng);
+    let p_g = FixedGenerators::SpendingKeyGenerator;
+
+    let mut cs = TestConstraintSystem::<E>::new();
+    let s_bits = alloc_bits(cs.namespace(|| "s"), &scalar_bits(s));
+    let res = ecc::fixed_base_multiplication(cs.namespace(|| "mul"), p_g, &s_bits, params).unwrap();
+
+    prop_assert!(cs.is_satisfied());
+    assert_point_eq(&res, &params.generator(p_g).mul(s, params))
+}
+
+fn check_montgomery_ops<E: JubjubEngine>(
+    seed: [u32; 4],
+    params: &E::Params
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    let p = montgomery::Point::<E, _>::rand(rng, params);
+    let q = montgomery::Point::<E, _>::rand(rng, params);
+    prop_assume!(p != q && p != q.negate());
+
+    let (p_x, p_y) = p.into_xy().unwrap();
+    let (q_x, q_y) = q.into_xy().unwrap();
+
+    let mut cs = TestConstraintSystem::<E>::new();
+    let p_var = MontgomeryPoint::interpret_unchecked(
+        alloc_num(cs.namespace(|| "p x"), p_x).into(),
+        alloc_num(cs.namespace(|| "p y"), p_y).into()
+    );
+    let q_var = MontgomeryPoint::interpret_unchecked(
+        alloc_num(cs.namespace(|| "q x"), q_x).into(),
+        alloc_num(cs.namespace(|| "q y"), q_y).into()
+    );
+
+    let sum = p_var.add(cs.namespace(|| "add"), &q_var, params).unwrap();
+    let sum = sum.into_edwards(cs.namespace(|| "sum into edwards"), params).unwrap();
+    let p_edwards = p_var.into_edwards(cs.namespace(|| "p into edwards"), params).unwrap();
+
+    prop_assert!(cs.is_satisfied());
+    assert_point_eq(&sum, &edwards::Point::from_montgomery(&p.add(&q, params), params))?;
+    assert_point_eq(&p_edwards, &edwards::Point::from_montgomery(&p, params))
+}
+
+fn int_to_fr(n: u128) -> Fr {
+    Fr::from_str(&n.to_string()).unwrap()
+}
+
+/// Converts the scalar of a signature into the field element
+/// the gadgets take, as it is smaller than the modulus of Fr
+fn signature_scalar(s: Fs) -> Fr {
+    let mut s_repr = <Fr as PrimeField>::Repr::default();
+    let mut s_bytes = vec![];
+    s.into_repr().write_le(&mut s_bytes).unwrap();
+    s_repr.read_le(&s_bytes[..]).unwrap();
+
+    Fr::from_repr(s_repr).unwrap()
+}
+
+fn check_circomlib_eddsa(
+    seed: [u32; 4],
+    tamper: bool,
+    hash: CircomlibHash
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    let params = &AltJubjubBn256::new();
+    let circomlib_params = CircomlibEddsaParams::new(params);
+    let sk = PrivateKey::<Bn256>(rng.gen());
+    let pk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+    let msg: Fr = rng.gen();
+    let sig = match hash {
+        CircomlibHash::Mimc7 => sk.sign_mimc7(&msg, rng, params, &circomlib_params),
+        CircomlibHash::Poseidon => sk.sign_poseidon(&msg, rng, params, &circomlib_params)
+    };
+
+    let mut verified_msg = msg;
+    if tamper {
+        verified_msg.add_assign(&Fr::one());
+    }
+
+    let mut cs = TestConstraintSystem::<Bn256>::new();
+    let message = alloc_num(cs.namespace(|| "message"), verified_msg);
+    let generator = EdwardsPoint::witness(cs.namespace(|| "generator"), Some(circomlib_params.generator().clone()), params).unwrap();
+    let signature = ::circuit::baby_eddsa::EddsaSignature {
+        r: EdwardsPoint::witness(cs.namespace(|| "r"), Some(sig.r.clone()), params).unwrap(),
+        s: alloc_num(cs.namespace(|| "s"), signature_scalar(sig.s)),
+        pk: EdwardsPoint::witness(cs.namespace(|| "pk"), Some(pk.0.clone()), params).unwrap()
+    };
+
+    let valid = match hash {
+        CircomlibHash::Mimc7 => {
+            signature.verify_mimc7_signature(
+                cs.namespace(|| "verify"), params, &message, generator, &circomlib_params
+            ).unwrap();
+
+            pk.verify_mimc7(&verified_msg, &sig, params, &circomlib_params)
+        },
+        CircomlibHash::Poseidon => {
+            signature.verify_poseidon_signature(
+                cs.namespace(|| "verify"), params, &message, generator, &circomlib_params
+            ).unwrap();
+
+            pk.verify_poseidon(&verified_msg, &sig, params, &circomlib_params)
+        }
+    };
+
+    prop_assert_eq!(valid, !tamper);
+    prop_assert_eq!(cs.is_satisfied(), valid);
+
+    Ok(())
+}
+
+/// Checks the gadgets verifying the signatures of the message
+/// bytes, hashed with blake2s for `verify_eddsa_for_snark`
+/// and taken as the challenge for `verify_raw_message_signature`
+fn check_bytes_eddsa(
+    seed: [u32; 4],
+    msg: &[u8],
+    tamper: bool,
+    raw: bool
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    let params = &AltJubjubBn256::new();
+    let p_g = FixedGenerators::SpendingKeyGenerator;
+    let sk = PrivateKey::<Bn256>(rng.gen());
+    let pk = PublicKey::from_private(&sk, p_g, params);
+    let sig = if raw {
+        sk.sign_raw_message(msg, rng, p_g, params, 16)
+    } else {
+        sk.sign_for_snark(msg, rng, p_g, params)
+    };
+
+    let mut verified_msg = msg.to_vec();
+    if tamper {
+        verified_msg[0] ^= 1;
+    }
+
+    let mut cs = TestConstraintSystem::<Bn256>::new();
+    let message = alloc_bits(cs.namespace(|| "message"), &multipack::bytes_to_bits_le(&verified_msg));
+    let generator = EdwardsPoint::witness(cs.namespace(|| "generator"), Some(params.generator(p_g).clone()), params).unwrap();
+    let signature = ::circuit::baby_eddsa::EddsaSignature {
+        r: EdwardsPoint::witness(cs.namespace(|| "r"), Some(sig.r.clone()), params).unwrap(),
+        s: alloc_num(cs.namespace(|| "s"), signature_scalar(sig.s)),
+        pk: EdwardsPoint::witness(cs.namespace(|| "pk"), Some(pk.0.clone()), params).unwrap()
+    };
+
+    let valid = if raw {
+        signature.verify_raw_message_signature(cs.namespace(|| "verify"), params, &message, generator, 16).unwrap();
+
+        pk.verify_for_raw_message(&verified_msg, &sig, p_g, params, 16)
+    } else {
+        signature.verify_eddsa_for_snark(cs.namespace(|| "verify"), params, &message, generator).unwrap();
+
+        pk.verify_for_snark(&verified_msg, &sig, p_g, params)
+    };
+
+    prop_assert_eq!(valid, !tamper);
+    prop_assert_eq!(cs.is_satisfied(), valid);
+
+    Ok(())
+}
+
+fn check_redjubjub_randomization<E: JubjubEngine, P: JubjubParams<E>>(
+    seed: [u32; 4],
+    params: &P
+) -> Result<(), TestCaseError>
+{
+    let rng = &mut XorShiftRng::from_seed(seed);
+    let p_g = FixedGenerators::SpendingKeyGenerator;
+    let sk = redjubjub::PrivateKey::<E>(rng.gen());
+    let pk = redjubjub::PublicKey::from_private(&sk, p_g, params);
+    let alpha = E::Fs::rand(rng);
+
+    let mut cs = TestConstraintSystem::<E>::new();
+    let ak = EdwardsPoint::witness(cs.namespace(|| "ak"), Some(pk.0.clone()), params).unwrap();
+    let alpha_bits = alloc_bits(cs.namespace(|| "alpha"), &scalar_bits(alpha));
+    let ar = ecc::fixed_base_multiplication(cs.namespace(|| "alpha mul"), p_g, &alpha_bits, params).unwrap();
+    let rk = ak.add(cs.namespace(|| "rk"), &ar, params).unwrap();
+
+    prop_assert!(cs.is_satisfied());
+    assert_point_eq(&rk, &pk.randomize(alpha, p_g, params).0)
+}
+
+/// Values of at most 64 bits, often small so that the
+/// compared values are equal
+fn comparand() -> impl Strategy<Value = u64> {
+    prop_oneof![0u64..4, any::<u64>()]
+}
+
+/// A boolean, which is allocated, constant or negated
+/// from an allocated bit depending on the second value
+fn boolean() -> impl Strategy<Value = (bool, u8)> {
+    (any::<bool>(), 0u8..3)
+}
+
+fn alloc_boolean<E: Engine, CS: ConstraintSystem<E>>(cs: CS, (value, kind): (bool, u8)) -> Boolean {
+    match kind {
+        0 => Boolean::constant(value),
+        1 => Boolean::from(AllocatedBit::alloc(cs, Some(value)).unwrap()),
+        _ => Boolean::from(AllocatedBit::alloc(cs, Some(!value)).unwrap()).not()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn pedersen_hash_matches_native(input in prop::collection::vec(any::<bool>(), 0..800)) {
+        check_pedersen_hash::<Bls12, _>(&input, &JubjubBls12::new())?;
+        check_pedersen_hash::<Bn256, _>(&input, &AltJubjubBn256::new())?;
+        check_pedersen_hash::<Bn256, _>(&input, &JubjubBn256::new())?;
+    }
+
+    #[test]
+    fn blake2s_matches_native(data in prop::collection::vec(any::<u8>(), 0..160)) {
+        let mut h = Blake2s::with_params(32, &[], &[], b"12345678");
+        h.update(&data);
+        let expected = multipack::bytes_to_bits_le(h.finalize().as_ref());
+
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        let input = alloc_bits(cs.namespace(|| "input"), &multipack::bytes_to_bits_le(&data));
+        let res = blake2s::blake2s(cs.namespace(|| "blake2s"), &input, b"12345678").unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(bits_value(&res), expected);
+    }
+
+    #[test]
+    fn sha256_matches_native(data in prop::collection::vec(any::<u8>(), 0..160)) {
+        let mut h = Sha256::new();
+        h.input(&data);
+        let mut hash = [0u8; 32];
+        h.result(&mut hash[..]);
+
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        let input = alloc_bits(cs.namespace(|| "input"), &multipack::bytes_to_bits(&data));
+        let res = sha256::sha256(cs.namespace(|| "sha256"), &input).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(bits_value(&res), multipack::bytes_to_bits(&hash));
+    }
+
+    #[test]
+    fn edwards_ops_match_native(seed in seed()) {
+        check_edwards_ops::<Bls12>(seed, &JubjubBls12::new())?;
+        check_edwards_ops::<Bn256>(seed, &AltJubjubBn256::new())?;
+    }
+
+    #[test]
+    fn fixed_base_multiplication_matches_native(seed in seed()) {
+        check_fixed_base_multiplication::<Bls12>(seed, &JubjubBls12::new())?;
+        check_fixed_base_multiplication::<Bn256>(seed, &AltJubjubBn256::new())?;
+    }
+
+    #[test]
+    fn montgomery_ops_match_native(seed in seed()) {
+        check_montgomery_ops::<Bls12>(seed, &JubjubBls12::new())?;
+        check_montgomery_ops::<Bn256>(seed, &AltJubjubBn256::new())?;
+    }
+
+    #[test]
+    fn float_parsing_matches_native(encoding in prop::collection::vec(any::<bool>(), 16)) {
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let bits = alloc_bits(cs.namespace(|| "encoding"), &encoding);
+        let res = float_point::parse_with_exponent_le(cs.namespace(|| "parse"), &bits, 5, 11, 10).unwrap();
+
+        let expected = float_point::parse_float_to_u128(encoding, 5, 11, 10).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(res.get_value().unwrap(), int_to_fr(expected));
+    }
+
+    #[test]
+    fn mimc7_matches_native(seed in seed(), len in 1usize..4) {
+        let rng = &mut XorShiftRng::from_seed(seed);
+        let params = &Mimc7Params::<Bn256>::new();
+        let inputs: Vec<Fr> = (0..len).map(|_| rng.gen()).collect();
+        let key: Fr = rng.gen();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let input_vars: Vec<_> = inputs.iter().enumerate().map(|(i, x)| {
+            alloc_num(cs.namespace(|| format!("input {}", i)), *x)
+        }).collect();
+        let key_var = alloc_num(cs.namespace(|| "key"), key);
+
+        let hash = mimc7::mimc7_hash(cs.namespace(|| "hash"), &input_vars[0], &key_var, params).unwrap();
+        let multi_hash = mimc7::mimc7_multi_hash(cs.namespace(|| "multi hash"), &input_vars, &key_var, params).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(hash.get_value().unwrap(), ::mimc7::mimc7_hash(&inputs[0], &key, params));
+        prop_assert_eq!(multi_hash.get_value().unwrap(), ::mimc7::mimc7_multi_hash(&inputs, &key, params));
+    }
+
+    #[test]
+    fn poseidon_matches_native(seed in seed(), len in 1usize..7) {
+        let rng = &mut XorShiftRng::from_seed(seed);
+        let params = &PoseidonParams::<Bn256>::circomlib();
+        let len = ::std::cmp::min(len, params.t());
+        let inputs: Vec<Fr> = (0..len).map(|_| rng.gen()).collect();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let input_vars: Vec<_> = inputs.iter().enumerate().map(|(i, x)| {
+            alloc_num(cs.namespace(|| format!("input {}", i)), *x)
+        }).collect();
+        let hash = poseidon::poseidon_hash(cs.namespace(|| "hash"), &input_vars, params).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(hash.get_value().unwrap(), ::poseidon::poseidon_hash(&inputs, params));
+    }
+
+    #[test]
+    fn multipacking_matches_native(bits in prop::collection::vec(any::<bool>(), 0..800)) {
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        let bit_vars = alloc_bits(cs.namespace(|| "bits"), &bits);
+        multipack::pack_into_inputs(cs.namespace(|| "pack"), &bit_vars).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert!(cs.verify(&multipack::compute_multipacking::<Bls12>(&bits)));
+    }
+
+    #[test]
+    fn uint32_ops_match_native(a in any::<u32>(), b in any::<u32>(), c in any::<u32>(), by in 0usize..32) {
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        let a_var = UInt32::alloc(cs.namespace(|| "a"), Some(a)).unwrap();
+        let b_var = UInt32::alloc(cs.namespace(|| "b"), Some(b)).unwrap();
+        let c_var = UInt32::constant(c);
+
+        let xor = a_var.xor(cs.namespace(|| "xor"), &b_var).unwrap();
+        let sum = {
+            let mut cs = MultiEq::new(&mut cs);
+            UInt32::addmany(cs.namespace(|| "addition"), &[xor.clone(), c_var, a_var.clone()]).unwrap()
+        };
+        let rotated = sum.rotr(by);
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(u32_value(&xor), a ^ b);
+        prop_assert_eq!(u32_value(&rotated), (a ^ b).wrapping_add(c).wrapping_add(a).rotate_right(by as u32));
+    }
+
+    #[test]
+    fn lookup3_xy_matches_table(seed in seed(), index in 0usize..8) {
+        let rng = &mut XorShiftRng::from_seed(seed);
+        let coords: Vec<(Fr, Fr)> = (0..8).map(|_| (rng.gen(), rng.gen())).collect();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let bits = alloc_bits(cs.namespace(|| "index"), &[index & 1 == 1, index & 2 == 2, index & 4 == 4]);
+        let (x, y) = lookup::lookup3_xy(cs.namespace(|| "lookup"), &bits, &coords).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!((x.get_value().unwrap(), y.get_value().unwrap()), coords[index]);
+    }
+
+    #[test]
+    fn sparse_merkle_root_matches_native(seed in seed(), depth in 1usize..8, key in any::<u64>()) {
+        let rng = &mut XorShiftRng::from_seed(seed);
+        let params = &PoseidonParams::<Bn256>::new(3, 8, 57);
+        let key = key & ((1 << depth) - 1);
+        let leaf: Fr = rng.gen();
+        let siblings: Vec<Fr> = (0..depth).map(|_| rng.gen()).collect();
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let key_bits: Vec<bool> = (0..depth).map(|i| (key >> i) & 1 == 1).collect();
+        let key_vars = alloc_bits(cs.namespace(|| "key"), &key_bits);
+        let leaf_var = alloc_num(cs.namespace(|| "leaf"), leaf);
+        let sibling_vars: Vec<_> = siblings.iter().enumerate().map(|(i, s)| {
+            alloc_num(cs.namespace(|| format!("sibling {}", i)), *s)
+        }).collect();
+
+        let root = sparse_merkle_tree::compute_root(cs.namespace(|| "root"), &key_vars, &leaf_var, &sibling_vars, params).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(root.get_value().unwrap(), ::sparse_merkle_tree::compute_root(key, &leaf, &siblings, params));
+    }
+
+    #[test]
+    fn elgamal_matches_native(seed in seed(), message in any::<u8>()) {
+        let rng = &mut XorShiftRng::from_seed(seed);
+        let params = &AltJubjubBn256::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let sk = PrivateKey::<Bn256>(rng.gen());
+        let pk = PublicKey::from_private(&sk, p_g, params);
+        let r = rng.gen();
+
+        let expected = ::elgamal::Ciphertext::encrypt_with_randomness(
+            &::elgamal::encode_message(message as u64, p_g, params),
+            &pk,
+            r,
+            p_g,
+            params
+        );
+
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let message_bits: Vec<bool> = (0..8).map(|i| (message >> i) & 1 == 1).collect();
+        let message_bits = alloc_bits(cs.namespace(|| "message"), &message_bits);
+        let pk_var = EdwardsPoint::witness(cs.namespace(|| "pk"), Some(pk.0.clone()), params).unwrap();
+        let r_bits = alloc_bits(cs.namespace(|| "r"), &scalar_bits(r));
+        let sk_bits = alloc_bits(cs.namespace(|| "sk"), &scalar_bits(sk.0));
+
+        let encoded = elgamal::encode_message(cs.namespace(|| "encode"), &message_bits, p_g, params).unwrap();
+        let c = elgamal::Ciphertext::encrypt(cs.namespace(|| "encrypt"), &encoded, &pk_var, &r_bits, p_g, params).unwrap();
+        let shared = elgamal::ecdh(cs.namespace(|| "ecdh"), &sk_bits, &c.c1, params).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        assert_point_eq(&c.c1, &expected.c1)?;
+        assert_point_eq(&c.c2, &expected.c2)?;
+        assert_point_eq(&shared, &::elgamal::ecdh(&sk, &PublicKey(expected.c1.clone()), params))?;
+    }
+
+    #[test]
+    fn eddsa_poseidon_matches_native(seed in seed(), tamper in any::<bool>()) {
+        check_circomlib_eddsa(seed, tamper, CircomlibHash::Poseidon)?;
+    }
+
+    #[test]
+    fn eddsa_mimc7_matches_native(seed in seed(), tamper in any::<bool>()) {
+        check_circomlib_eddsa(seed, tamper, CircomlibHash::Mimc7)?;
+    }
+
+    #[test]
+    fn eddsa_for_snark_matches_native(seed in seed(), msg in prop::collection::vec(any::<u8>(), 1..33), tamper in any::<bool>()) {
+        check_bytes_eddsa(seed, &msg, tamper, false)?;
+    }
+
+    #[test]
+    fn eddsa_raw_message_matches_native(seed in seed(), msg in prop::collection::vec(any::<u8>(), 1..17), tamper in any::<bool>()) {
+        check_bytes_eddsa(seed, &msg, tamper, true)?;
+    }
+
+    #[test]
+    fn redjubjub_randomization_matches_native(seed in seed()) {
+        check_redjubjub_randomization::<Bls12, _>(seed, &JubjubBls12::new())?;
+        check_redjubjub_randomization::<Bn256, _>(seed, &AltJubjubBn256::new())?;
+    }
+
+    #[test]
+    fn comparisons_match_native(a in comparand(), b in comparand(), x in comparand()) {
+        let mut cs = TestConstraintSystem::<Bn256>::new();
+        let a_var = alloc_num(cs.namespace(|| "a"), int_to_fr(a as u128));
+        let b_var = alloc_num(cs.namespace(|| "b"), int_to_fr(b as u128));
+        let x_var = alloc_num(cs.namespace(|| "x"), int_to_fr(x as u128));
+
+        let less = AllocatedNum::less_than(cs.namespace(|| "a < b"), &a_var, &b_var, 64).unwrap();
+        let less_or_equal = AllocatedNum::less_than_or_equal(cs.namespace(|| "a <= b"), &a_var, &b_var, 64).unwrap();
+        let in_range = AllocatedNum::is_in_range(cs.namespace(|| "a <= x < b"), &x_var, &a_var, &b_var, 64).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(less.get_value().unwrap(), a < b);
+        prop_assert_eq!(less_or_equal.get_value().unwrap(), a <= b);
+        prop_assert_eq!(in_range.get_value().unwrap(), a <= x && x < b);
+    }
+
+    #[test]
+    fn boolean_ops_match_native(a in boolean(), b in boolean(), c in boolean()) {
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+        let a_var = alloc_boolean(cs.namespace(|| "a"), a);
+        let b_var = alloc_boolean(cs.namespace(|| "b"), b);
+        let c_var = alloc_boolean(cs.namespace(|| "c"), c);
+        let (a, b, c) = (a.0, b.0, c.0);
+
+        let xor = Boolean::xor(cs.namespace(|| "xor"), &a_var, &b_var).unwrap();
+        let and = Boolean::and(cs.namespace(|| "and"), &a_var, &b_var).unwrap();
+        let ch = Boolean::sha256_ch(cs.namespace(|| "ch"), &a_var, &b_var, &c_var).unwrap();
+        let maj = Boolean::sha256_maj(cs.namespace(|| "maj"), &a_var, &b_var, &c_var).unwrap();
+
+        // The allocated bits of the same values
+        let a_bit = AllocatedBit::alloc(cs.namespace(|| "a bit"), Some(a)).unwrap();
+        let b_bit = AllocatedBit::alloc(cs.namespace(|| "b bit"), Some(b)).unwrap();
+        let bit_xor = AllocatedBit::xor(cs.namespace(|| "bit xor"), &a_bit, &b_bit).unwrap();
+        let bit_and = AllocatedBit::and(cs.namespace(|| "bit and"), &a_bit, &b_bit).unwrap();
+        let bit_and_not = AllocatedBit::and_not(cs.namespace(|| "bit and not"), &a_bit, &b_bit).unwrap();
+        let bit_nor = AllocatedBit::nor(cs.namespace(|| "bit nor"), &a_bit, &b_bit).unwrap();
+        Boolean::enforce_equal(cs.namespace(|| "a equality"), &a_var, &Boolean::from(a_bit)).unwrap();
+
+        prop_assert!(cs.is_satisfied());
+        prop_assert_eq!(xor.get_value().unwrap(), a ^ b);
+        prop_assert_eq!(and.get_value().unwrap(), a & b);
+        prop_assert_eq!(ch.get_value().unwrap(), (a & b) ^ (!a & c));
+        prop_assert_eq!(maj.get_value().unwrap(), (a & b) ^ (a & c) ^ (b & c));
+        prop_assert_eq!(bit_xor.get_value().unwrap(), a ^ b);
+        prop_assert_eq!(bit_and.get_value().unwrap(), a & b);
+        prop_assert_eq!(bit_and_not.get_value().unwrap(), a & !b);
+        prop_assert_eq!(bit_nor.get_value().unwrap(), !a & !b);
+
+        // Unequal booleans are not satisfiable, and for constants
+        // this is found during synthesis
+        let unequal = Boolean::enforce_equal(cs.namespace(|| "b inequality"), &b_var, &Boolean::constant(!b));
+        match unequal {
+            Ok(()) => prop_assert!(!cs.is_satisfied()),
+            Err(_) => prop_assert!(b_var.is_constant())
+        }
+    }
+}
@@ -17,6 +17,7 @@ use bellman::{
 };
 
 use std::collections::HashMap;
+use std::fmt;
 use std::fmt::Write;
 
 use byteorder::{BigEndian, ByteOrder};
@@ -26,6 +27,9 @@ use std::collections::HashSet;
 
 use blake2_rfc::blake2s::Blake2s;
 
+#[cfg(test)]
+mod differential;
+
 #[derive(Debug)]
 enum NamedObject {
     Constraint(usize),
@@ -152,6 +156,59 @@ fn eval_lc<E: Engine>(
     acc
 }
 
+/// A term of a linear combination evaluated under the current assignment
+pub struct EvaluatedTerm<E: Engine> {
+    pub name: String,
+    pub coeff: E::Fr,
+    /// Value assigned to the variable
+    pub value: E::Fr
+}
+
+/// A linear combination with its terms and value
+pub struct EvaluatedLc<E: Engine> {
+    pub terms: Vec<EvaluatedTerm<E>>,
+    pub value: E::Fr
+}
+
+/// Diagnostics of a constraint that does not hold,
+/// i.e. `a.value * b.value != c.value`
+pub struct UnsatisfiedConstraint<E: Engine> {
+    /// Index of the constraint in the order of enforcement
+    pub index: usize,
+    pub path: String,
+    /// Namespaces the constraint was enforced in
+    pub namespace: Vec<String>,
+    pub a: EvaluatedLc<E>,
+    pub b: EvaluatedLc<E>,
+    pub c: EvaluatedLc<E>
+}
+
+impl<E: Engine> fmt::Display for EvaluatedLc<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.value)?;
+
+        for term in self.terms.iter() {
+            writeln!(f, "      {} * `{}` = {}", term.coeff, term.name, term.value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: Engine> fmt::Display for UnsatisfiedConstraint<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut ab = self.a.value;
+        ab.mul_assign(&self.b.value);
+
+        writeln!(f, "constraint #{} `{}` is unsatisfied", self.index, self.path)?;
+        writeln!(f, "  namespace: {}", self.namespace.join(" > "))?;
+        write!(f, "  A = {}", self.a)?;
+        write!(f, "  B = {}", self.b)?;
+        write!(f, "  C = {}", self.c)?;
+        writeln!(f, "  A * B = {}", ab)
+    }
+}
+
 impl<E: Engine> TestConstraintSystem<E> {
     pub fn new() -> TestConstraintSystem<E> {
         let mut map = HashMap::new();
@@ -320,6 +377,67 @@ impl<E: Engine> TestConstraintSystem<E> {
         None
     }
 
+    fn evaluate_lc(&self, lc: &LinearCombination<E>) -> EvaluatedLc<E> {
+        let terms = proc_lc::<E>(lc.as_ref()).into_iter().map(|(var, coeff)| {
+            let &(ref value, ref name) = match var.0.get_unchecked() {
+                Index::Input(i) => &self.inputs[i],
+                Index::Aux(i) => &self.aux[i]
+            };
+
+            EvaluatedTerm {
+                name: name.clone(),
+                coeff: coeff,
+                value: *value
+            }
+        }).collect();
+
+        EvaluatedLc {
+            terms: terms,
+            value: eval_lc::<E>(lc.as_ref(), &self.inputs, &self.aux)
+        }
+    }
+
+    /// Returns the diagnostics of all of the constraints that do not hold
+    pub fn unsatisfied_constraints(&self) -> Vec<UnsatisfiedConstraint<E>> {
+        let mut result = vec![];
+
+        for (index, &(ref a, ref b, ref c, ref path)) in self.constraints.iter().enumerate() {
+            let a = self.evaluate_lc(a);
+            let b = self.evaluate_lc(b);
+            let c = self.evaluate_lc(c);
+
+            let mut ab = a.value;
+            ab.mul_assign(&b.value);
+
+            if ab != c.value {
+                let mut namespace: Vec<String> = path.split('/').map(|s| s.to_string()).collect();
+                namespace.pop();
+
+                result.push(UnsatisfiedConstraint {
+                    index: index,
+                    path: path.clone(),
+                    namespace: namespace,
+                    a: a,
+                    b: b,
+                    c: c
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Formats all of the constraints that do not hold
+    pub fn format_unsatisfied(&self) -> String {
+        let mut s = String::new();
+
+        for constraint in self.unsatisfied_constraints() {
+            write!(&mut s, "{}", constraint).unwrap();
+        }
+
+        s
+    }
+
     pub fn is_satisfied(&self) -> bool
     {
         self.which_is_unsatisfied().is_none()
@@ -531,10 +649,22 @@ fn test_cs() {
     assert!(!cs.is_satisfied());
     assert!(cs.which_is_unsatisfied() == Some("mult"));
 
+    let unsatisfied = cs.unsatisfied_constraints();
+    assert_eq!(unsatisfied.len(), 1);
+    assert_eq!(unsatisfied[0].index, 0);
+    assert_eq!(unsatisfied[0].path, "mult");
+    assert!(unsatisfied[0].namespace.is_empty());
+    assert_eq!(unsatisfied[0].a.terms.len(), 1);
+    assert_eq!(unsatisfied[0].a.terms[0].name, "a/var");
+    assert_eq!(unsatisfied[0].a.value, Fr::from_str("4").unwrap());
+    assert_eq!(unsatisfied[0].c.value, Fr::from_str("40").unwrap());
+    assert!(cs.format_unsatisfied().contains("`a/var`"));
+
     assert!(cs.get("product") == Fr::from_str("40").unwrap());
 
     cs.set("product", Fr::from_str("16").unwrap());
     assert!(cs.is_satisfied());
+    assert!(cs.unsatisfied_constraints().is_empty());
 
     {
         let mut cs = cs.namespace(|| "test1");
@@ -544,3 +674,63 @@ fn test_cs() {
 
     assert!(cs.get("test1/test2/hehe") == Fr::one());
 }
+
+#[test]
+fn test_unsatisfied_constraints() {
+    use pairing::bls12_381::{Bls12, Fr};
+    use ff::PrimeField;
+
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+    let a = cs.alloc(|| "a", || Ok(Fr::from_str("4").unwrap())).unwrap();
+    let c = cs.alloc(|| "c", || Ok(Fr::from_str("12").unwrap())).unwrap();
+    let one = TestConstraintSystem::<Bls12>::one();
+
+    {
+        let mut cs = cs.namespace(|| "outer");
+        let mut cs = cs.namespace(|| "inner");
+        cs.enforce(
+            || "triple",
+            |lc| lc + (Fr::from_str("2").unwrap(), a) + a,
+            |lc| lc + one,
+            |lc| lc + c
+        );
+        cs.enforce(
+            || "square",
+            |lc| lc + a,
+            |lc| lc + a,
+            |lc| lc + c
+        );
+    }
+
+    let unsatisfied = cs.unsatisfied_constraints();
+    assert_eq!(unsatisfied.len(), 1);
+    assert_eq!(unsatisfied[0].index, 1);
+    assert_eq!(unsatisfied[0].path, "outer/inner/square");
+    assert_eq!(unsatisfied[0].namespace, vec!["outer".to_string(), "inner".to_string()]);
+    assert_eq!(unsatisfied[0].c.value, Fr::from_str("12").unwrap());
+
+    cs.set("c", Fr::from_str("13").unwrap());
+
+    let unsatisfied = cs.unsatisfied_constraints();
+    assert_eq!(unsatisfied.len(), 2);
+    assert_eq!(cs.which_is_unsatisfied(), Some(&*unsatisfied[0].path));
+
+    // Terms of the same variable are merged
+    let triple = &unsatisfied[0];
+    assert_eq!(triple.a.terms.len(), 1);
+    assert_eq!(triple.a.terms[0].name, "a");
+    assert_eq!(triple.a.terms[0].coeff, Fr::from_str("3").unwrap());
+    assert_eq!(triple.a.terms[0].value, Fr::from_str("4").unwrap());
+    assert_eq!(triple.b.terms[0].name, "ONE");
+    assert_eq!(triple.c.value, Fr::from_str("13").unwrap());
+
+    let formatted = cs.format_unsatisfied();
+    assert!(formatted.contains("constraint #0 `outer/inner/triple` is unsatisfied"));
+    assert!(formatted.contains("namespace: outer > inner"));
+    assert!(formatted.contains("`c`"));
+    assert!(formatted.contains(&format!(
+        "{} * `a` = {}\n",
+        Fr::from_str("3").unwrap(),
+        Fr::from_str("4").unwrap()
+    )));
+}
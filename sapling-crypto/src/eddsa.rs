@@ -2,7 +2,7 @@
 //! Generation of randomness is not specified
 
 use ff::{Field, PrimeField, PrimeFieldRepr};
-use rand::{Rng};
+use rand::{Rng, Rand};
 use std::io::{self, Read, Write};
 
 use jubjub::{
@@ -10,9 +10,13 @@ use jubjub::{
     JubjubEngine, 
     JubjubParams, 
     Unknown, 
+    PrimeOrder,
     edwards::Point,
     ToUniform};
 use util::{hash_to_scalar, hash_to_scalar_s};
+use mimc7::{Mimc7Params, mimc7_multi_hash};
+use poseidon::{PoseidonParams, poseidon_hash};
+use blake512::blake512;
 
 use ::constants::{MATTER_EDDSA_BLAKE2S_PERSONALIZATION};
 
@@ -58,6 +62,107 @@ pub struct PrivateKey<E: JubjubEngine>(pub E::Fs);
 #[derive(Clone)]
 pub struct PublicKey<E: JubjubEngine>(pub Point<E, Unknown>);
 
+/// Hash functions used by circomlib's EdDSA variants
+#[derive(Copy, Clone)]
+pub enum CircomlibHash {
+    Mimc7,
+    Poseidon
+}
+
+/// Parameters of EdDSA as implemented by circomlib. circomlib uses
+/// Baby Jubjub in the form `168700.x^2 + y^2 = 1 + 168696.x^2.y^2`,
+/// that maps to our curve by dividing x coordinates by `x_scaling`,
+/// and its Base8 point as a generator.
+pub struct CircomlibEddsaParams<E: JubjubEngine> {
+    generator: Point<E, PrimeOrder>,
+    x_scaling: E::Fr,
+    mimc7_params: Mimc7Params<E>,
+    poseidon_params: PoseidonParams<E>
+}
+
+impl<E: JubjubEngine> CircomlibEddsaParams<E> {
+    /// Parameters for a curve whose x coordinates are the ones of circomlib
+    /// divided by `x_scaling`, with the generator given by its coordinates
+    /// as seen by circomlib. Returns `None` if the generator is not in the
+    /// prime order subgroup.
+    pub fn from_circomlib_generator(
+        x_scaling: E::Fr,
+        generator_x: E::Fr,
+        generator_y: E::Fr,
+        mimc7_params: Mimc7Params<E>,
+        poseidon_params: PoseidonParams<E>,
+        params: &E::Params
+    ) -> Option<Self> {
+        let mut tmp_params = CircomlibEddsaParams {
+            generator: Point::zero(),
+            x_scaling: x_scaling,
+            mimc7_params: mimc7_params,
+            poseidon_params: poseidon_params
+        };
+
+        let generator = tmp_params.from_circomlib_xy(generator_x, generator_y, params)?;
+        tmp_params.generator = generator.as_prime_order(params)?;
+
+        Some(tmp_params)
+    }
+
+    pub fn generator(&self) -> &Point<E, PrimeOrder> {
+        &self.generator
+    }
+
+    pub fn x_scaling(&self) -> &E::Fr {
+        &self.x_scaling
+    }
+
+    pub fn mimc7_params(&self) -> &Mimc7Params<E> {
+        &self.mimc7_params
+    }
+
+    pub fn poseidon_params(&self) -> &PoseidonParams<E> {
+        &self.poseidon_params
+    }
+
+    /// Returns coordinates of the point as seen by circomlib
+    pub fn into_circomlib_xy<Order>(&self, p: &Point<E, Order>) -> (E::Fr, E::Fr) {
+        let (mut x, y) = p.into_xy();
+        x.mul_assign(&self.x_scaling);
+
+        (x, y)
+    }
+
+    /// Interprets coordinates of a point as seen by circomlib
+    pub fn from_circomlib_xy(&self, x: E::Fr, y: E::Fr, params: &E::Params) -> Option<Point<E, Unknown>> {
+        let mut x = x;
+        x.mul_assign(&self.x_scaling.inverse().expect("scaling is not zero"));
+
+        Point::from_xy(x, y, params)
+    }
+
+    /// H(R_x, R_y, A_x, A_y, M) with coordinates as seen by circomlib
+    fn hash_signature_parts(
+        &self,
+        r: &Point<E, Unknown>,
+        pk: &Point<E, Unknown>,
+        msg: &E::Fr,
+        hash: CircomlibHash
+    ) -> E::Fs {
+        let (r_x, r_y) = self.into_circomlib_xy(r);
+        let (pk_x, pk_y) = self.into_circomlib_xy(pk);
+
+        let inputs = [r_x, r_y, pk_x, pk_y, *msg];
+
+        let h = match hash {
+            CircomlibHash::Mimc7 => mimc7_multi_hash(&inputs, &E::Fr::zero(), &self.mimc7_params),
+            CircomlibHash::Poseidon => poseidon_hash(&inputs, &self.poseidon_params)
+        };
+
+        let mut h_bytes = [0u8; 32];
+        h.into_repr().write_le(& mut h_bytes[..]).expect("has serialized h");
+
+        E::Fs::to_uniform_32(&h_bytes[..])
+    }
+}
+
 impl SerializedSignature {
     pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
         let mut rbar = [0u8; 32];
@@ -89,6 +194,53 @@ impl<E: JubjubEngine> PrivateKey<E> {
         write_scalar::<E, W>(&self.0, writer)
     }
 
+    /// Derives the private key of a circomlib private key seed as
+    /// circomlib's `prv2pub` does: the first half of BLAKE-512(seed),
+    /// pruned, is circomlib's secret scalar, which is eight times ours.
+    pub fn from_circomlib_seed(seed: &[u8]) -> Self {
+        let h = blake512(seed);
+
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&h[..32]);
+        s[0] &= 0xf8;
+        s[31] &= 0x7f;
+        s[31] |= 0x40;
+
+        // s >> 3, which is exact after pruning
+        let mut shifted = [0u8; 32];
+        for i in 0..32 {
+            shifted[i] = s[i] >> 3;
+            if i < 31 {
+                shifted[i] |= s[i + 1] << 5;
+            }
+        }
+
+        PrivateKey(E::Fs::to_uniform_32(&shifted[..]))
+    }
+
+    /// Signs a field element with a circomlib private key seed exactly as
+    /// circomlib's `signMiMC` and `signPoseidon` do, the nonce being
+    /// BLAKE-512 of the second half of BLAKE-512(seed) and the message.
+    pub fn sign_with_circomlib_seed(
+        seed: &[u8],
+        msg: &E::Fr,
+        hash: CircomlibHash,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Signature<E> {
+        let h = blake512(seed);
+
+        let mut nonce_input = h[32..].to_vec();
+        let mut msg_bytes = [0u8; 32];
+        msg.into_repr().write_le(& mut msg_bytes[..]).expect("has serialized msg");
+        nonce_input.extend_from_slice(&msg_bytes[..]);
+
+        // r = BLAKE-512(h[32..64] || M) mod l
+        let r = E::Fs::to_uniform(&blake512(&nonce_input)[..]);
+
+        Self::from_circomlib_seed(seed).sign_circomlib_with_nonce(msg, r, hash, params, circomlib_params)
+    }
+
     pub fn sign_raw_message<R: Rng>(
         &self,
         msg: &[u8],
@@ -241,6 +393,70 @@ impl<E: JubjubEngine> PrivateKey<E> {
         let as_unknown = Point::from(r_g);
         Signature { r: as_unknown, s: s }
     }
+
+    /// Signs a field element as circomlib's EdDSA-MiMC does, but with a
+    /// random nonce: signatures verify with circomlib yet differ from
+    /// circomlib's deterministic ones, see `sign_with_circomlib_seed`.
+    pub fn sign_mimc7<R: Rng>(
+        &self,
+        msg: &E::Fr,
+        rng: &mut R,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Signature<E> {
+        let r = self.circomlib_nonce(msg, rng);
+
+        self.sign_circomlib_with_nonce(msg, r, CircomlibHash::Mimc7, params, circomlib_params)
+    }
+
+    /// Signs a field element as circomlib's EdDSA-Poseidon does.
+    /// See `sign_mimc7` for compatibility notes.
+    pub fn sign_poseidon<R: Rng>(
+        &self,
+        msg: &E::Fr,
+        rng: &mut R,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Signature<E> {
+        let r = self.circomlib_nonce(msg, rng);
+
+        self.sign_circomlib_with_nonce(msg, r, CircomlibHash::Poseidon, params, circomlib_params)
+    }
+
+    fn circomlib_nonce<R: Rng>(&self, msg: &E::Fr, rng: &mut R) -> E::Fs {
+        // T = (l_H + 128) bits of randomness
+        // For H*, l_H = 512 bits
+        let mut t = [0u8; 80];
+        rng.fill_bytes(&mut t[..]);
+
+        let mut msg_bytes = [0u8; 32];
+        msg.into_repr().write_le(& mut msg_bytes[..]).expect("has serialized msg");
+
+        // r = H*(T || M)
+        h_star::<E>(&t[..], &msg_bytes[..])
+    }
+
+    fn sign_circomlib_with_nonce(
+        &self,
+        msg: &E::Fr,
+        r: E::Fs,
+        hash: CircomlibHash,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Signature<E> {
+        let pk = PublicKey::from_private_circomlib(&self, params, circomlib_params);
+
+        // R = r . B8
+        let r_g: Point<E, Unknown> = circomlib_params.generator().mul(r, params).into();
+
+        // S = r + H(R || A || M) . 8 . sk
+        let mut s = circomlib_params.hash_signature_parts(&r_g, &pk.0, msg, hash);
+        s.mul_assign(&self.0);
+        s.mul_assign(&E::Fs::from_str("8").unwrap());
+        s.add_assign(&r);
+
+        Signature { r: r_g, s: s }
+    }
 }
 
 impl<E: JubjubEngine> PublicKey<E> {
@@ -249,6 +465,18 @@ impl<E: JubjubEngine> PublicKey<E> {
         PublicKey(res)
     }
 
+    /// Public key for circomlib's EdDSA variants. circomlib's secret
+    /// scalar is eight times the one of this private key, which is
+    /// derived from a circomlib seed by `PrivateKey::from_circomlib_seed`.
+    pub fn from_private_circomlib(
+        privkey: &PrivateKey<E>,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> Self {
+        let res = circomlib_params.generator().mul(privkey.0, params).into();
+        PublicKey(res)
+    }
+
     pub fn randomize(&self, alpha: E::Fs, p_g: FixedGenerators, params: &E::Params) -> Self {
         let res: Point<E, Unknown> = params.generator(p_g).mul(alpha, params).into();
         let res = res.add(&self.0, params);
@@ -264,13 +492,7 @@ impl<E: JubjubEngine> PublicKey<E> {
         self.0.write(writer)
     }
 
-    pub fn verify(
-        &self,
-        msg: &[u8],
-        sig: &Signature<E>,
-        p_g: FixedGenerators,
-        params: &E::Params,
-    ) -> bool {
+    fn challenge(&self, msg: &[u8], sig: &Signature<E>) -> E::Fs {
         // c = H*(Rbar || Pk || M)
         let (r_g_x, r_g_y) = sig.r.into_xy();
         let mut r_g_x_bytes = [0u8; 32];
@@ -288,7 +510,46 @@ impl<E: JubjubEngine> PublicKey<E> {
 
         let concatenated: Vec<u8> = r_g_x_bytes.iter().chain(r_g_y_bytes.iter()).chain(pk_x_bytes.iter()).chain(pk_y_bytes.iter()).cloned().collect();
 
-        let c = h_star::<E>(&concatenated[..], msg);
+        h_star::<E>(&concatenated[..], msg)
+    }
+
+    fn challenge_for_raw_message(msg: &[u8], max_message_size: usize) -> E::Fs {
+        // c = M
+
+        assert!(msg.len() <= max_message_size);
+        assert!(max_message_size * 8 <= E::Fs::CAPACITY as usize);
+        // assert!(max_message_size * 8 <= E::Fs::Repr::len());
+        // we also pad message to max size
+
+        // pad with zeroes to match representation length
+        let mut msg_padded : Vec<u8> = msg.iter().cloned().collect();
+        msg_padded.resize(32, 0u8);
+
+        E::Fs::to_uniform_32(msg_padded.as_ref())
+    }
+
+    fn challenge_for_snark(msg: &[u8], sig: &Signature<E>) -> E::Fs {
+        // c = H*(R_x || M)
+        let (r_g_x, _) = sig.r.into_xy();
+        let mut r_g_x_bytes = [0u8; 32];
+        r_g_x.into_repr().write_le(& mut r_g_x_bytes[..]).expect("has serialized r_g_x");
+
+        let concatenated: Vec<u8> = r_g_x_bytes.iter().cloned().collect();
+
+        let mut msg_padded : Vec<u8> = msg.iter().cloned().collect();
+        msg_padded.resize(32, 0u8);
+
+        h_star_s::<E>(&concatenated[..], &msg_padded[..])
+    }
+
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        sig: &Signature<E>,
+        p_g: FixedGenerators,
+        params: &E::Params,
+    ) -> bool {
+        let c = self.challenge(msg, sig);
 
         // this one is for a simple sanity check. In application purposes the pk will always be in a right group 
         let order_check_pk = self.0.mul(E::Fs::char(), params);
@@ -324,18 +585,7 @@ impl<E: JubjubEngine> PublicKey<E> {
         params: &E::Params,
         max_message_size: usize,
     ) -> bool {
-        // c = M
-
-        assert!(msg.len() <= max_message_size);
-        assert!(max_message_size * 8 <= E::Fs::CAPACITY as usize);
-        // assert!(max_message_size * 8 <= E::Fs::Repr::len());
-        // we also pad message to max size
-
-        // pad with zeroes to match representation length
-        let mut msg_padded : Vec<u8> = msg.iter().cloned().collect();
-        msg_padded.resize(32, 0u8);
-
-        let c = E::Fs::to_uniform_32(msg_padded.as_ref());
+        let c = Self::challenge_for_raw_message(msg, max_message_size);
 
         // this one is for a simple sanity check. In application purposes the pk will always be in a right group 
         let order_check_pk = self.0.mul(E::Fs::char(), params);
@@ -372,17 +622,7 @@ impl<E: JubjubEngine> PublicKey<E> {
         p_g: FixedGenerators,
         params: &E::Params,
     ) -> bool {
-        // c = H*(R_x || M)
-        let (r_g_x, _) = sig.r.into_xy();
-        let mut r_g_x_bytes = [0u8; 32];
-        r_g_x.into_repr().write_le(& mut r_g_x_bytes[..]).expect("has serialized r_g_x");
-
-        let concatenated: Vec<u8> = r_g_x_bytes.iter().cloned().collect();
-
-        let mut msg_padded : Vec<u8> = msg.iter().cloned().collect();
-        msg_padded.resize(32, 0u8);
-
-        let c = h_star_s::<E>(&concatenated[..], &msg_padded[..]);
+        let c = Self::challenge_for_snark(msg, sig);
 
         // this one is for a simple sanity check. In application purposes the pk will always be in a right group 
         let order_check_pk = self.0.mul(E::Fs::char(), params);
@@ -410,6 +650,47 @@ impl<E: JubjubEngine> PublicKey<E> {
         ).eq(&Point::zero())
     }
 
+    pub fn verify_mimc7(
+        &self,
+        msg: &E::Fr,
+        sig: &Signature<E>,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> bool {
+        self.verify_circomlib(msg, sig, CircomlibHash::Mimc7, params, circomlib_params)
+    }
+
+    pub fn verify_poseidon(
+        &self,
+        msg: &E::Fr,
+        sig: &Signature<E>,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> bool {
+        self.verify_circomlib(msg, sig, CircomlibHash::Poseidon, params, circomlib_params)
+    }
+
+    fn verify_circomlib(
+        &self,
+        msg: &E::Fr,
+        sig: &Signature<E>,
+        hash: CircomlibHash,
+        params: &E::Params,
+        circomlib_params: &CircomlibEddsaParams<E>
+    ) -> bool {
+        // c = H(R || A || M)
+        let c = circomlib_params.hash_signature_parts(&sig.r, &self.0, msg, hash);
+
+        // S . B8 = R + c . 8 . A, which also holds for small order
+        // components of the public key
+        let pk_mul_hash: Point<E, Unknown> = self.0.mul_by_cofactor(params).mul(c, params).into();
+
+        pk_mul_hash.add(&sig.r, params).add(
+            &circomlib_params.generator().mul(sig.s, params).negate().into(),
+            params
+        ).eq(&Point::zero())
+    }
+
     pub fn verify_serialized(
         &self,
         msg: &[u8],
@@ -440,9 +721,280 @@ impl<E: JubjubEngine> PublicKey<E> {
     }
 }
 
+/// Signature of a byte message, to be checked in a batch
+pub struct BatchEntry<'a, E: JubjubEngine> {
+    pub vk: PublicKey<E>,
+    pub msg: &'a [u8],
+    pub sig: Signature<E>,
+}
+
+/// Signature of a field element as made by circomlib,
+/// to be checked in a batch
+pub struct CircomlibBatchEntry<E: JubjubEngine> {
+    pub vk: PublicKey<E>,
+    pub msg: E::Fr,
+    pub sig: Signature<E>,
+}
+
+/// Parts of the equation 0 = -S . P_G + R + c . vk
+struct PreparedEntry<E: JubjubEngine> {
+    vk: Point<E, Unknown>,
+    r: Point<E, Unknown>,
+    c: E::Fs,
+    s: E::Fs,
+}
+
+fn is_in_prime_order_subgroup<E: JubjubEngine>(p: &Point<E, Unknown>, params: &E::Params) -> bool {
+    p.mul(E::Fs::char(), params).eq(&Point::zero())
+}
+
+/// Reads `c` bits of the scalar starting from the bit `start`
+fn get_window<F: PrimeField>(repr: &F::Repr, start: usize, c: usize) -> usize {
+    let limbs = repr.as_ref();
+
+    let mut digit = 0;
+    for i in 0..c {
+        let bit = start + i;
+        if bit < limbs.len() * 64 && (limbs[bit / 64] >> (bit % 64)) & 1 == 1 {
+            digit |= 1 << i;
+        }
+    }
+
+    digit
+}
+
+/// Computes sum of scalars[i] . bases[i] using Pippenger's bucket method
+fn multiscalar_mul<E: JubjubEngine>(
+    bases: &[Point<E, Unknown>],
+    scalars: &[E::Fs],
+    params: &E::Params
+) -> Point<E, Unknown>
+{
+    assert_eq!(bases.len(), scalars.len());
+
+    let c = if bases.len() < 32 {
+        3
+    } else {
+        (f64::from(bases.len() as u32)).ln().ceil() as usize
+    };
+
+    let reprs: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+
+    let mut acc = Point::zero();
+    let mut window_start = ((E::Fs::NUM_BITS as usize - 1) / c) * c;
+
+    loop {
+        for _ in 0..c {
+            acc = acc.double(params);
+        }
+
+        let mut buckets = vec![Point::<E, Unknown>::zero(); (1 << c) - 1];
+        for (base, repr) in bases.iter().zip(reprs.iter()) {
+            let digit = get_window::<E::Fs>(repr, window_start, c);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].add(base, params);
+            }
+        }
+
+        // sum of j . buckets[j - 1] as a sum of running sums
+        let mut running_sum = Point::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum = running_sum.add(&bucket, params);
+            acc = acc.add(&running_sum, params);
+        }
+
+        if window_start == 0 {
+            break;
+        }
+        window_start -= c;
+    }
+
+    acc
+}
+
+/// Checks 0 = sum z_i . (-S_i . P_G + R_i + c_i . vk_i) for random z_i
+/// with a single multiscalar multiplication. If the batch does not pass,
+/// entries are checked one by one to find the failing ones. `None` entries
+/// did not pass preliminary checks and are reported as failing.
+fn batch_verify_prepared<E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    entries: &[Option<PreparedEntry<E>>],
+    generator: Point<E, Unknown>,
+    params: &E::Params,
+) -> Result<(), Vec<usize>>
+{
+    let mut bases = Vec::with_capacity(entries.len() * 2 + 1);
+    let mut scalars = Vec::with_capacity(entries.len() * 2 + 1);
+    let mut generator_scalar = E::Fs::zero();
+
+    for entry in entries.iter().filter_map(|e| e.as_ref()) {
+        let z = E::Fs::rand(rng);
+
+        let mut s = entry.s;
+        s.mul_assign(&z);
+        generator_scalar.sub_assign(&s);
+
+        let mut c = entry.c;
+        c.mul_assign(&z);
+
+        bases.push(entry.r.clone());
+        scalars.push(z);
+        bases.push(entry.vk.clone());
+        scalars.push(c);
+    }
+
+    bases.push(generator.clone());
+    scalars.push(generator_scalar);
+
+    let batch_is_valid = multiscalar_mul(&bases, &scalars, params).eq(&Point::zero());
+
+    let failed: Vec<usize> = entries.iter().enumerate().filter(|&(_, entry)| {
+        match *entry {
+            None => true,
+            Some(_) if batch_is_valid => false,
+            Some(ref e) => !e.vk.mul(e.c, params).add(&e.r, params).add(
+                &generator.mul(e.s, params).negate(),
+                params
+            ).eq(&Point::zero())
+        }
+    }).map(|(i, _)| i).collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed)
+    }
+}
+
+/// Batch version of `PublicKey::verify`. Returns indices
+/// of the entries that do not pass verification.
+pub fn batch_verify<'a, E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[BatchEntry<'a, E>],
+    p_g: FixedGenerators,
+    params: &E::Params,
+) -> Result<(), Vec<usize>>
+{
+    let entries: Vec<_> = batch.iter().map(|entry| {
+        if !is_in_prime_order_subgroup(&entry.vk.0, params) || !is_in_prime_order_subgroup(&entry.sig.r, params) {
+            return None;
+        }
+
+        Some(PreparedEntry {
+            vk: entry.vk.0.clone(),
+            r: entry.sig.r.clone(),
+            c: entry.vk.challenge(entry.msg, &entry.sig),
+            s: entry.sig.s
+        })
+    }).collect();
+
+    batch_verify_prepared(rng, &entries, params.generator(p_g).clone().into(), params)
+}
+
+/// Batch version of `PublicKey::verify_for_raw_message`. Returns
+/// indices of the entries that do not pass verification.
+pub fn batch_verify_for_raw_message<'a, E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[BatchEntry<'a, E>],
+    p_g: FixedGenerators,
+    params: &E::Params,
+    max_message_size: usize,
+) -> Result<(), Vec<usize>>
+{
+    let entries: Vec<_> = batch.iter().map(|entry| {
+        if !is_in_prime_order_subgroup(&entry.vk.0, params) || !is_in_prime_order_subgroup(&entry.sig.r, params) {
+            return None;
+        }
+
+        Some(PreparedEntry {
+            vk: entry.vk.0.clone(),
+            r: entry.sig.r.clone(),
+            c: PublicKey::<E>::challenge_for_raw_message(entry.msg, max_message_size),
+            s: entry.sig.s
+        })
+    }).collect();
+
+    batch_verify_prepared(rng, &entries, params.generator(p_g).clone().into(), params)
+}
+
+/// Batch version of `PublicKey::verify_for_snark`. Returns
+/// indices of the entries that do not pass verification.
+pub fn batch_verify_for_snark<'a, E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[BatchEntry<'a, E>],
+    p_g: FixedGenerators,
+    params: &E::Params,
+) -> Result<(), Vec<usize>>
+{
+    let entries: Vec<_> = batch.iter().map(|entry| {
+        if !is_in_prime_order_subgroup(&entry.vk.0, params) || !is_in_prime_order_subgroup(&entry.sig.r, params) {
+            return None;
+        }
+
+        Some(PreparedEntry {
+            vk: entry.vk.0.clone(),
+            r: entry.sig.r.clone(),
+            c: PublicKey::<E>::challenge_for_snark(entry.msg, &entry.sig),
+            s: entry.sig.s
+        })
+    }).collect();
+
+    batch_verify_prepared(rng, &entries, params.generator(p_g).clone().into(), params)
+}
+
+fn batch_verify_circomlib<E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[CircomlibBatchEntry<E>],
+    hash: CircomlibHash,
+    params: &E::Params,
+    circomlib_params: &CircomlibEddsaParams<E>
+) -> Result<(), Vec<usize>>
+{
+    let entries: Vec<_> = batch.iter().map(|entry| {
+        // single signature check rejects R with small order
+        // components, while a random linear combination may not
+        if !is_in_prime_order_subgroup(&entry.sig.r, params) {
+            return None;
+        }
+
+        Some(PreparedEntry {
+            vk: entry.vk.0.mul_by_cofactor(params).into(),
+            r: entry.sig.r.clone(),
+            c: circomlib_params.hash_signature_parts(&entry.sig.r, &entry.vk.0, &entry.msg, hash),
+            s: entry.sig.s
+        })
+    }).collect();
+
+    batch_verify_prepared(rng, &entries, circomlib_params.generator().clone().into(), params)
+}
+
+/// Batch version of `PublicKey::verify_mimc7`. Returns
+/// indices of the entries that do not pass verification.
+pub fn batch_verify_mimc7<E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[CircomlibBatchEntry<E>],
+    params: &E::Params,
+    circomlib_params: &CircomlibEddsaParams<E>
+) -> Result<(), Vec<usize>>
+{
+    batch_verify_circomlib(rng, batch, CircomlibHash::Mimc7, params, circomlib_params)
+}
+
+/// Batch version of `PublicKey::verify_poseidon`. Returns
+/// indices of the entries that do not pass verification.
+pub fn batch_verify_poseidon<E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    batch: &[CircomlibBatchEntry<E>],
+    params: &E::Params,
+    circomlib_params: &CircomlibEddsaParams<E>
+) -> Result<(), Vec<usize>>
+{
+    batch_verify_circomlib(rng, batch, CircomlibHash::Poseidon, params, circomlib_params)
+}
+
 #[cfg(test)]
 mod baby_tests {
-    use pairing::bn256::Bn256;
+    use pairing::bn256::{Bn256, Fr};
     use rand::thread_rng;
 
     use alt_babyjubjub::{AltJubjubBn256, fs::Fs, edwards, FixedGenerators};
@@ -632,4 +1184,187 @@ mod baby_tests {
         let (x, y) = vk.0.into_xy();
         println!("Public generator x = {}, y = {}", x, y);
     }
-}
\ No newline at end of file
+
+    fn circomlib_test_vector() -> ([u8; 32], PrivateKey<Bn256>, Fs, Fr) {
+        // Private key seed of circomlib's tests, where our secret scalar is
+        // circomlib's one divided by eight and r is circomlib's nonce
+        let seed = hex!("0001020304050607080900010203040506070809000102030405060708090001");
+        let sk = PrivateKey::<Bn256>(Fs::from_str("994010219703001815340449613425044215384278165578404307786348688961164616823").unwrap());
+        let r = Fs::from_str("998509002261817064039893525009363315223455691288800741227950990424097427109").unwrap();
+        let msg = Fr::from_str("42649378395939397566720").unwrap();
+
+        (seed, sk, r, msg)
+    }
+
+    #[test]
+    fn circomlib_seed_derivation() {
+        let (seed, sk, _, _) = circomlib_test_vector();
+
+        assert!(PrivateKey::<Bn256>::from_circomlib_seed(&seed[..]).0 == sk.0);
+    }
+
+    #[test]
+    fn circomlib_public_key() {
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+        let (_, sk, _, _) = circomlib_test_vector();
+
+        let pk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+
+        assert_eq!(circomlib_params.into_circomlib_xy(&pk.0), (
+            Fr::from_str("13277427435165878497778222415993513565335242147425444199013288855685581939618").unwrap(),
+            Fr::from_str("13622229784656158136036771217484571176836296686641868549125388198837476602820").unwrap()
+        ));
+    }
+
+    #[test]
+    fn circomlib_mimc7_signature() {
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+        let (seed, sk, r, msg) = circomlib_test_vector();
+        let pk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+
+        let sig = sk.sign_circomlib_with_nonce(&msg, r, CircomlibHash::Mimc7, params, &circomlib_params);
+
+        let seed_sig = PrivateKey::sign_with_circomlib_seed(&seed[..], &msg, CircomlibHash::Mimc7, params, &circomlib_params);
+        assert!(seed_sig.r == sig.r);
+        assert!(seed_sig.s == sig.s);
+
+        assert_eq!(circomlib_params.into_circomlib_xy(&sig.r), (
+            Fr::from_str("11384336176656855268977457483345535180380036354188103142384839473266348197733").unwrap(),
+            Fr::from_str("15383486972088797283337779941324724402501462225528836549661220478783371668959").unwrap()
+        ));
+        assert_eq!(sig.s, Fs::from_str("2523202440825208709475937830811065542425109372212752003460238913256192595070").unwrap());
+
+        assert!(pk.verify_mimc7(&msg, &sig, params, &circomlib_params));
+        assert!(!pk.verify_poseidon(&msg, &sig, params, &circomlib_params));
+
+        let mut wrong_msg = msg;
+        wrong_msg.add_assign(&Fr::one());
+        assert!(!pk.verify_mimc7(&wrong_msg, &sig, params, &circomlib_params));
+    }
+
+    #[test]
+    fn circomlib_poseidon_signature() {
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+        let (seed, sk, r, msg) = circomlib_test_vector();
+        let pk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+
+        let sig = sk.sign_circomlib_with_nonce(&msg, r, CircomlibHash::Poseidon, params, &circomlib_params);
+
+        let seed_sig = PrivateKey::sign_with_circomlib_seed(&seed[..], &msg, CircomlibHash::Poseidon, params, &circomlib_params);
+        assert!(seed_sig.r == sig.r);
+        assert!(seed_sig.s == sig.s);
+
+        assert_eq!(sig.s, Fs::from_str("248298168863866362217836334079793350221620631973732197668910946177382043688").unwrap());
+
+        assert!(pk.verify_poseidon(&msg, &sig, params, &circomlib_params));
+        assert!(!pk.verify_mimc7(&msg, &sig, params, &circomlib_params));
+    }
+
+    #[test]
+    fn random_circomlib_signatures() {
+        let rng = &mut thread_rng();
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+
+        for _ in 0..10 {
+            let sk = PrivateKey::<Bn256>(rng.gen());
+            let pk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+            let msg: Fr = rng.gen();
+
+            let sig = sk.sign_mimc7(&msg, rng, params, &circomlib_params);
+            assert!(pk.verify_mimc7(&msg, &sig, params, &circomlib_params));
+
+            let sig = sk.sign_poseidon(&msg, rng, params, &circomlib_params);
+            assert!(pk.verify_poseidon(&msg, &sig, params, &circomlib_params));
+        }
+    }
+
+    #[test]
+    fn multiscalar_mul_is_sum_of_products() {
+        let rng = &mut thread_rng();
+        let params = &AltJubjubBn256::new();
+
+        for &n in [1, 5, 40].iter() {
+            let bases: Vec<edwards::Point<Bn256, Unknown>> = (0..n).map(|_| edwards::Point::rand(rng, params)).collect();
+            let scalars: Vec<Fs> = (0..n).map(|_| rng.gen()).collect();
+
+            let expected = bases.iter().zip(scalars.iter()).fold(edwards::Point::zero(), |acc, (b, s)| {
+                acc.add(&b.mul(*s, params), params)
+            });
+
+            assert!(multiscalar_mul(&bases, &scalars, params) == expected);
+        }
+    }
+
+    #[test]
+    fn batch_verification() {
+        let rng = &mut thread_rng();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        let msg = b"Foo bar pad to16";
+        let wrong_msg = b"Foo bar pad to15";
+
+        let keys: Vec<_> = (0..10).map(|_| {
+            let sk = PrivateKey::<Bn256>(rng.gen());
+            let vk = PublicKey::from_private(&sk, p_g, params);
+
+            (sk, vk)
+        }).collect();
+
+        let mut batch: Vec<_> = keys.iter().map(|&(ref sk, ref vk)| {
+            BatchEntry { vk: vk.clone(), msg: &msg[..], sig: sk.sign(msg, rng, p_g, params) }
+        }).collect();
+        assert_eq!(batch_verify(rng, &batch, p_g, params), Ok(()));
+
+        batch[3].msg = &wrong_msg[..];
+        batch[7].sig = batch[8].sig.clone();
+        assert_eq!(batch_verify(rng, &batch, p_g, params), Err(vec![3, 7]));
+
+        let mut batch: Vec<_> = keys.iter().map(|&(ref sk, ref vk)| {
+            BatchEntry { vk: vk.clone(), msg: &msg[..], sig: sk.sign_for_snark(msg, rng, p_g, params) }
+        }).collect();
+        assert_eq!(batch_verify_for_snark(rng, &batch, p_g, params), Ok(()));
+
+        batch[0].msg = &wrong_msg[..];
+        assert_eq!(batch_verify_for_snark(rng, &batch, p_g, params), Err(vec![0]));
+
+        let mut batch: Vec<_> = keys.iter().map(|&(ref sk, ref vk)| {
+            BatchEntry { vk: vk.clone(), msg: &msg[..], sig: sk.sign_raw_message(msg, rng, p_g, params, 16) }
+        }).collect();
+        assert_eq!(batch_verify_for_raw_message(rng, &batch, p_g, params, 16), Ok(()));
+
+        batch[9].msg = &wrong_msg[..];
+        assert_eq!(batch_verify_for_raw_message(rng, &batch, p_g, params, 16), Err(vec![9]));
+    }
+
+    #[test]
+    fn circomlib_batch_verification() {
+        let rng = &mut thread_rng();
+        let params = &AltJubjubBn256::new();
+        let circomlib_params = CircomlibEddsaParams::new(params);
+
+        let mut batch: Vec<_> = (0..10).map(|_| {
+            let sk = PrivateKey::<Bn256>(rng.gen());
+            let vk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+            let msg: Fr = rng.gen();
+            let sig = sk.sign_poseidon(&msg, rng, params, &circomlib_params);
+
+            CircomlibBatchEntry { vk: vk, msg: msg, sig: sig }
+        }).collect();
+        assert_eq!(batch_verify_poseidon(rng, &batch, params, &circomlib_params), Ok(()));
+        assert_eq!(batch_verify_mimc7(rng, &batch, params, &circomlib_params), Err((0..10).collect()));
+
+        batch[2].msg.add_assign(&Fr::one());
+        assert_eq!(batch_verify_poseidon(rng, &batch, params, &circomlib_params), Err(vec![2]));
+
+        let (seed, sk, r, msg) = circomlib_test_vector();
+        let vk = PublicKey::from_private_circomlib(&sk, params, &circomlib_params);
+        let sig = sk.sign_circomlib_with_nonce(&msg, r, CircomlibHash::Mimc7, params, &circomlib_params);
+        let batch = vec![CircomlibBatchEntry { vk: vk, msg: msg, sig: sig }];
+        assert_eq!(batch_verify_mimc7(rng, &batch, params, &circomlib_params), Ok(()));
+    }
+}
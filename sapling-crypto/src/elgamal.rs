@@ -0,0 +1,205 @@
+//! ElGamal encryption of curve points and ECDH key agreement,
+//! using the same keys as EdDSA. Small numbers can be encrypted
+//! by encoding them as multiples of the generator, making the
+//! ciphertexts additively homomorphic.
+
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use rand::{Rng, Rand};
+
+use jubjub::{
+    FixedGenerators,
+    JubjubEngine,
+    JubjubParams,
+    Unknown,
+    edwards::Point
+};
+use eddsa::{PrivateKey, PublicKey};
+
+#[derive(Clone)]
+pub struct Ciphertext<E: JubjubEngine> {
+    /// r * G
+    pub c1: Point<E, Unknown>,
+    /// M + r * PK
+    pub c2: Point<E, Unknown>
+}
+
+/// Encodes a number as the multiple of the generator
+pub fn encode_message<E: JubjubEngine>(
+    m: u64,
+    p_g: FixedGenerators,
+    params: &E::Params
+) -> Point<E, Unknown>
+{
+    let m = E::Fs::from_repr(<E::Fs as PrimeField>::Repr::from(m)).expect("u64 fits in the scalar field");
+
+    params.generator(p_g).mul(m, params).into()
+}
+
+/// Finds a number up to `max` encoded in the point
+/// by trying all of them, so it's only suitable for
+/// small numbers, e.g. vote counts
+pub fn decode_message<E: JubjubEngine>(
+    p: &Point<E, Unknown>,
+    max: u64,
+    p_g: FixedGenerators,
+    params: &E::Params
+) -> Option<u64>
+{
+    let generator: Point<E, Unknown> = params.generator(p_g).clone().into();
+    let mut acc = Point::zero();
+
+    for m in 0..(max + 1) {
+        if acc == *p {
+            return Some(m);
+        }
+
+        acc = acc.add(&generator, params);
+    }
+
+    None
+}
+
+impl<E: JubjubEngine> Ciphertext<E> {
+    pub fn encrypt<R: Rng>(
+        msg: &Point<E, Unknown>,
+        pk: &PublicKey<E>,
+        rng: &mut R,
+        p_g: FixedGenerators,
+        params: &E::Params
+    ) -> Self
+    {
+        let r = E::Fs::rand(rng);
+
+        Self::encrypt_with_randomness(msg, pk, r, p_g, params)
+    }
+
+    /// Encryption with the given randomness, that should
+    /// never be reused for the same public key
+    pub fn encrypt_with_randomness(
+        msg: &Point<E, Unknown>,
+        pk: &PublicKey<E>,
+        r: E::Fs,
+        p_g: FixedGenerators,
+        params: &E::Params
+    ) -> Self
+    {
+        let c1 = params.generator(p_g).mul(r, params).into();
+        let c2 = pk.0.mul(r, params).add(msg, params);
+
+        Ciphertext {
+            c1: c1,
+            c2: c2
+        }
+    }
+
+    pub fn decrypt(&self, sk: &PrivateKey<E>, params: &E::Params) -> Point<E, Unknown> {
+        // M = c2 - sk * c1
+        self.c2.add(&self.c1.mul(sk.0, params).negate(), params)
+    }
+
+    /// Returns an encryption of the sum of the messages
+    pub fn add(&self, other: &Self, params: &E::Params) -> Self {
+        Ciphertext {
+            c1: self.c1.add(&other.c1, params),
+            c2: self.c2.add(&other.c2, params)
+        }
+    }
+
+    /// Returns another encryption of the same message
+    pub fn rerandomize<R: Rng>(
+        &self,
+        pk: &PublicKey<E>,
+        rng: &mut R,
+        p_g: FixedGenerators,
+        params: &E::Params
+    ) -> Self
+    {
+        self.add(&Self::encrypt(&Point::zero(), pk, rng, p_g, params), params)
+    }
+}
+
+/// Derives the shared point `sk * PK` of Diffie-Hellman key agreement.
+/// The public key is expected to be checked to be in the prime order
+/// subgroup, otherwise the result may leak bits of the private key.
+pub fn ecdh<E: JubjubEngine>(
+    sk: &PrivateKey<E>,
+    pk: &PublicKey<E>,
+    params: &E::Params
+) -> Point<E, Unknown>
+{
+    pk.0.mul(sk.0, params)
+}
+
+#[cfg(test)]
+mod baby_tests {
+    use pairing::bn256::Bn256;
+    use rand::{XorShiftRng, SeedableRng, Rng};
+    use alt_babyjubjub::AltJubjubBn256;
+
+    use super::*;
+
+    #[test]
+    fn encryption_roundtrip() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        for _ in 0..10 {
+            let sk = PrivateKey::<Bn256>(rng.gen());
+            let pk = PublicKey::from_private(&sk, p_g, params);
+            let msg = Point::rand(rng, params);
+
+            let c = Ciphertext::encrypt(&msg, &pk, rng, p_g, params);
+            assert!(c.decrypt(&sk, params) == msg);
+
+            let c2 = c.rerandomize(&pk, rng, p_g, params);
+            assert!(c2.c1 != c.c1);
+            assert!(c2.decrypt(&sk, params) == msg);
+
+            let wrong_sk = PrivateKey::<Bn256>(rng.gen());
+            assert!(c.decrypt(&wrong_sk, params) != msg);
+        }
+    }
+
+    #[test]
+    fn homomorphic_tally() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        let sk = PrivateKey::<Bn256>(rng.gen());
+        let pk = PublicKey::from_private(&sk, p_g, params);
+
+        let votes: Vec<u64> = (0..20).map(|_| rng.gen::<u64>() % 2).collect();
+
+        let tally = votes.iter().map(|v| {
+            Ciphertext::encrypt(&encode_message(*v, p_g, params), &pk, rng, p_g, params)
+        }).fold(
+            Ciphertext { c1: Point::zero(), c2: Point::zero() },
+            |acc, c| acc.add(&c, params)
+        );
+
+        let expected = votes.iter().sum::<u64>();
+        let decrypted = tally.decrypt(&sk, params);
+
+        assert_eq!(decode_message(&decrypted, 20, p_g, params), Some(expected));
+        if expected > 0 {
+            assert_eq!(decode_message(&decrypted, expected - 1, p_g, params), None);
+        }
+    }
+
+    #[test]
+    fn shared_secret() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &AltJubjubBn256::new();
+
+        let sk_a = PrivateKey::<Bn256>(rng.gen());
+        let pk_a = PublicKey::from_private(&sk_a, p_g, params);
+        let sk_b = PrivateKey::<Bn256>(rng.gen());
+        let pk_b = PublicKey::from_private(&sk_b, p_g, params);
+
+        assert!(ecdh(&sk_a, &pk_b, params) == ecdh(&sk_b, &pk_a, params));
+        assert!(ecdh(&sk_a, &pk_a, params) != ecdh(&sk_b, &pk_a, params));
+    }
+}
@@ -1,5 +1,6 @@
 use jubjub::{
     JubjubEngine,
+    JubjubParams,
     PrimeOrder,
     edwards
 };
@@ -11,19 +12,20 @@ use ff::{
 use blake2_rfc::blake2s::Blake2s;
 use constants;
 
-/// Produces a random point in the Jubjub curve.
+/// Produces a random point in the curve defined by `params`.
 /// The point is guaranteed to be prime order
 /// and not the identity.
-pub fn group_hash<E: JubjubEngine>(
+pub fn group_hash<E: JubjubEngine, P: JubjubParams<E>>(
     tag: &[u8],
     personalization: &[u8],
-    params: &E::Params
+    params: &P
 ) -> Option<edwards::Point<E, PrimeOrder>>
 {
     assert_eq!(personalization.len(), 8);
 
-    // Check to see that scalar field is 255 bits
-    assert!(E::Fr::NUM_BITS == 255);
+    // Check to see that scalar field is at most 255 bits,
+    // so there is space for the sign bit of a point encoding
+    assert!(E::Fr::NUM_BITS <= 255);
 
     let mut h = Blake2s::with_params(32, &[], &[], personalization);
     h.update(constants::GH_FIRST_BLOCK);
@@ -44,38 +46,3 @@ pub fn group_hash<E: JubjubEngine>(
         Err(_) => None
     }
 }
-
-/// Produces a random point in the Alt Baby Jubjub curve.
-/// The point is guaranteed to be prime order
-/// and not the identity.
-pub fn baby_group_hash<E: JubjubEngine>(
-    tag: &[u8],
-    personalization: &[u8],
-    params: &E::Params
-) -> Option<edwards::Point<E, PrimeOrder>>
-{
-    assert_eq!(personalization.len(), 8);
-
-    // Check to see that scalar field is 255 bits
-    assert!(E::Fr::NUM_BITS == 254);
-
-    let mut h = Blake2s::with_params(32, &[], &[], personalization);
-    h.update(constants::GH_FIRST_BLOCK);
-    h.update(tag);
-    let h = h.finalize().as_ref().to_vec();
-    assert!(h.len() == 32);
-
-    match edwards::Point::<E, _>::read(&h[..], params) {
-        Ok(p) => {
-            let p = p.mul_by_cofactor(params);
-
-            if p != edwards::Point::zero() {
-                Some(p)
-            } else {
-                None
-            }
-        },
-        Err(_) => None
-    }
-}
-
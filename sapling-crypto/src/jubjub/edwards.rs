@@ -94,9 +94,9 @@ impl<E: JubjubEngine, Subgroup> PartialEq for Point<E, Subgroup> {
 }
 
 impl<E: JubjubEngine> Point<E, Unknown> {
-    pub fn read<R: Read>(
+    pub fn read<R: Read, P: JubjubParams<E>>(
         reader: R,
-        params: &E::Params
+        params: &P
     ) -> io::Result<Self>
     {
         let mut y_repr = <E::Fr as PrimeField>::Repr::default();
@@ -120,10 +120,10 @@ impl<E: JubjubEngine> Point<E, Unknown> {
         }
     }
 
-    pub fn from_xy(x: E::Fr, y: E::Fr, params: &E::Params) -> Option<Self>
+    pub fn from_xy<P: JubjubParams<E>>(x: E::Fr, y: E::Fr, params: &P) -> Option<Self>
     {
         // check that a point is on curve
-        // y^2 - x^2 = 1 + d * x^2 * y^2
+        // a * x^2 + y^2 = 1 + d * x^2 * y^2
 
         // tmp0 = x^2
         let mut tmp0 = x;
@@ -133,8 +133,9 @@ impl<E: JubjubEngine> Point<E, Unknown> {
         let mut tmp1 = y;
         tmp1.square();
 
-        let mut lhs = tmp1;
-        lhs.sub_assign(&tmp0);
+        let mut lhs = tmp0;
+        lhs.mul_assign(params.edwards_a());
+        lhs.add_assign(&tmp1);
 
         let mut rhs = tmp0;
         rhs.mul_assign(&tmp1);
@@ -158,27 +159,27 @@ impl<E: JubjubEngine> Point<E, Unknown> {
         })
     }
 
-    pub fn get_for_y(y: E::Fr, sign: bool, params: &E::Params) -> Option<Self>
+    pub fn get_for_y<P: JubjubParams<E>>(y: E::Fr, sign: bool, params: &P) -> Option<Self>
     {
-        // Given a y on the curve, x^2 = (y^2 - 1) / (dy^2 + 1)
+        // Given a y on the curve, x^2 = (y^2 - 1) / (dy^2 - a)
         // This is defined for all valid y-coordinates,
-        // as dy^2 + 1 = 0 has no solution in Fr.
+        // as dy^2 - a = 0 has no solution in Fr.
 
         // tmp1 = y^2
         let mut tmp1 = y;
         tmp1.square();
 
-        // tmp2 = (y^2 * d) + 1
+        // tmp2 = (y^2 * d) - a
         let mut tmp2 = tmp1;
         tmp2.mul_assign(params.edwards_d());
-        tmp2.add_assign(&E::Fr::one());
+        tmp2.sub_assign(params.edwards_a());
 
         // tmp1 = y^2 - 1
         tmp1.sub_assign(&E::Fr::one());
 
         match tmp2.inverse() {
             Some(tmp2) => {
-                // tmp1 = (y^2 - 1) / (dy^2 + 1)
+                // tmp1 = (y^2 - 1) / (dy^2 - a)
                 tmp1.mul_assign(&tmp2);
 
                 match tmp1.sqrt() {
@@ -218,7 +219,7 @@ impl<E: JubjubEngine> Point<E, Unknown> {
 
     /// This guarantees the point is in the prime order subgroup
     #[must_use]
-    pub fn mul_by_cofactor(&self, params: &E::Params) -> Point<E, PrimeOrder>
+    pub fn mul_by_cofactor<P: JubjubParams<E>>(&self, params: &P) -> Point<E, PrimeOrder>
     {
         let tmp = self.double(params)
                       .double(params)
@@ -227,7 +228,7 @@ impl<E: JubjubEngine> Point<E, Unknown> {
         convert_subgroup(&tmp)
     }
 
-    pub fn rand<R: Rng>(rng: &mut R, params: &E::Params) -> Self
+    pub fn rand<R: Rng, P: JubjubParams<E>>(rng: &mut R, params: &P) -> Self
     {
         loop {
             let y: E::Fr = rng.gen();
@@ -260,9 +261,9 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     /// Convert from a Montgomery point
-    pub fn from_montgomery(
+    pub fn from_montgomery<P: JubjubParams<E>>(
         m: &montgomery::Point<E, Subgroup>,
-        params: &E::Params
+        params: &P
     ) -> Self
     {
         match m.into_xy() {
@@ -370,7 +371,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
 
     /// Attempts to cast this as a prime order element, failing if it's
     /// not in the prime order subgroup.
-    pub fn as_prime_order(&self, params: &E::Params) -> Option<Point<E, PrimeOrder>> {
+    pub fn as_prime_order<P: JubjubParams<E>>(&self, params: &P) -> Option<Point<E, PrimeOrder>> {
         if self.mul(E::Fs::char(), params) == Point::zero() {
             Some(convert_subgroup(self))
         } else {
@@ -412,7 +413,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn double(&self, _: &E::Params) -> Self {
+    pub fn double<P: JubjubParams<E>>(&self, params: &P) -> Self {
         // See "Twisted Edwards Curves Revisited"
         //     Huseyin Hisil, Kenneth Koon-Ho Wong, Gary Carter, and Ed Dawson
         //     Section 3.3
@@ -432,15 +433,14 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
         c.double();
 
         // D = a*A
-        //   = -A
         let mut d = a;
-        d.negate();
+        d.mul_assign(params.edwards_a());
 
         // E = (X1+Y1)^2 - A - B
         let mut e = self.x;
         e.add_assign(&self.y);
         e.square();
-        e.add_assign(&d); // -A = D
+        e.sub_assign(&a);
         e.sub_assign(&b);
 
         // G = D+B
@@ -481,7 +481,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn add(&self, other: &Self, params: &E::Params) -> Self
+    pub fn add<P: JubjubParams<E>>(&self, other: &Self, params: &P) -> Self
     {
         // See "Twisted Edwards Curves Revisited"
         //     Huseyin Hisil, Kenneth Koon-Ho Wong, Gary Carter, and Ed Dawson
@@ -505,12 +505,12 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
         d.mul_assign(&other.z);
 
         // H = B - aA
-        //   = B + A
-        let mut h = b;
-        h.add_assign(&a);
+        let mut h = a;
+        h.mul_assign(params.edwards_a());
+        h.negate();
+        h.add_assign(&b);
 
         // E = (x1 + y1) * (x2 + y2) - A - B
-        //   = (x1 + y1) * (x2 + y2) - H
         let mut e = self.x;
         e.add_assign(&self.y);
         {
@@ -518,7 +518,8 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
             tmp.add_assign(&other.y);
             e.mul_assign(&tmp);
         }
-        e.sub_assign(&h);
+        e.sub_assign(&a);
+        e.sub_assign(&b);
 
         // F = D - C
         let mut f = d;
@@ -554,10 +555,10 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn mul<S: Into<<E::Fs as PrimeField>::Repr>>(
+    pub fn mul<S: Into<<E::Fs as PrimeField>::Repr>, P: JubjubParams<E>>(
         &self,
         scalar: S,
-        params: &E::Params
+        params: &P
     ) -> Self
     {
         // Standard double-and-add scalar multiplication
@@ -16,6 +16,10 @@
 //! It is a complete twisted Edwards curve, so the equivalence with
 //! the Montgomery curve forms a group isomorphism, allowing points
 //! to be freely converted between the two forms.
+//!
+//! Implementations of the `edwards` and `montgomery` forms are generic
+//! over the curve constants of `JubjubParams` and are shared with
+//! (alternative) Baby Jubjub, which only define their parameters.
 
 use pairing::{
     Engine,
@@ -36,7 +40,8 @@ use pairing::bls12_381::{
     Fr
 };
 
-/// This is an implementation of the twisted Edwards Jubjub curve.
+/// This is an implementation of twisted Edwards curves
+/// of the form `ax^2 + y^2 = 1 + dx^2y^2`.
 pub mod edwards;
 
 /// This is an implementation of the birationally equivalent
@@ -104,8 +109,13 @@ pub trait JubjubEngine: Engine {
 }
 
 /// The pre-computed parameters for Jubjub, including curve
-/// constants and various limits and window tables.
+/// constants and various limits and window tables. Curve
+/// arithmetic and gadgets take these parameters explicitly,
+/// so one engine can be used with several curves sharing
+/// the scalar field.
 pub trait JubjubParams<E: JubjubEngine>: Sized {
+    /// The `a` constant of the twisted Edwards curve.
+    fn edwards_a(&self) -> &E::Fr;
     /// The `d` constant of the twisted Edwards curve.
     fn edwards_d(&self) -> &E::Fr;
     /// The `A` constant of the birationally equivalent Montgomery curve.
@@ -143,6 +153,7 @@ impl JubjubEngine for Bls12 {
 }
 
 pub struct JubjubBls12 {
+    edwards_a: Fr,
     edwards_d: Fr,
     montgomery_a: Fr,
     montgomery_2a: Fr,
@@ -157,6 +168,7 @@ pub struct JubjubBls12 {
 }
 
 impl JubjubParams<Bls12> for JubjubBls12 {
+    fn edwards_a(&self) -> &Fr { &self.edwards_a }
     fn edwards_d(&self) -> &Fr { &self.edwards_d }
     fn montgomery_a(&self) -> &Fr { &self.montgomery_a }
     fn montgomery_2a(&self) -> &Fr { &self.montgomery_2a }
@@ -195,7 +207,12 @@ impl JubjubBls12 {
         let mut montgomery_2a = montgomery_a;
         montgomery_2a.double();
 
+        let mut edwards_a = Fr::one();
+        edwards_a.negate();
+
         let mut tmp_params = JubjubBls12 {
+            // a = -1
+            edwards_a: edwards_a,
             // d = -(10240/10241)
             edwards_d: Fr::from_str("19257038036680949359750312669786877991949435402254120286184196891950884077233").unwrap(),
             // A = 40962
@@ -414,7 +431,7 @@ impl JubjubBls12 {
 fn test_jubjub_bls12() {
     let params = JubjubBls12::new();
 
-    tests::test_suite::<Bls12>(&params);
+    tests::test_suite::<Bls12, _>(&params);
 
     let test_repr = hex!("9d12b88b08dcbef8a11ee0712d94cb236ee2f4ca17317075bfafc82ce3139d31");
     let p = edwards::Point::<Bls12, _>::read(&test_repr[..], &params).unwrap();
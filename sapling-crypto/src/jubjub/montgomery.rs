@@ -66,7 +66,7 @@ impl<E: JubjubEngine, Subgroup> PartialEq for Point<E, Subgroup> {
 }
 
 impl<E: JubjubEngine> Point<E, Unknown> {
-    pub fn get_for_x(x: E::Fr, sign: bool, params: &E::Params) -> Option<Self>
+    pub fn get_for_x<P: JubjubParams<E>>(x: E::Fr, sign: bool, params: &P) -> Option<Self>
     {
         // Given an x on the curve, y = sqrt(x^3 + A*x^2 + x)
 
@@ -98,7 +98,7 @@ impl<E: JubjubEngine> Point<E, Unknown> {
 
     /// This guarantees the point is in the prime order subgroup
     #[must_use]
-    pub fn mul_by_cofactor(&self, params: &E::Params) -> Point<E, PrimeOrder>
+    pub fn mul_by_cofactor<P: JubjubParams<E>>(&self, params: &P) -> Point<E, PrimeOrder>
     {
         let tmp = self.double(params)
                       .double(params)
@@ -107,7 +107,7 @@ impl<E: JubjubEngine> Point<E, Unknown> {
         convert_subgroup(&tmp)
     }
 
-    pub fn rand<R: Rng>(rng: &mut R, params: &E::Params) -> Self
+    pub fn rand<R: Rng, P: JubjubParams<E>>(rng: &mut R, params: &P) -> Self
     {
         loop {
             let x: E::Fr = rng.gen();
@@ -124,9 +124,9 @@ impl<E: JubjubEngine> Point<E, Unknown> {
 
 impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     /// Convert from an Edwards point
-    pub fn from_edwards(
+    pub fn from_edwards<P: JubjubParams<E>>(
         e: &edwards::Point<E, Subgroup>,
-        params: &E::Params
+        params: &P
     ) -> Self
     {
         let (x, y) = e.into_xy();
@@ -191,7 +191,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
 
     /// Attempts to cast this as a prime order element, failing if it's
     /// not in the prime order subgroup.
-    pub fn as_prime_order(&self, params: &E::Params) -> Option<Point<E, PrimeOrder>> {
+    pub fn as_prime_order<P: JubjubParams<E>>(&self, params: &P) -> Option<Point<E, PrimeOrder>> {
         if self.mul(E::Fs::char(), params) == Point::zero() {
             Some(convert_subgroup(self))
         } else {
@@ -227,7 +227,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn double(&self, params: &E::Params) -> Self {
+    pub fn double<P: JubjubParams<E>>(&self, params: &P) -> Self {
         if self.infinity {
             return Point::zero();
         }
@@ -284,7 +284,7 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn add(&self, other: &Self, params: &E::Params) -> Self
+    pub fn add<P: JubjubParams<E>>(&self, other: &Self, params: &P) -> Self
     {
         // This is a standard affine point addition formula
         // See 4.3.2 The group law for Weierstrass curves
@@ -335,10 +335,10 @@ impl<E: JubjubEngine, Subgroup> Point<E, Subgroup> {
     }
 
     #[must_use]
-    pub fn mul<S: Into<<E::Fs as PrimeField>::Repr>>(
+    pub fn mul<S: Into<<E::Fs as PrimeField>::Repr>, P: JubjubParams<E>>(
         &self,
         scalar: S,
-        params: &E::Params
+        params: &P
     ) -> Self
     {
         // Standard double-and-add scalar multiplication
@@ -16,17 +16,17 @@ use ff::{
 
 use rand::{XorShiftRng, SeedableRng, Rand};
 
-pub fn test_suite<E: JubjubEngine>(params: &E::Params) {
-    test_back_and_forth::<E>(params);
-    test_jubjub_params::<E>(params);
-    test_rand::<E>(params);
-    test_get_for::<E>(params);
-    test_identities::<E>(params);
-    test_addition_associativity::<E>(params);
-    test_order::<E>(params);
-    test_mul_associativity::<E>(params);
-    test_loworder::<E>(params);
-    test_read_write::<E>(params);
+pub fn test_suite<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
+    test_back_and_forth::<E, P>(params);
+    test_jubjub_params::<E, P>(params);
+    test_rand::<E, P>(params);
+    test_get_for::<E, P>(params);
+    test_identities::<E, P>(params);
+    test_addition_associativity::<E, P>(params);
+    test_order::<E, P>(params);
+    test_mul_associativity::<E, P>(params);
+    test_loworder::<E, P>(params);
+    test_read_write::<E, P>(params);
 }
 
 fn is_on_mont_curve<E: JubjubEngine, P: JubjubParams<E>>(
@@ -64,9 +64,11 @@ fn is_on_twisted_edwards_curve<E: JubjubEngine, P: JubjubParams<E>>(
     let mut y2 = y;
     y2.square();
 
-    // -x^2 + y^2
+    // a*x^2 + y^2
     let mut lhs = y2;
-    lhs.sub_assign(&x2);
+    let mut a_x2 = x2;
+    a_x2.mul_assign(params.edwards_a());
+    lhs.add_assign(&a_x2);
 
     // 1 + d x^2 y^2
     let mut rhs = y2;
@@ -77,7 +79,7 @@ fn is_on_twisted_edwards_curve<E: JubjubEngine, P: JubjubParams<E>>(
     lhs == rhs
 }
 
-fn test_loworder<E: JubjubEngine>(params: &E::Params) {
+fn test_loworder<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
     let inf = montgomery::Point::zero();
 
@@ -107,7 +109,7 @@ fn test_loworder<E: JubjubEngine>(params: &E::Params) {
     assert!(loworder_points[7] == inf);
 }
 
-fn test_mul_associativity<E: JubjubEngine>(params: &E::Params) {
+fn test_mul_associativity<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     use self::edwards::Point;
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
@@ -141,7 +143,7 @@ fn test_mul_associativity<E: JubjubEngine>(params: &E::Params) {
     }
 }
 
-fn test_order<E: JubjubEngine>(params: &E::Params) {
+fn test_order<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     use self::edwards::Point;
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
@@ -169,7 +171,7 @@ fn test_order<E: JubjubEngine>(params: &E::Params) {
     assert!(at_least_one_not_in_prime_order_subgroup);
 }
 
-fn test_addition_associativity<E: JubjubEngine>(params: &E::Params) {
+fn test_addition_associativity<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     for _ in 0..1000 {
@@ -179,7 +181,7 @@ fn test_addition_associativity<E: JubjubEngine>(params: &E::Params) {
         let b = Point::<E, _>::rand(rng, params);
         let c = Point::<E, _>::rand(rng, params);
 
-        assert!(a.add(&b, &params).add(&c, &params) == c.add(&a, &params).add(&b, &params));
+        assert!(a.add(&b, params).add(&c, params) == c.add(&a, params).add(&b, params));
     }
 
     for _ in 0..1000 {
@@ -189,25 +191,25 @@ fn test_addition_associativity<E: JubjubEngine>(params: &E::Params) {
         let b = Point::<E, _>::rand(rng, params);
         let c = Point::<E, _>::rand(rng, params);
 
-        assert!(a.add(&b, &params).add(&c, &params) == c.add(&a, &params).add(&b, &params));
+        assert!(a.add(&b, params).add(&c, params) == c.add(&a, params).add(&b, params));
     }
 }
 
-fn test_identities<E: JubjubEngine>(params: &E::Params) {
+fn test_identities<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     {
         use self::edwards::Point;
 
         let z = Point::<E, PrimeOrder>::zero();
-        assert!(z.double(&params) == z);
+        assert!(z.double(params) == z);
         assert!(z.negate() == z);
 
         for _ in 0..100 {
             let r = Point::<E, _>::rand(rng, params);
 
-            assert!(r.add(&Point::zero(), &params) == r);
-            assert!(r.add(&r.negate(), &params) == Point::zero());
+            assert!(r.add(&Point::zero(), params) == r);
+            assert!(r.add(&r.negate(), params) == Point::zero());
         }
     }
 
@@ -215,19 +217,19 @@ fn test_identities<E: JubjubEngine>(params: &E::Params) {
         use self::montgomery::Point;
 
         let z = Point::<E, PrimeOrder>::zero();
-        assert!(z.double(&params) == z);
+        assert!(z.double(params) == z);
         assert!(z.negate() == z);
 
         for _ in 0..100 {
             let r = Point::<E, _>::rand(rng, params);
 
-            assert!(r.add(&Point::zero(), &params) == r);
-            assert!(r.add(&r.negate(), &params) == Point::zero());
+            assert!(r.add(&Point::zero(), params) == r);
+            assert!(r.add(&r.negate(), params) == Point::zero());
         }
     }
 }
 
-fn test_get_for<E: JubjubEngine>(params: &E::Params) {
+fn test_get_for<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     for _ in 0..1000 {
@@ -246,7 +248,7 @@ fn test_get_for<E: JubjubEngine>(params: &E::Params) {
     }
 }
 
-fn test_read_write<E: JubjubEngine>(params: &E::Params) {
+fn test_read_write<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     for _ in 0..1000 {
@@ -261,7 +263,7 @@ fn test_read_write<E: JubjubEngine>(params: &E::Params) {
     }
 }
 
-fn test_rand<E: JubjubEngine>(params: &E::Params) {
+fn test_rand<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     for _ in 0..1000 {
@@ -280,7 +282,7 @@ fn test_rand<E: JubjubEngine>(params: &E::Params) {
     }
 }
 
-fn test_back_and_forth<E: JubjubEngine>(params: &E::Params) {
+fn test_back_and_forth<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
     let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
 
     for _ in 0..1000 {
@@ -303,10 +305,9 @@ fn test_back_and_forth<E: JubjubEngine>(params: &E::Params) {
     }
 }
 
-fn test_jubjub_params<E: JubjubEngine>(params: &E::Params) {
-    // a = -1
-    let mut a = E::Fr::one();
-    a.negate();
+pub fn test_jubjub_params<E: JubjubEngine, P: JubjubParams<E>>(params: &P) {
+    // a
+    let a = *params.edwards_a();
 
     {
         // Check that 2A is consistent with A
@@ -360,7 +361,7 @@ fn test_jubjub_params<E: JubjubEngine>(params: &E::Params) {
 
     {
         // Check the validity of the scaling factor
-        let mut tmp = a;
+        let mut tmp = *params.edwards_a();
         tmp.sub_assign(&params.edwards_d());
         tmp = tmp.inverse().unwrap();
         tmp.mul_assign(&E::Fr::from_str("4").unwrap());
@@ -368,6 +369,17 @@ fn test_jubjub_params<E: JubjubEngine>(params: &E::Params) {
         assert_eq!(&tmp, params.scale());
     }
 
+    {
+        // Check that the number of windows for fixed-base
+        // scalar multiplication is sufficient for all scalars.
+
+        assert!(params.fixed_base_chunks_per_generator() * 3 >= E::Fs::NUM_BITS as usize);
+
+        // ... and that it's *just* efficient enough.
+
+        assert!((params.fixed_base_chunks_per_generator() - 1) * 3 < E::Fs::NUM_BITS as usize);
+    }
+
     {
         // Check that the number of windows per generator
         // in the Pedersen hash does not allow for collisions
@@ -402,15 +414,4 @@ fn test_jubjub_params<E: JubjubEngine>(params: &E::Params) {
             }
         }
     }
-
-    {
-        // Check that the number of windows for fixed-base
-        // scalar multiplication is sufficient for all scalars.
-
-        assert!(params.fixed_base_chunks_per_generator() * 3 >= E::Fs::NUM_BITS as usize);
-
-        // ... and that it's *just* efficient enough.
-
-        assert!((params.fixed_base_chunks_per_generator() - 1) * 3 < E::Fs::NUM_BITS as usize);
-    }
 }
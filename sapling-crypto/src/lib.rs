@@ -17,6 +17,10 @@ extern crate hex_literal;
 #[cfg(test)]
 extern crate crypto;
 
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+
 pub mod babyjubjub;
 pub mod jubjub;
 pub mod alt_babyjubjub;
@@ -31,8 +35,14 @@ pub mod redbabyjubjub;
 pub mod redjubjub;
 pub mod baby_util;
 pub mod util;
+pub mod blake512;
 pub mod eddsa;
+pub mod mimc7;
+pub mod poseidon;
+pub mod elgamal;
+pub mod sparse_merkle_tree;
 
 extern crate serde;
 #[macro_use]
-extern crate serde_derive;
\ No newline at end of file
+extern crate serde_derive;
+extern crate serde_json;
\ No newline at end of file
@@ -0,0 +1,209 @@
+//! Poseidon hash function as implemented by circomlib for EdDSA.
+//! The state of width `t` goes through `full_rounds / 2` full rounds,
+//! `partial_rounds` partial rounds and `full_rounds / 2` full rounds,
+//! each being a round constant addition, a `x^5` S-box on all (or the
+//! first) state elements and a multiplication by a Cauchy MDS matrix.
+//! Round constants and the matrix are derived by iterated BLAKE2b of
+//! the seed "poseidon", same as circomlib does.
+
+use pairing::Engine;
+use ff::{Field, PrimeField};
+use blake2_rfc::blake2b::Blake2b;
+
+const POSEIDON_SEED: &'static str = "poseidon";
+
+pub struct PoseidonParams<E: Engine> {
+    t: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: Vec<E::Fr>,
+    mds_matrix: Vec<Vec<E::Fr>>
+}
+
+/// Interprets a little-endian byte string as an integer
+/// and reduces it modulo the field characteristic
+fn le_bytes_to_field<F: PrimeField>(bytes: &[u8]) -> F {
+    let base = F::from_str("256").unwrap();
+
+    let mut acc = F::zero();
+    for b in bytes.iter().rev() {
+        acc.mul_assign(&base);
+        acc.add_assign(&F::from_str(&b.to_string()).unwrap());
+    }
+
+    acc
+}
+
+/// Outputs `n` field elements by iterating BLAKE2b
+/// over the seed
+fn pseudo_random<F: PrimeField>(seed: &[u8], n: usize) -> Vec<F> {
+    let mut result = Vec::with_capacity(n);
+
+    let mut h = Blake2b::new(32);
+    h.update(seed);
+    let mut digest = h.finalize();
+
+    while result.len() < n {
+        result.push(le_bytes_to_field(digest.as_bytes()));
+
+        let mut h = Blake2b::new(32);
+        h.update(digest.as_bytes());
+        digest = h.finalize();
+    }
+
+    result
+}
+
+impl<E: Engine> PoseidonParams<E> {
+    pub fn new(t: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        assert!(t > 1);
+        assert!(full_rounds % 2 == 0);
+
+        let round_constants = pseudo_random::<E::Fr>(
+            format!("{}_constants", POSEIDON_SEED).as_bytes(),
+            full_rounds + partial_rounds
+        );
+
+        // Cauchy matrix M[i][j] = 1 / (x_i - y_j) requires
+        // all of the x_i and y_j to be different
+        let mut nonce = 0;
+        let points = loop {
+            let points = pseudo_random::<E::Fr>(
+                format!("{}_matrix_{:04}", POSEIDON_SEED, nonce).as_bytes(),
+                t * 2
+            );
+
+            let all_different = points.iter().enumerate().all(|(i, p1)| {
+                points.iter().skip(i + 1).all(|p2| p1 != p2)
+            });
+
+            if all_different {
+                break points;
+            }
+
+            nonce += 1;
+        };
+
+        let mds_matrix = (0..t).map(|i| {
+            (0..t).map(|j| {
+                let mut tmp = points[i];
+                tmp.sub_assign(&points[t + j]);
+
+                tmp.inverse().expect("points are different")
+            }).collect()
+        }).collect();
+
+        PoseidonParams {
+            t: t,
+            full_rounds: full_rounds,
+            partial_rounds: partial_rounds,
+            round_constants: round_constants,
+            mds_matrix: mds_matrix
+        }
+    }
+
+    /// Parameters used by circomlib's EdDSA-Poseidon
+    pub fn circomlib() -> Self {
+        Self::new(6, 8, 57)
+    }
+
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    pub fn full_rounds(&self) -> usize {
+        self.full_rounds
+    }
+
+    pub fn partial_rounds(&self) -> usize {
+        self.partial_rounds
+    }
+
+    pub fn round_constants(&self) -> &[E::Fr] {
+        &self.round_constants
+    }
+
+    pub fn mds_matrix(&self) -> &[Vec<E::Fr>] {
+        &self.mds_matrix
+    }
+
+    /// Returns true if all of the state elements are
+    /// passed through the S-box in this round
+    pub fn is_full_round(&self, round: usize) -> bool {
+        round < self.full_rounds / 2 || round >= self.full_rounds / 2 + self.partial_rounds
+    }
+}
+
+fn pow5<F: Field>(x: &F) -> F {
+    let mut x4 = *x;
+    x4.square();
+    x4.square();
+    x4.mul_assign(x);
+
+    x4
+}
+
+/// Hashes up to `t` field elements. Missing inputs are set to zero,
+/// and the first element of the final state is the output.
+pub fn poseidon_hash<E: Engine>(
+    inputs: &[E::Fr],
+    params: &PoseidonParams<E>
+) -> E::Fr
+{
+    assert!(inputs.len() > 0);
+    assert!(inputs.len() <= params.t());
+
+    let mut state = inputs.to_vec();
+    state.resize(params.t(), E::Fr::zero());
+
+    for (round, c) in params.round_constants().iter().enumerate() {
+        for s in state.iter_mut() {
+            s.add_assign(c);
+        }
+
+        if params.is_full_round(round) {
+            for s in state.iter_mut() {
+                *s = pow5(s);
+            }
+        } else {
+            state[0] = pow5(&state[0]);
+        }
+
+        state = params.mds_matrix().iter().map(|row| {
+            let mut acc = E::Fr::zero();
+            for (m, s) in row.iter().zip(state.iter()) {
+                let mut tmp = *m;
+                tmp.mul_assign(s);
+                acc.add_assign(&tmp);
+            }
+
+            acc
+        }).collect();
+    }
+
+    state[0]
+}
+
+#[cfg(test)]
+mod test {
+    use pairing::bn256::{Bn256, Fr};
+    use ff::PrimeField;
+    use super::*;
+
+    #[test]
+    fn test_poseidon_vectors() {
+        let params = PoseidonParams::<Bn256>::circomlib();
+
+        let inputs: Vec<Fr> = (1..6).map(|i| Fr::from_str(&i.to_string()).unwrap()).collect();
+
+        assert_eq!(
+            poseidon_hash(&inputs[0..2], &params),
+            Fr::from_str("12242166908188651009877250812424843524687801523336557272219921456462821518061").unwrap()
+        );
+
+        assert_eq!(
+            poseidon_hash(&inputs, &params),
+            Fr::from_str("20988307633319688150948164954996290879952759954468093738436579145167809963446").unwrap()
+        );
+    }
+}
@@ -129,7 +129,7 @@ impl Diversifier {
         params: &E::Params
     ) -> Option<edwards::Point<E, PrimeOrder>>
     {
-        group_hash::<E>(&self.0, constants::KEY_DIVERSIFICATION_PERSONALIZATION, params)
+        group_hash::<E, _>(&self.0, constants::KEY_DIVERSIFICATION_PERSONALIZATION, params)
     }
 }
 
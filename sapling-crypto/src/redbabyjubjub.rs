@@ -1,213 +1,14 @@
-//! Implementation of RedJubjub, a specialization of RedDSA to the Jubjub curve.
-//! See section 5.4.6 of the Sapling protocol specification.
+//! RedDSA on Baby Jubjub is the same as RedJubjub,
+//! given `babyjubjub::JubjubBn256` as parameters.
 
-use ff::{Field, PrimeField, PrimeFieldRepr};
-use rand::{Rng, Rand};
-use std::io::{self, Read, Write};
-
-use babyjubjub::{FixedGenerators, JubjubEngine, JubjubParams, Unknown, edwards::Point};
-use baby_util::{hash_to_scalar};
-
-fn read_scalar<E: JubjubEngine, R: Read>(reader: R) -> io::Result<E::Fs> {
-    let mut s_repr = <E::Fs as PrimeField>::Repr::default();
-    s_repr.read_le(reader)?;
-
-    match E::Fs::from_repr(s_repr) {
-        Ok(s) => Ok(s),
-        Err(_) => Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "scalar is not in field",
-        )),
-    }
-}
-
-fn write_scalar<E: JubjubEngine, W: Write>(s: &E::Fs, writer: W) -> io::Result<()> {
-    s.into_repr().write_le(writer)
-}
-
-fn h_star<E: JubjubEngine>(a: &[u8], b: &[u8]) -> E::Fs {
-    hash_to_scalar::<E>(b"Zcash_RedJubjubH", a, b)
-}
-
-#[derive(Copy, Clone)]
-pub struct Signature {
-    rbar: [u8; 32],
-    sbar: [u8; 32],
-}
-
-pub struct PrivateKey<E: JubjubEngine>(pub E::Fs);
-
-pub struct PublicKey<E: JubjubEngine>(pub Point<E, Unknown>);
-
-impl Signature {
-    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
-        let mut rbar = [0u8; 32];
-        let mut sbar = [0u8; 32];
-        reader.read_exact(&mut rbar)?;
-        reader.read_exact(&mut sbar)?;
-        Ok(Signature { rbar, sbar })
-    }
-
-    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
-        writer.write_all(&self.rbar)?;
-        writer.write_all(&self.sbar)
-    }
-}
-
-impl<E: JubjubEngine> PrivateKey<E> {
-    pub fn randomize(&self, alpha: E::Fs) -> Self {
-        let mut tmp = self.0;
-        tmp.add_assign(&alpha);
-        PrivateKey(tmp)
-    }
-
-    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
-        let pk = read_scalar::<E, R>(reader)?;
-        Ok(PrivateKey(pk))
-    }
-
-    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
-        write_scalar::<E, W>(&self.0, writer)
-    }
-
-    pub fn sign<R: Rng>(
-        &self,
-        msg: &[u8],
-        rng: &mut R,
-        p_g: FixedGenerators,
-        params: &E::Params,
-    ) -> Signature {
-        // T = (l_H + 128) bits of randomness
-        // For H*, l_H = 512 bits
-        let mut t = [0u8; 80];
-        rng.fill_bytes(&mut t[..]);
-
-        // r = H*(T || M)
-        let r = h_star::<E>(&t[..], msg);
-
-        // R = r . P_G
-        let r_g = params.generator(p_g).mul(r, params);
-        let mut rbar = [0u8; 32];
-        r_g.write(&mut rbar[..])
-            .expect("Jubjub points should serialize to 32 bytes");
-
-        // S = r + H*(Rbar || M) . sk
-        let mut s = h_star::<E>(&rbar[..], msg);
-        s.mul_assign(&self.0);
-        s.add_assign(&r);
-        let mut sbar = [0u8; 32];
-        write_scalar::<E, &mut [u8]>(&s, &mut sbar[..])
-            .expect("Jubjub scalars should serialize to 32 bytes");
-
-        Signature { rbar, sbar }
-    }
-}
-
-impl<E: JubjubEngine> PublicKey<E> {
-    pub fn from_private(privkey: &PrivateKey<E>, p_g: FixedGenerators, params: &E::Params) -> Self {
-        let res = params.generator(p_g).mul(privkey.0, params).into();
-        PublicKey(res)
-    }
-
-    pub fn randomize(&self, alpha: E::Fs, p_g: FixedGenerators, params: &E::Params) -> Self {
-        let res: Point<E, Unknown> = params.generator(p_g).mul(alpha, params).into();
-        let res = res.add(&self.0, params);
-        PublicKey(res)
-    }
-
-    pub fn read<R: Read>(reader: R, params: &E::Params) -> io::Result<Self> {
-        let p = Point::read(reader, params)?;
-        Ok(PublicKey(p))
-    }
-
-    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
-        self.0.write(writer)
-    }
-
-    pub fn verify(
-        &self,
-        msg: &[u8],
-        sig: &Signature,
-        p_g: FixedGenerators,
-        params: &E::Params,
-    ) -> bool {
-        // c = H*(Rbar || M)
-        let c = h_star::<E>(&sig.rbar[..], msg);
-
-        // Signature checks:
-        // R != invalid
-        let r = match Point::read(&sig.rbar[..], params) {
-            Ok(r) => r,
-            Err(_) => return false,
-        };
-        // S < order(G)
-        // (E::Fs guarantees its representation is in the field)
-        let s = match read_scalar::<E, &[u8]>(&sig.sbar[..]) {
-            Ok(s) => s,
-            Err(_) => return false,
-        };
-        // 0 = h_G(-S . P_G + R + c . vk)
-        self.0.mul(c, params).add(&r, params).add(
-            &params.generator(p_g).mul(s, params).negate().into(),
-            params
-        ).mul_by_cofactor(params).eq(&Point::zero())
-    }
-}
-
-pub struct BatchEntry<'a, E: JubjubEngine> {
-    vk: PublicKey<E>,
-    msg: &'a [u8],
-    sig: Signature,
-}
-
-// TODO: #82: This is a naive implementation currently,
-// and doesn't use multiexp.
-pub fn batch_verify<'a, E: JubjubEngine, R: Rng>(
-    rng: &mut R,
-    batch: &[BatchEntry<'a, E>],
-    p_g: FixedGenerators,
-    params: &E::Params,
-) -> bool
-{
-    let mut acc = Point::<E, Unknown>::zero();
-
-    for entry in batch {
-        let mut r = match Point::<E, Unknown>::read(&entry.sig.rbar[..], params) {
-            Ok(r) => r,
-            Err(_) => return false,
-        };
-        let mut s = match read_scalar::<E, &[u8]>(&entry.sig.sbar[..]) {
-            Ok(s) => s,
-            Err(_) => return false,
-        };
-
-        let mut c = h_star::<E>(&entry.sig.rbar[..], entry.msg);
-
-        let z = E::Fs::rand(rng);
-
-        s.mul_assign(&z);
-        s.negate();
-
-        r = r.mul(z, params);
-
-        c.mul_assign(&z);
-
-        acc = acc.add(&r, params);
-        acc = acc.add(&entry.vk.0.mul(c, params), params);
-        acc = acc.add(&params.generator(p_g).mul(s, params).into(), params);
-    }
-
-    acc = acc.mul_by_cofactor(params).into();
-
-    acc.eq(&Point::zero())
-}
+pub use redjubjub::*;
 
 #[cfg(test)]
 mod tests {
     use pairing::bn256::Bn256;
-    use rand::thread_rng;
-
-    use babyjubjub::{JubjubBn256, fs::Fs, edwards};
+    use rand::{thread_rng, Rng};
+    use ff::PrimeField;
+    use babyjubjub::{JubjubBn256, FixedGenerators, fs::Fs, edwards};
 
     use super::*;
 
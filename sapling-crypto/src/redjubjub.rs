@@ -71,12 +71,12 @@ impl<E: JubjubEngine> PrivateKey<E> {
         write_scalar::<E, W>(&self.0, writer)
     }
 
-    pub fn sign<R: Rng>(
+    pub fn sign<R: Rng, P: JubjubParams<E>>(
         &self,
         msg: &[u8],
         rng: &mut R,
         p_g: FixedGenerators,
-        params: &E::Params,
+        params: &P,
     ) -> Signature {
         // T = (l_H + 128) bits of randomness
         // For H*, l_H = 512 bits
@@ -105,18 +105,18 @@ impl<E: JubjubEngine> PrivateKey<E> {
 }
 
 impl<E: JubjubEngine> PublicKey<E> {
-    pub fn from_private(privkey: &PrivateKey<E>, p_g: FixedGenerators, params: &E::Params) -> Self {
+    pub fn from_private<P: JubjubParams<E>>(privkey: &PrivateKey<E>, p_g: FixedGenerators, params: &P) -> Self {
         let res = params.generator(p_g).mul(privkey.0, params).into();
         PublicKey(res)
     }
 
-    pub fn randomize(&self, alpha: E::Fs, p_g: FixedGenerators, params: &E::Params) -> Self {
+    pub fn randomize<P: JubjubParams<E>>(&self, alpha: E::Fs, p_g: FixedGenerators, params: &P) -> Self {
         let res: Point<E, Unknown> = params.generator(p_g).mul(alpha, params).into();
         let res = res.add(&self.0, params);
         PublicKey(res)
     }
 
-    pub fn read<R: Read>(reader: R, params: &E::Params) -> io::Result<Self> {
+    pub fn read<R: Read, P: JubjubParams<E>>(reader: R, params: &P) -> io::Result<Self> {
         let p = Point::read(reader, params)?;
         Ok(PublicKey(p))
     }
@@ -125,12 +125,12 @@ impl<E: JubjubEngine> PublicKey<E> {
         self.0.write(writer)
     }
 
-    pub fn verify(
+    pub fn verify<P: JubjubParams<E>>(
         &self,
         msg: &[u8],
         sig: &Signature,
         p_g: FixedGenerators,
-        params: &E::Params,
+        params: &P,
     ) -> bool {
         // c = H*(Rbar || M)
         let c = h_star::<E>(&sig.rbar[..], msg);
@@ -163,11 +163,11 @@ pub struct BatchEntry<'a, E: JubjubEngine> {
 
 // TODO: #82: This is a naive implementation currently,
 // and doesn't use multiexp.
-pub fn batch_verify<'a, E: JubjubEngine, R: Rng>(
+pub fn batch_verify<'a, E: JubjubEngine, R: Rng, P: JubjubParams<E>>(
     rng: &mut R,
     batch: &[BatchEntry<'a, E>],
     p_g: FixedGenerators,
-    params: &E::Params,
+    params: &P,
 ) -> bool
 {
     let mut acc = Point::<E, Unknown>::zero();
@@ -0,0 +1,267 @@
+//! Sparse Merkle tree of a fixed depth, indexed by `u64` keys. Only
+//! the nodes that differ from the hash of an empty subtree are stored,
+//! so that trees with a large key space can be kept in memory.
+//! Nodes are hashed with two-input Poseidon, and empty leaves are zero,
+//! so that non-membership of a key is membership of zero.
+
+use std::collections::HashMap;
+
+use pairing::Engine;
+use ff::Field;
+
+use poseidon::{PoseidonParams, poseidon_hash};
+
+pub struct SparseMerkleTree<E: Engine> {
+    depth: usize,
+    /// Hashes of empty subtrees of every height,
+    /// starting with the empty leaf
+    defaults: Vec<E::Fr>,
+    /// Non-empty nodes by (height, index)
+    nodes: HashMap<(usize, u64), E::Fr>
+}
+
+/// Index of the node at the given height
+/// on the path from the leaf at `key`
+fn index_at(key: u64, height: usize) -> u64 {
+    if height < 64 {
+        key >> height
+    } else {
+        0
+    }
+}
+
+/// Hashes two children into their parent node
+pub fn hash_node<E: Engine>(
+    left: &E::Fr,
+    right: &E::Fr,
+    params: &PoseidonParams<E>
+) -> E::Fr
+{
+    poseidon_hash(&[*left, *right], params)
+}
+
+/// Computes the root from a leaf at the given key and its
+/// authentication path of siblings, starting from the leaf level
+pub fn compute_root<E: Engine>(
+    key: u64,
+    leaf: &E::Fr,
+    siblings: &[E::Fr],
+    params: &PoseidonParams<E>
+) -> E::Fr
+{
+    assert!(siblings.len() <= 64 && index_at(key, siblings.len()) == 0);
+
+    let mut cur = *leaf;
+
+    for (height, sibling) in siblings.iter().enumerate() {
+        cur = if (key >> height) & 1 == 1 {
+            hash_node(sibling, &cur, params)
+        } else {
+            hash_node(&cur, sibling, params)
+        };
+    }
+
+    cur
+}
+
+/// Checks that `value` is stored at `key` in the tree with the given root
+pub fn verify_membership<E: Engine>(
+    root: &E::Fr,
+    key: u64,
+    value: &E::Fr,
+    siblings: &[E::Fr],
+    params: &PoseidonParams<E>
+) -> bool
+{
+    compute_root(key, value, siblings, params) == *root
+}
+
+/// Checks that nothing is stored at `key` in the tree with the given root
+pub fn verify_non_membership<E: Engine>(
+    root: &E::Fr,
+    key: u64,
+    siblings: &[E::Fr],
+    params: &PoseidonParams<E>
+) -> bool
+{
+    verify_membership(root, key, &E::Fr::zero(), siblings, params)
+}
+
+/// Checks that replacing `old_value` at `key` by `new_value`
+/// changes the root from `old_root` to `new_root`
+pub fn verify_update<E: Engine>(
+    old_root: &E::Fr,
+    new_root: &E::Fr,
+    key: u64,
+    old_value: &E::Fr,
+    new_value: &E::Fr,
+    siblings: &[E::Fr],
+    params: &PoseidonParams<E>
+) -> bool
+{
+    verify_membership(old_root, key, old_value, siblings, params) &&
+    verify_membership(new_root, key, new_value, siblings, params)
+}
+
+impl<E: Engine> SparseMerkleTree<E> {
+    pub fn new(depth: usize, params: &PoseidonParams<E>) -> Self {
+        assert!(depth > 0 && depth <= 64);
+
+        let mut defaults = vec![E::Fr::zero()];
+        for height in 0..depth {
+            let tmp = hash_node(&defaults[height], &defaults[height], params);
+            defaults.push(tmp);
+        }
+
+        SparseMerkleTree {
+            depth: depth,
+            defaults: defaults,
+            nodes: HashMap::new()
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> E::Fr {
+        self.node(self.depth, 0)
+    }
+
+    fn node(&self, height: usize, index: u64) -> E::Fr {
+        match self.nodes.get(&(height, index)) {
+            Some(n) => *n,
+            None => self.defaults[height]
+        }
+    }
+
+    fn check_key(&self, key: u64) {
+        assert!(index_at(key, self.depth) == 0, "key is out of range");
+    }
+
+    /// Returns the value at `key`, that is zero if nothing is stored
+    pub fn get(&self, key: u64) -> E::Fr {
+        self.check_key(key);
+
+        self.node(0, key)
+    }
+
+    /// Stores `value` at `key`, and returns the authentication path
+    /// that proves the transition from the old root to the new root.
+    /// Storing zero removes the key.
+    pub fn update(&mut self, key: u64, value: E::Fr, params: &PoseidonParams<E>) -> Vec<E::Fr> {
+        let siblings = self.proof(key);
+
+        let mut cur = value;
+        for height in 0..(self.depth + 1) {
+            let index = index_at(key, height);
+
+            if cur == self.defaults[height] {
+                self.nodes.remove(&(height, index));
+            } else {
+                self.nodes.insert((height, index), cur);
+            }
+
+            if height < self.depth {
+                cur = if index & 1 == 1 {
+                    hash_node(&siblings[height], &cur, params)
+                } else {
+                    hash_node(&cur, &siblings[height], params)
+                };
+            }
+        }
+
+        siblings
+    }
+
+    /// Returns the authentication path of `key`,
+    /// starting from the sibling of the leaf
+    pub fn proof(&self, key: u64) -> Vec<E::Fr> {
+        self.check_key(key);
+
+        (0..self.depth).map(|height| {
+            self.node(height, (key >> height) ^ 1)
+        }).collect()
+    }
+
+    /// Number of non-empty leaves
+    pub fn len(&self) -> usize {
+        self.nodes.keys().filter(|&&(height, _)| height == 0).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pairing::bn256::{Bn256, Fr};
+    use rand::{XorShiftRng, SeedableRng, Rng};
+    use super::*;
+
+    fn test_params() -> PoseidonParams<Bn256> {
+        PoseidonParams::new(3, 8, 57)
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let params = test_params();
+        let tree = SparseMerkleTree::<Bn256>::new(32, &params);
+
+        let mut root = Fr::zero();
+        for _ in 0..32 {
+            root = hash_node(&root, &root, &params);
+        }
+
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.get(12345), Fr::zero());
+        assert!(verify_non_membership(&tree.root(), 12345, &tree.proof(12345), &params));
+    }
+
+    #[test]
+    fn test_updates() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = test_params();
+
+        for &depth in &[1, 5, 64] {
+            let mut tree = SparseMerkleTree::<Bn256>::new(depth, &params);
+            let empty_root = tree.root();
+
+            let keys: Vec<u64> = (0..10).map(|_| {
+                if depth == 64 { rng.gen() } else { rng.gen::<u64>() % (1 << depth) }
+            }).collect();
+
+            let mut values = HashMap::new();
+
+            for key in keys.iter() {
+                let old_root = tree.root();
+                let old_value = tree.get(*key);
+                let value: Fr = rng.gen();
+
+                let siblings = tree.update(*key, value, &params);
+                values.insert(*key, value);
+
+                assert!(verify_update(&old_root, &tree.root(), *key, &old_value, &value, &siblings, &params));
+                assert!(!verify_update(&old_root, &tree.root(), *key, &value, &value, &siblings, &params));
+            }
+
+            for (key, value) in values.iter() {
+                let siblings = tree.proof(*key);
+
+                assert_eq!(tree.get(*key), *value);
+                assert!(verify_membership(&tree.root(), *key, value, &siblings, &params));
+                assert!(!verify_non_membership(&tree.root(), *key, &siblings, &params));
+            }
+
+            let absent = (0..(1u64 << depth.min(16))).find(|k| !values.contains_key(k));
+            if let Some(key) = absent {
+                assert!(verify_non_membership(&tree.root(), key, &tree.proof(key), &params));
+            }
+
+            // Removing all of the keys gives back the empty tree
+            for key in values.keys() {
+                tree.update(*key, Fr::zero(), &params);
+            }
+
+            assert_eq!(tree.root(), empty_root);
+            assert_eq!(tree.nodes.len(), 0);
+        }
+    }
+}
@@ -20,7 +20,7 @@ use bellman::{
     Circuit,
     SynthesisError,
     ConstraintSystem,
-    groth16::{Proof, Parameters, verify_proof, create_random_proof, prepare_verifying_key, generate_random_parameters}
+    groth16::{Proof, Parameters, ConstraintMatrices, WitnessCalculator, verify_proof, create_random_proof, create_random_proof_with_assignment, prepare_verifying_key, generate_random_parameters}
 };
 
 pub struct Stopwatch {
@@ -80,12 +80,12 @@ extern "C" {
     fn finish_timer(timer_id: u32) -> u32;
 }
 
-struct DiscreteLogCircuit<'a, E: JubjubEngine> {
-    pub params: &'a E::Params,
+struct DiscreteLogCircuit<'a, E: JubjubEngine, P: 'a + JubjubParams<E>> {
+    pub params: &'a P,
     pub x: Option<E::Fr>,
 }
 
-impl<'a, E: JubjubEngine> Circuit<E> for DiscreteLogCircuit<'a, E> {
+impl<'a, E: JubjubEngine, P: JubjubParams<E>> Circuit<E> for DiscreteLogCircuit<'a, E, P> {
     fn synthesize<CS: ConstraintSystem<E>>(
         self,
         cs: &mut CS
@@ -123,13 +123,13 @@ impl<'a, E: JubjubEngine> Circuit<E> for DiscreteLogCircuit<'a, E> {
     }
 }
 
-struct TreeCircuit<'a, E: JubjubEngine> {
-    pub params: &'a E::Params,
+struct TreeCircuit<'a, E: JubjubEngine, P: 'a + JubjubParams<E>> {
+    pub params: &'a P,
     pub x: Option<E::Fr>,
 	pub depth: u8,
 }
 
-impl<'a, E: JubjubEngine> Circuit<E> for TreeCircuit<'a, E> {
+impl<'a, E: JubjubEngine, P: JubjubParams<E>> Circuit<E> for TreeCircuit<'a, E, P> {
     fn synthesize<CS: ConstraintSystem<E>>(
         self,
         cs: &mut CS
@@ -271,11 +271,21 @@ fn run_generate_tree(seed_slice: &[u32], depth: u8) -> Result<KGGenerate, Box<Er
         },
         rng
     )?;
+    let matrices = ConstraintMatrices::<Bn256>::synthesize(
+        TreeCircuit {
+            params: j_params,
+            x: None,
+            depth: depth,
+        }
+    )?;
     let millis = stopwatch.finish();
 
     let mut v = vec![];
 
+    // The constraint matrices follow the parameters,
+    // so that proving doesn't synthesize the circuit
     params.write(&mut v)?;
+    matrices.write(&mut v)?;
 
     Ok(KGGenerate {
         params: hex::encode(&v[..]),
@@ -354,23 +364,45 @@ fn run_prove_tree(seed_slice: &[u32], params: &str, x_hex: &str, depth: u8) -> R
     if params.len() == 0 {
         return Err("Params are empty. Did you generate or load params?".into())
     }
-    let de_params = Parameters::<Bn256>::read(&hex::decode(params)?[..], true)?;
+    let bytes = hex::decode(params)?;
+    let mut reader = &bytes[..];
+    let de_params = Parameters::<Bn256>::read(&mut reader, true)?;
 
     let rng = &mut ChaChaRng::from_seed(seed_slice);
     let params = &JubjubBn256::new();
 
+    // Parameters generated before the constraint
+    // matrices were stored don't include them
+    let matrices = if reader.is_empty() {
+        ConstraintMatrices::<Bn256>::synthesize(
+            TreeCircuit {
+                params: params,
+                x: None,
+                depth: depth,
+            }
+        )?
+    } else {
+        ConstraintMatrices::<Bn256>::read(&mut reader)?
+    };
+
     let x_big = BigInt::from_str_radix(x_hex, 16)?;
     let x_raw = &x_big.to_str_radix(10);
     let x = Fr::from_str(x_raw).ok_or("couldn't parse Fr")?;
 
     let stopwatch = Stopwatch::start();
 
-    let proof = create_random_proof(
+    let witness = WitnessCalculator::calculate(
         TreeCircuit {
             params: params,
             x: Some(x),
             depth: depth,
-        },
+        }
+    )?;
+
+    let proof = create_random_proof_with_assignment(
+        &matrices,
+        witness.input_assignment,
+        witness.aux_assignment,
         &de_params,
         rng
     )?;
@@ -386,7 +418,7 @@ fn run_prove_tree(seed_slice: &[u32], params: &str, x_hex: &str, depth: u8) -> R
         lhs.reverse();
         rhs.reverse();
 
-        cur = sapling_crypto::baby_pedersen_hash::pedersen_hash::<Bn256, _>(
+        cur = sapling_crypto::baby_pedersen_hash::pedersen_hash::<Bn256, _, _>(
             sapling_crypto::baby_pedersen_hash::Personalization::MerkleTree(i as usize),
             lhs.into_iter()
                .take(Fr::NUM_BITS as usize)